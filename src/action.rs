@@ -1,3 +1,4 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes};
 use serde::{Deserialize, Serialize};
 
 /// Adds an action to an intent filter.
@@ -43,4 +44,16 @@ pub struct Action {
     /// [`Intent`]: https://developer.android.com/reference/android/content/Intent
     #[yaserde(attribute, prefix = "android")]
     pub name: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Action>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
@@ -1,12 +1,15 @@
-use crate::VarOrBool;
+use crate::{VarOr, VarOrBool};
 
 use super::attribute_list::{AttributeList, VerticalBar};
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::intent_filter::IntentFilter;
 use super::layout::Layout;
 use super::meta_data::MetaData;
+use super::property::Property;
 use super::resources::{
     DrawableResource, MipmapOrDrawableResource, Resource, StringResourceOrString, StyleResource,
 };
+use super::tools::ToolsAttributes;
 use super::ui_options::UiOptions;
 use serde::{Deserialize, Serialize};
 
@@ -18,35 +21,47 @@ use serde::{Deserialize, Serialize};
 ///
 /// ## XML Syntax
 /// ```xml
-/// <activity android:allowEmbedded=["true" | "false"]
+/// <activity android:allowCrossUidActivitySwitchFromBelow=["true" | "false"]
+///           android:allowEmbedded=["true" | "false"]
 ///           android:allowTaskReparenting=["true" | "false"]
+///           android:allowUntrustedActivityEmbedding=["true" | "false"]
 ///           android:alwaysRetainTaskState=["true" | "false"]
+///           android:attributionTags="string"
 ///           android:autoRemoveFromRecents=["true" | "false"]
 ///           android:banner="drawable resource"
+///           android:canDisplayOnRemoteDevices=["true" | "false"]
 ///           android:clearTaskOnLaunch=["true" | "false"]
-///           android:colorMode=["hdr" | "wideColorGamut"]
-///           android:configChanges=["mcc", "mnc", "locale",
-///                                  "touchscreen", "keyboard", "keyboardHidden",
-///                                  "navigation", "screenLayout", "fontScale",
-///                                  "uiMode", "orientation", "density",
-///                                  "screenSize", "smallestScreenSize"]
+///           android:colorMode=["default" | "hdr" | "wideColorGamut"]
+///           android:configChanges=["colorMode", "density",
+///                                  "fontScale", "fontWeightAdjustment",
+///                                  "grammaticalGender", "keyboard",
+///                                  "keyboardHidden", "layoutDirection", "locale",
+///                                  "mcc", "mnc", "navigation", "orientation",
+///                                  "screenLayout", "screenSize",
+///                                  "smallestScreenSize", "touchscreen", "uiMode"]
 ///           android:directBootAware=["true" | "false"]
 ///           android:documentLaunchMode=["intoExisting" | "always" |
 ///                                       "none" | "never"]
 ///           android:enabled=["true" | "false"]
+///           android:enableOnBackInvokedCallback=["true" | "false"]
+///           android:enableVrMode="string"
 ///           android:excludeFromRecents=["true" | "false"]
 ///           android:exported=["true" | "false"]
 ///           android:finishOnTaskLaunch=["true" | "false"]
 ///           android:hardwareAccelerated=["true" | "false"]
 ///           android:icon="drawable resource"
 ///           android:immersive=["true" | "false"]
+///           android:inheritShowWhenLocked=["true" | "false"]
+///           android:knownActivityEmbeddingCerts="string array resource | string"
 ///           android:label="string resource"
-///           android:launchMode=["standard" | "singleTop"|
-///                               "singleTask" | "singleInstance"]
+///           android:launchMode=["standard" | "singleTop" |
+///                               "singleTask" | "singleInstance" |
+///                               "singleInstancePerTask"]
 ///           android:lockTaskMode=["normal" | "never" |
 ///                                 "if_whitelisted" | "always"]
 ///           android:maxRecents="integer"
 ///           android:maxAspectRatio="float"
+///           android:minAspectRatio="float"
 ///           android:multiprocess=["true" | "false"]
 ///           android:name="string"
 ///           android:noHistory=["true" | "false"]
@@ -54,9 +69,14 @@ use serde::{Deserialize, Serialize};
 ///           android:persistableMode=["persistRootOnly" |
 ///                                    "persistAcrossReboots" | "persistNever"]
 ///           android:permission="string"
+///           android:preferMinimalPostProcessing=["true" | "false"]
 ///           android:process="string"
 ///           android:relinquishTaskIdentity=["true" | "false"]
+///           android:requireContentUriPermissionFromCaller=["none" | "read" | "write" |
+///                                                          "readOrWrite" | "readAndWrite"]
+///           android:requiredDisplayCategory="string"
 ///           android:resizeableActivity=["true" | "false"]
+///           android:roundIcon="drawable resource"
 ///           android:screenOrientation=["unspecified" | "behind" |
 ///                                      "landscape" | "portrait" |
 ///                                      "reverseLandscape" | "reversePortrait" |
@@ -65,16 +85,19 @@ use serde::{Deserialize, Serialize};
 ///                                      "sensor" | "fullSensor"|"nosensor" |
 ///                                      "user" | "fullUser" | "locked"]
 ///           android:showForAllUsers=["true" | "false"]
+///           android:showWhenLocked=["true" | "false"]
 ///           android:stateNotNeeded=["true" | "false"]
 ///           android:supportsPictureInPicture=["true" | "false"]
 ///           android:taskAffinity="string"
 ///           android:theme="resource or theme"
+///           android:turnScreenOn=["true" | "false"]
 ///           android:uiOptions=["none" | "splitActionBarWhenNarrow"]
 ///           android:windowSoftInputMode=["stateUnspecified",
 ///                                        "stateUnchanged", "stateHidden",
 ///                                        "stateAlwaysHidden", "stateVisible",
 ///                                        "stateAlwaysVisible", "adjustUnspecified",
-///                                        "adjustResize", "adjustPan"] >
+///                                        "adjustResize", "adjustPan",
+///                                        "adjustNothing"] >
 ///     ...
 /// </activity>
 /// ```
@@ -85,6 +108,7 @@ use serde::{Deserialize, Serialize};
 /// ## Can contain
 /// * [`<intent-filter>`]
 /// * [`<meta-data>`]
+/// * [`<property>`]
 /// * [`<layout>`]
 ///
 /// ## Introduced in
@@ -95,11 +119,27 @@ use serde::{Deserialize, Serialize};
 /// [`<application>`]: crate::Application
 /// [`<intent-filter>`]: crate::IntentFilter
 /// [`<meta-data>`]: crate::MetaData
+/// [`<property>`]: crate::Property
 /// [`<layout>`]: crate::Layout
 /// [`noHistory`]: crate::Activity#structfield.no_history
 /// [`windowSoftInputMode`]: crate::Activity#structfield.window_soft_input_mode
 #[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Default, Clone)]
 pub struct Activity {
+    /// Whether the activity allows apps with a different UID whose activities are below
+    /// it in the task to start activities. This overrides the value set on
+    /// [`<application>`].
+    ///
+    /// The default value is "`true`".
+    ///
+    /// This attribute was introduced in API Level 35.
+    ///
+    /// [`<application>`]: crate::Application#structfield.allow_cross_uid_activity_switch_from_below
+    #[yaserde(
+        attribute,
+        prefix = "android",
+        rename = "allowCrossUidActivitySwitchFromBelow"
+    )]
+    pub allow_cross_uid_activity_switch_from_below: Option<VarOrBool>,
     /// Indicate that the activity can be launched as the embedded child of another
     /// activity. Particularly in the case where the child lives in a container such
     /// as a Display owned by another activity. For example, activities that are used
@@ -142,6 +182,21 @@ pub struct Activity {
     /// [`launchMode`]: crate::Activity#structfield.launch_mode
     #[yaserde(attribute, prefix = "android", rename = "allowTaskReparenting")]
     pub allow_task_reparenting: Option<VarOrBool>,
+    /// Whether the activity can be embedded in the task of another app, which isn't
+    /// trusted by default. Other apps can always embed the activity when they have the
+    /// same signing certificate, or one listed in [`knownActivityEmbeddingCerts`].
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 33.
+    ///
+    /// [`knownActivityEmbeddingCerts`]: crate::Activity#structfield.known_activity_embedding_certs
+    #[yaserde(
+        attribute,
+        prefix = "android",
+        rename = "allowUntrustedActivityEmbedding"
+    )]
+    pub allow_untrusted_activity_embedding: Option<VarOrBool>,
     /// Whether or not the state of the task that the activity is in will always be
     /// maintained by the system — "`true`" if it will be, and "`false`" if the system
     /// is allowed to reset the task to its initial state in certain situations. The
@@ -159,6 +214,14 @@ pub struct Activity {
     /// multiple open tabs) that users would not like to lose.
     #[yaserde(attribute, prefix = "android", rename = "alwaysRetainTaskState")]
     pub always_retain_task_state: Option<VarOrBool>,
+    /// The [`attribution tags`] the activity uses, separated by `|`, so that the data
+    /// accesses made by the activity are attributed to them.
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`attribution tags`]: https://developer.android.com/guide/topics/data/audit-access#attribution-tags
+    #[yaserde(attribute, prefix = "android", rename = "attributionTags")]
+    pub attribution_tags: Option<String>,
     /// Whether or not tasks launched by activities with this attribute remains in the
     /// [`overview screen`] until the last activity in the task is completed. If true, the
     /// task is automatically removed from the `overview screen.` This overrides the
@@ -191,6 +254,14 @@ pub struct Activity {
     /// [`Provide a home screen banner`]: https://developer.android.com/training/tv/start/start#banner
     #[yaserde(attribute, prefix = "android")]
     pub banner: Option<Resource<DrawableResource>>,
+    /// Whether the activity can be launched on a remote device that streams the apps of
+    /// the phone, like a companion device or a car.
+    ///
+    /// The default value is "`true`".
+    ///
+    /// This attribute was introduced in API Level 33.
+    #[yaserde(attribute, prefix = "android", rename = "canDisplayOnRemoteDevices")]
+    pub can_display_on_remote_devices: Option<VarOrBool>,
     /// Whether or not all activities will be removed from the task, except for the root
     /// activity, whenever it is re-launched from the home screen — "`true`" if the
     /// task is always stripped down to its root activity, and "`false`" if not. The
@@ -232,7 +303,7 @@ pub struct Activity {
     /// [`Enhancing Graphics with Wide Color Content`]: https://developer.android.com/training/wide-color-gamut
     /// [`SRGB`]: https://developer.android.com/reference/android/graphics/ColorSpace.Named#SRGB
     #[yaserde(attribute, prefix = "android", rename = "colorMode")]
-    pub color_mode: Option<ColorMode>,
+    pub color_mode: Option<VarOr<ColorMode>>,
     /// Lists configuration changes that the activity will handle itself. When a
     /// configuration change occurs at runtime, the activity is shut down and
     /// restarted by default, but declaring a configuration with this
@@ -282,7 +353,7 @@ pub struct Activity {
     ///
     /// [`overview screen`]: https://developer.android.com/guide/components/activities/recents
     #[yaserde(attribute, prefix = "android", rename = "documentLaunchMode")]
-    pub document_launch_mode: Option<DocumentLaunchMode>,
+    pub document_launch_mode: Option<VarOr<DocumentLaunchMode>>,
     /// Whether or not the activity can be instantiated by the system — "`true`" if it can
     /// be, and "`false`" if not.
     ///
@@ -298,6 +369,24 @@ pub struct Activity {
     /// [`enabled`]: crate::Application#structfield.enabled
     #[yaserde(attribute, prefix = "android")]
     pub enabled: Option<VarOrBool>,
+    /// Whether the activity opts in to the [`predictive back gesture`], receiving back
+    /// events with `OnBackInvokedCallback` rather than `onBackPressed()`. This overrides
+    /// the value set on [`<application>`].
+    ///
+    /// This attribute was introduced in API Level 33.
+    ///
+    /// [`predictive back gesture`]: https://developer.android.com/guide/navigation/custom-back/predictive-back-gesture
+    /// [`<application>`]: crate::Application#structfield.enable_on_back_invoked_callback
+    #[yaserde(attribute, prefix = "android", rename = "enableOnBackInvokedCallback")]
+    pub enable_on_back_invoked_callback: Option<VarOrBool>,
+    /// The component name of the [`VrListenerService`] to enable while the activity is
+    /// in VR mode, like `com.example.VrListener`.
+    ///
+    /// This attribute was introduced in API Level 24.
+    ///
+    /// [`VrListenerService`]: https://developer.android.com/reference/android/service/vr/VrListenerService
+    #[yaserde(attribute, prefix = "android", rename = "enableVrMode")]
+    pub enable_vr_mode: Option<String>,
     /// Whether or not the task initiated by this activity should be excluded
     /// from the list of recently used applications, the [`overview screen`].
     /// That is, when this activity is the root activity of a new task, this
@@ -397,6 +486,22 @@ pub struct Activity {
     /// [`setImmersive()`]: https://developer.android.com/reference/android/app/Activity#setImmersive(boolean)
     #[yaserde(attribute, prefix = "android")]
     pub immersive: Option<VarOrBool>,
+    /// Whether the activity is shown on top of the lock screen when the activity
+    /// below it in the task is, as set by its [`showWhenLocked`] attribute.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 29.
+    ///
+    /// [`showWhenLocked`]: crate::Activity#structfield.show_when_locked
+    #[yaserde(attribute, prefix = "android", rename = "inheritShowWhenLocked")]
+    pub inherit_show_when_locked: Option<VarOrBool>,
+    /// The SHA-256 digests of the signing certificates of the apps allowed to embed the
+    /// activity in their task, or a reference to a string array resource listing them.
+    ///
+    /// This attribute was introduced in API Level 33.
+    #[yaserde(attribute, prefix = "android", rename = "knownActivityEmbeddingCerts")]
+    pub known_activity_embedding_certs: Option<String>,
     /// A user-readable label for the activity. The label is displayed on-screen when the
     /// activity must be represented to the user. It's often displayed along with the
     /// activity icon. If this attribute is not set, the label set for the application
@@ -425,7 +530,7 @@ pub struct Activity {
     ///
     /// [`Intent`]: https://developer.android.com/reference/android/content/Intent
     #[yaserde(attribute, prefix = "android", rename = "launchMode")]
-    pub launch_mode: Option<LaunchMode>,
+    pub launch_mode: Option<VarOr<LaunchMode>>,
     /// Determines how the system presents this activity when the device is running in
     /// [`lock task mode`].
     ///
@@ -442,7 +547,7 @@ pub struct Activity {
     /// [`lock task mode`]: https://developer.android.com/work/dpc/dedicated-devices/lock-task-mode
     /// [`privileged apps`]: https://source.android.com/devices/tech/config/perms-allowlist
     #[yaserde(attribute, prefix = "android", rename = "lockTaskMode")]
-    pub lock_task_mode: Option<LockTaskMode>,
+    pub lock_task_mode: Option<VarOr<LockTaskMode>>,
     /// The maximum number of tasks rooted at this activity in the [`overview screen`].
     /// When this number of entries is reached, the system removes the least-recently
     /// used instance from the overview screen. Valid values are 1 through 50 (25 on
@@ -453,7 +558,7 @@ pub struct Activity {
     ///
     /// [`overview screen`]: https://developer.android.com/guide/components/activities/recents
     #[yaserde(attribute, prefix = "android", rename = "maxRecents")]
-    pub max_recents: Option<u32>,
+    pub max_recents: Option<VarOr<u32>>,
     /// The maximum aspect ratio the activity supports. If the app runs on a device with a
     /// wider aspect ratio, the system automatically letterboxes the app, leaving
     /// portions of the screen unused so the app can run at its specified maximum
@@ -473,7 +578,21 @@ pub struct Activity {
     /// [`Supporting Multiple Screens`]: https://developer.android.com/guide/practices/screens_support
     /// [`resizeableActivity`]: crate::Activity#structfield.resizeable_activity
     #[yaserde(attribute, prefix = "android", rename = "maxAspectRatio")]
-    pub max_aspect_ratio: Option<f32>,
+    pub max_aspect_ratio: Option<VarOr<f32>>,
+    /// The minimum aspect ratio the activity supports, expressed as the decimal form of
+    /// the quotient of the device's longer dimension divided by its shorter dimension.
+    /// If the app runs on a device with a narrower aspect ratio, the system letterboxes
+    /// the app.
+    ///
+    /// ## Note
+    /// This attribute is ignored if the activity has [`resizeableActivity`] set to true,
+    /// since that means your activity supports any size.
+    ///
+    /// This attribute was introduced in API Level 29.
+    ///
+    /// [`resizeableActivity`]: crate::Activity#structfield.resizeable_activity
+    #[yaserde(attribute, prefix = "android", rename = "minAspectRatio")]
+    pub min_aspect_ratio: Option<VarOr<f32>>,
     /// Whether an instance of the activity can be launched into the process of the
     /// component that started it — "`true`" if it can be, and "`false`" if not.
     ///
@@ -571,7 +690,7 @@ pub struct Activity {
     ///
     /// [`back stack`]: https://developer.android.com/guide/components/activities/tasks-and-back-stack
     #[yaserde(attribute, prefix = "android", rename = "persistableMode")]
-    pub persistable_mode: Option<PersistableMode>,
+    pub persistable_mode: Option<VarOr<PersistableMode>>,
     /// The name of a permission that clients must have to launch the activity or
     /// otherwise get it to respond to an intent. If a caller of [`startActivity()`]
     /// or [`startActivityForResult()`] has not been granted the specified permission,
@@ -592,6 +711,14 @@ pub struct Activity {
     /// [`Security and Permissions`]: https://developer.android.com/training/articles/security-tips
     #[yaserde(attribute, prefix = "android")]
     pub permission: Option<String>,
+    /// Whether the activity prefers the display to minimize its post-processing, like
+    /// the game mode of TVs, to reduce latency. The display may still process frames.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 30.
+    #[yaserde(attribute, prefix = "android", rename = "preferMinimalPostProcessing")]
+    pub prefer_minimal_post_processing: Option<VarOrBool>,
     /// The name of the process in which the activity should run. Normally, all components
     /// of an application run in a default process name created for the application
     /// and you do not need to use this attribute. But if necessary, you can override
@@ -630,6 +757,25 @@ pub struct Activity {
     /// [`overview screen`]: https://developer.android.com/guide/components/activities/recents
     #[yaserde(attribute, prefix = "android", rename = "relinquishTaskIdentity")]
     pub relinquish_task_identity: Option<VarOrBool>,
+    /// The permission the caller must hold on the content URIs of the intent starting
+    /// the activity.
+    ///
+    /// The default value is "`none`".
+    ///
+    /// This attribute was introduced in API Level 35.
+    #[yaserde(
+        attribute,
+        prefix = "android",
+        rename = "requireContentUriPermissionFromCaller"
+    )]
+    pub require_content_uri_permission_from_caller: Option<VarOr<RequireContentUriPermission>>,
+    /// The category of the displays the activity can be launched on, like
+    /// `automotive_displays`. The activity can only be launched on displays of the same
+    /// category, and displays with a category only launch activities declaring it.
+    ///
+    /// This attribute was introduced in API Level 34.
+    #[yaserde(attribute, prefix = "android", rename = "requiredDisplayCategory")]
+    pub required_display_category: Option<String>,
     /// Specifies whether the app supports [`multi-window display`]. You can set
     /// this attribute in either the `<activity>` or [`<application>`] element.
     ///
@@ -654,6 +800,14 @@ pub struct Activity {
     /// [`<application>`]: crate::Application
     #[yaserde(attribute, prefix = "android", rename = "resizeableActivity")]
     pub resizeable_activity: Option<VarOrBool>,
+    /// A round icon for the activity, used instead of [`icon`] by launchers that display
+    /// round icons.
+    ///
+    /// This attribute was introduced in API Level 25.
+    ///
+    /// [`icon`]: crate::Activity#structfield.icon
+    #[yaserde(attribute, prefix = "android", rename = "roundIcon")]
+    pub round_icon: Option<MipmapOrDrawableResource>,
     /// The orientation of the activity's display on the device. The system ignores this
     /// attribute if the activity is running in [`multi-window mode`].
     ///
@@ -679,7 +833,7 @@ pub struct Activity {
     /// [`multi-window mode`]: https://developer.android.com/guide/topics/ui/multi-window
     /// [`<uses-feature>`]: crate::UsesFeature
     #[yaserde(attribute, prefix = "android", rename = "screenOrientation")]
-    pub screen_orientation: Option<ScreenOrientation>,
+    pub screen_orientation: Option<VarOr<ScreenOrientation>>,
     /// Whether or not the activity is shown when the device's current user is
     /// different than the user who launched the activity. You can set this
     /// attribute to a literal value — "`true`" or "`false`" — or you can set the
@@ -689,6 +843,14 @@ pub struct Activity {
     /// This attribute was added in API level 23.
     #[yaserde(attribute, prefix = "android", rename = "showForAllUsers")]
     pub show_for_all_users: Option<VarOrBool>,
+    /// Whether the activity is shown on top of the lock screen when the device is
+    /// locked, the lock screen staying behind it.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 27.
+    #[yaserde(attribute, prefix = "android", rename = "showWhenLocked")]
+    pub show_when_locked: Option<VarOrBool>,
     /// Whether or not the activity can be killed and successfully restarted without
     /// having saved its state — "`true`" if it can be restarted without reference to
     /// its previous state, and "`false`" if its previous state is required. The
@@ -764,6 +926,16 @@ pub struct Activity {
     /// [`Styles and Themes`]: https://developer.android.com/guide/topics/ui/look-and-feel/themes
     #[yaserde(attribute, prefix = "android")]
     pub theme: Option<Resource<StyleResource>>,
+    /// Whether the screen turns on when the activity is resumed. It is usually used with
+    /// [`showWhenLocked`].
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 27.
+    ///
+    /// [`showWhenLocked`]: crate::Activity#structfield.show_when_locked
+    #[yaserde(attribute, prefix = "android", rename = "turnScreenOn")]
+    pub turn_screen_on: Option<VarOrBool>,
     /// Extra options for an activity's UI.
     ///
     /// For more information about the app bar, see the [`Adding the App Bar`] training
@@ -773,7 +945,7 @@ pub struct Activity {
     ///
     /// [`Adding the App Bar`]: https://developer.android.com/training/appbar
     #[yaserde(attribute, prefix = "android", rename = "uiOptions")]
-    pub ui_options: Option<UiOptions>,
+    pub ui_options: Option<VarOr<UiOptions>>,
     /// How the main window of the activity interacts with the window containing the
     /// on-screen soft keyboard. The setting for this attribute affects two things:
     ///
@@ -815,6 +987,21 @@ pub struct Activity {
     #[yaserde(rename = "meta-data")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub meta_data: Vec<MetaData>,
+    /// List of `<property>` tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub property: Vec<Property>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Activity>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 impl Activity {
@@ -836,10 +1023,13 @@ impl Activity {
 #[serde(rename_all = "camelCase")]
 #[derive(Default)]
 pub enum ColorMode {
+    /// `Default value`. The activity uses the sRGB color space.
+    #[yaserde(rename = "default")]
+    #[default]
+    Default,
     /// Indicating that the activity should use a high dynamic range if the presentation
     /// display supports it.
     #[yaserde(rename = "hdr")]
-    #[default]
     Hdr,
     /// Indicating that the activity should use a wide color gamut if the presentation
     /// display supports it. To render wide color gamut content, your app must load a
@@ -854,6 +1044,10 @@ pub enum ColorMode {
 #[serde(rename_all = "camelCase")]
 #[derive(Default)]
 pub enum ConfigChanges {
+    /// The display color mode has changed, like its wide color gamut or high dynamic
+    /// range support. Added in API level 26.
+    #[yaserde(rename = "colorMode")]
+    ColorMode,
     /// The display density has changed — the user might have specified a different
     /// display scale, or a different display might now be active. Added in API level
     /// 24.
@@ -864,6 +1058,14 @@ pub enum ConfigChanges {
     /// size.
     #[yaserde(rename = "fontScale")]
     FontScale,
+    /// The font weight adjustment has changed — the user has turned bold text on or off.
+    /// Added in API level 31.
+    #[yaserde(rename = "fontWeightAdjustment")]
+    FontWeightAdjustment,
+    /// The grammatical gender the user is addressed with has changed. Added in API level
+    /// 34.
+    #[yaserde(rename = "grammaticalGender")]
+    GrammaticalGender,
     /// The keyboard type has changed — for example, the user has plugged in an external
     /// keyboard.
     #[yaserde(rename = "keyboard")]
@@ -1092,6 +1294,22 @@ pub enum LaunchMode {
     /// Multiple Instances?: No
     #[yaserde(rename = "singleInstance")]
     SingleInstance,
+    /// The activity can only be running as the root activity of the task, the first
+    /// activity that created the task, and therefore there will only be one instance
+    /// of this activity in a task. Unlike "`singleInstance`", the activity can be
+    /// started in multiple instances in different tasks if the
+    /// [`FLAG_ACTIVITY_MULTIPLE_TASK`] or [`FLAG_ACTIVITY_NEW_DOCUMENT`] flag is set.
+    ///
+    /// Use Cases: Specialized launches (not recommended for general use)
+    ///
+    /// Multiple Instances?: Conditionally
+    ///
+    /// This value was introduced in API Level 31.
+    ///
+    /// [`FLAG_ACTIVITY_MULTIPLE_TASK`]: https://developer.android.com/reference/android/content/Intent#FLAG_ACTIVITY_MULTIPLE_TASK
+    /// [`FLAG_ACTIVITY_NEW_DOCUMENT`]: https://developer.android.com/reference/android/content/Intent#FLAG_ACTIVITY_NEW_DOCUMENT
+    #[yaserde(rename = "singleInstancePerTask")]
+    SingleInstancePerTask,
 }
 
 /// This value indicates how tasks rooted at this activity will behave in lockTask mode.
@@ -1199,6 +1417,30 @@ pub enum PersistableMode {
     PersistNever,
 }
 
+/// The permission that the caller of an activity must hold on the content URIs of the
+/// intent, in its data and `ClipData`.
+#[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Default)]
+pub enum RequireContentUriPermission {
+    /// `Default value`. No permission is required.
+    #[yaserde(rename = "none")]
+    #[default]
+    None,
+    /// The caller must be able to read the content URIs.
+    #[yaserde(rename = "read")]
+    Read,
+    /// The caller must be able to write the content URIs.
+    #[yaserde(rename = "write")]
+    Write,
+    /// The caller must be able to read or write the content URIs.
+    #[yaserde(rename = "readOrWrite")]
+    ReadOrWrite,
+    /// The caller must be able to read and write the content URIs.
+    #[yaserde(rename = "readAndWrite")]
+    ReadAndWrite,
+}
+
 /// The orientation of the activity's display on the device.
 #[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -1352,4 +1594,8 @@ pub enum WindowSoftInputMode {
     /// get at and interact with obscured parts of the window.
     #[yaserde(rename = "adjustPan")]
     AdjustPan,
+    /// The activity's main window is neither resized nor panned for the soft keyboard,
+    /// the activity handling the window insets itself.
+    #[yaserde(rename = "adjustNothing")]
+    AdjustNothing,
 }
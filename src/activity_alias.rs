@@ -1,8 +1,11 @@
 use crate::VarOrBool;
 
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::intent_filter::IntentFilter;
 use super::meta_data::MetaData;
+use super::property::Property;
 use super::resources::{MipmapOrDrawableResource, Resource, StringResource};
+use super::tools::ToolsAttributes;
 use serde::{Deserialize, Serialize};
 
 /// An alias for an activity, named by the `targetActivity` attribute.
@@ -42,6 +45,7 @@ use serde::{Deserialize, Serialize};
 /// ## Can contain
 /// * [`<intent-filter>`]
 /// * [`<meta-data>`]
+/// * [`<property>`]
 ///
 /// ## Introduced in
 /// API Level 1
@@ -49,6 +53,7 @@ use serde::{Deserialize, Serialize};
 /// [`<application>`]: crate::Application
 /// [`<intent-filter>`]: crate::IntentFilter
 /// [`<meta-data>`]: crate::MetaData
+/// [`<property>`]: crate::Property
 /// [`<activity>`]: crate::Activity
 /// [`android.intent.action.MAIN`]: https://developer.android.com/reference/android/content/Intent#ACTION_MAIN
 /// [`android.intent.category.LAUNCHER`]: https://developer.android.com/reference/android/content/Intent#CATEGORY_LAUNCHER
@@ -136,4 +141,19 @@ pub struct ActivityAlias {
     #[yaserde(rename = "meta-data")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub meta_data: Vec<MetaData>,
+    /// List of `<property>` tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub property: Vec<Property>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<ActivityAlias>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
@@ -0,0 +1,57 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
+use serde::{Deserialize, Serialize};
+
+/// Declares a system service that the system server loads from an APEX module.
+///
+/// Only the manifests of APEX modules use this element.
+///
+/// ## XML Syntax
+/// ```xml
+/// <apex-system-service android:name="string"
+///                      android:path="string"
+///                      android:initOrder="integer"
+///                      android:minSdkVersion="integer"
+///                      android:maxSdkVersion="integer" />
+/// ```
+///
+/// ## Contained in
+/// * [`<application>`]
+///
+/// ## Introduced in
+/// API Level 33
+///
+/// [`<application>`]: crate::Application
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct ApexSystemService {
+    /// The fully qualified class name of the `SystemService` subclass.
+    #[yaserde(attribute, prefix = "android")]
+    pub name: Option<String>,
+    /// The path of the JAR file holding the service, in the APEX module. The service is
+    /// loaded from the system server class path when not set.
+    #[yaserde(attribute, prefix = "android")]
+    pub path: Option<String>,
+    /// The order in which the services of the module are started, the higher numbers
+    /// first.
+    #[yaserde(attribute, prefix = "android", rename = "initOrder")]
+    pub init_order: Option<VarOr<i32>>,
+    /// The lowest API level on which the service is started.
+    #[yaserde(attribute, prefix = "android", rename = "minSdkVersion")]
+    pub min_sdk_version: Option<VarOr<u32>>,
+    /// The highest API level on which the service is started.
+    #[yaserde(attribute, prefix = "android", rename = "maxSdkVersion")]
+    pub max_sdk_version: Option<VarOr<u32>>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<ApexSystemService>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
@@ -1,19 +1,28 @@
-use crate::VarOrBool;
+use crate::{VarOr, VarOrBool};
 
 use super::activity::Activity;
 use super::activity_alias::ActivityAlias;
+use super::apex_system_service::ApexSystemService;
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::meta_data::MetaData;
 use super::profileable::Profileable;
+use super::property::Property;
 use super::provider::Provider;
 use super::receiver::Receiver;
 use super::resources::{
     DrawableResource, MipmapOrDrawableResource, Resource, StringResource, StringResourceOrString,
     StyleResource, XmlResource,
 };
+use super::sdk_library::SdkLibrary;
 use super::service::Service;
+use super::static_library::StaticLibrary;
+use super::tools::ToolsAttributes;
 use super::ui_options::UiOptions;
 use super::uses_library::UsesLibrary;
 use super::uses_native_library::UsesNativeLibrary;
+use super::uses_package::UsesPackage;
+use super::uses_sdk_library::UsesSdkLibrary;
+use super::uses_static_library::UsesStaticLibrary;
 use serde::{Deserialize, Serialize};
 
 /// The declaration of the application.
@@ -29,20 +38,30 @@ use serde::{Deserialize, Serialize};
 /// ## XML Syntax
 /// ```xml
 /// <application android:allowTaskReparenting=["true" | "false"]
+///              android:allowAudioPlaybackCapture=["true" | "false"]
 ///              android:allowBackup=["true" | "false"]
 ///              android:allowClearUserData=["true" | "false"]
+///              android:allowCrossUidActivitySwitchFromBelow=["true" | "false"]
 ///              android:allowNativeHeapPointerTagging=["true" | "false"]
+///              android:appCategory=["accessibility" | "audio" | "game" |
+///                                   "image" | "maps" | "news" |
+///                                   "productivity" | "social" | "video"]
+///              android:appComponentFactory="string"
+///              android:attributionsAreUserVisible=["true" | "false"]
 ///              android:backupAgent="string"
 ///              android:backupInForeground=["true" | "false"]
 ///              android:banner="drawable resource"
+///              android:crossProfile=["true" | "false"]
+///              android:dataExtractionRules="xml resource"
 ///              android:debuggable=["true" | "false"]
 ///              android:description="string resource"
 ///              android:directBootAware=["true" | "false"]
 ///              android:enabled=["true" | "false"]
+///              android:enableOnBackInvokedCallback=["true" | "false"]
 ///              android:extractNativeLibs=["true" | "false"]
 ///              android:fullBackupContent="xml resource"
 ///              android:fullBackupOnly=["true" | "false"]
-///              android:gwpAsanMode=["always" | "never"]
+///              android:gwpAsanMode=["default" | "always" | "never"]
 ///              android:hasCode=["true" | "false"]
 ///              android:hasFragileUserData=["true" | "false"]
 ///              android:hardwareAccelerated=["true" | "false"]
@@ -51,25 +70,34 @@ use serde::{Deserialize, Serialize};
 ///              android:killAfterRestore=["true" | "false"]
 ///              android:largeHeap=["true" | "false"]
 ///              android:label="string resource"
+///              android:localeConfig="xml resource"
 ///              android:logo="drawable resource"
 ///              android:manageSpaceActivity="string"
+///              android:memtagMode=["default" | "off" | "async" | "sync"]
 ///              android:name="string"
+///              android:nativeHeapZeroInitialized=["true" | "false"]
 ///              android:networkSecurityConfig="xml resource"
 ///              android:permission="string"
 ///              android:persistent=["true" | "false"]
+///              android:preserveLegacyExternalStorage=["true" | "false"]
 ///              android:process="string"
 ///              android:restoreAnyVersion=["true" | "false"]
 ///              android:requestLegacyExternalStorage=["true" | "false"]
+///              android:requestRawExternalStorageAccess=["true" | "false"]
 ///              android:requiredAccountType="string"
+///              android:resetEnabledSettingsOnAppDataCleared=["true" | "false"]
 ///              android:resizeableActivity=["true" | "false"]
 ///              android:restrictedAccountType="string"
+///              android:roundIcon="drawable resource"
 ///              android:supportsRtl=["true" | "false"]
 ///              android:taskAffinity="string"
 ///              android:testOnly=["true" | "false"]
 ///              android:theme="resource or theme"
 ///              android:uiOptions=["none" | "splitActionBarWhenNarrow"]
 ///              android:usesCleartextTraffic=["true" | "false"]
-///              android:vmSafeMode=["true" | "false"] >
+///              android:usesNonSdkApi=["true" | "false"]
+///              android:vmSafeMode=["true" | "false"]
+///              android:zygotePreloadName="string" >
 ///       ...
 /// </application>
 /// ```
@@ -81,10 +109,18 @@ use serde::{Deserialize, Serialize};
 /// * [`<activity>`]
 /// * [`<activity-alias>`]
 /// * [`<meta-data>`]
+/// * [`<property>`]
 /// * [`<service>`]
 /// * [`<receiver>`]
 /// * [`<provider>`]
 /// * [`<uses-library>`]
+/// * [`<uses-native-library>`]
+/// * [`<uses-sdk-library>`]
+/// * [`<uses-static-library>`]
+/// * [`<uses-package>`]
+/// * [`<sdk-library>`]
+/// * [`<static-library>`]
+/// * [`<apex-system-service>`]
 ///
 /// ## Introduced in
 /// API Level 1
@@ -93,10 +129,18 @@ use serde::{Deserialize, Serialize};
 /// [`<activity>`]: crate::Activity
 /// [`<activity-alias>`]: crate::ActivityAlias
 /// [`<meta-data>`]: crate::MetaData
+/// [`<property>`]: crate::Property
 /// [`<service>`]: crate::Service
 /// [`<receiver>`]: crate::Receiver
 /// [`<provider>`]: crate::Provider
 /// [`<uses-library>`]: crate::UsesLibrary
+/// [`<uses-native-library>`]: crate::UsesNativeLibrary
+/// [`<uses-sdk-library>`]: crate::UsesSdkLibrary
+/// [`<uses-static-library>`]: crate::UsesStaticLibrary
+/// [`<uses-package>`]: crate::UsesPackage
+/// [`<sdk-library>`]: crate::SdkLibrary
+/// [`<static-library>`]: crate::StaticLibrary
+/// [`<apex-system-service>`]: crate::ApexSystemService
 #[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Default, Clone)]
 pub struct Application {
     /// Whether or not activities that the application defines can move from the task that
@@ -113,6 +157,17 @@ pub struct Application {
     /// [`allowTaskReparenting`]: crate::Activity#structfield.allow_task_reparenting
     #[yaserde(attribute, prefix = "android", rename = "allowTaskReparenting")]
     pub allow_task_reparenting: Option<VarOrBool>,
+    /// Whether other apps can capture the audio the app plays, with the
+    /// [`AudioPlaybackCaptureConfiguration`] API.
+    ///
+    /// The default value is "`true`" for apps targeting API Level 29 or higher, and
+    /// "`false`" for the others.
+    ///
+    /// This attribute was introduced in API Level 29.
+    ///
+    /// [`AudioPlaybackCaptureConfiguration`]: https://developer.android.com/reference/android/media/AudioPlaybackCaptureConfiguration
+    #[yaserde(attribute, prefix = "android", rename = "allowAudioPlaybackCapture")]
+    pub allow_audio_playback_capture: Option<VarOrBool>,
     /// Whether to allow the application to participate in the backup and restore
     /// infrastructure. If this attribute is set to false, no backup or restore of the
     /// application will ever be performed, even by a full-system backup that would
@@ -145,6 +200,19 @@ pub struct Application {
     /// [`Restoring User Data on New Devices`]: https://developer.android.com/guide/topics/data/backup
     #[yaserde(attribute, prefix = "android", rename = "allowClearUserData")]
     pub allow_clear_user_data: Option<VarOrBool>,
+    /// Whether the activities of the app allow apps with a different UID whose
+    /// activities are below them in the task to start activities. Activities can
+    /// override it with their own attribute.
+    ///
+    /// The default value is "`true`".
+    ///
+    /// This attribute was introduced in API Level 35.
+    #[yaserde(
+        attribute,
+        prefix = "android",
+        rename = "allowCrossUidActivitySwitchFromBelow"
+    )]
+    pub allow_cross_uid_activity_switch_from_below: Option<VarOrBool>,
     /// Whether or not the app has the Heap pointer tagging feature enabled.
     ///
     /// The default value of this attribute is `true`.
@@ -162,6 +230,30 @@ pub struct Application {
         rename = "allowNativeHeapPointerTagging"
     )]
     pub allow_native_heap_pointer_tagging: Option<VarOrBool>,
+    /// Declares the category of the app, used to group apps together in battery, network
+    /// or disk usage summaries.
+    ///
+    /// This attribute was introduced in API Level 26.
+    #[yaserde(attribute, prefix = "android", rename = "appCategory")]
+    pub app_category: Option<VarOr<AppCategory>>,
+    /// The fully qualified name of a subclass of [`AppComponentFactory`] the system uses
+    /// to instantiate the components of the app.
+    ///
+    /// This attribute was introduced in API Level 28.
+    ///
+    /// [`AppComponentFactory`]: https://developer.android.com/reference/android/app/AppComponentFactory
+    #[yaserde(attribute, prefix = "android", rename = "appComponentFactory")]
+    pub app_component_factory: Option<String>,
+    /// Whether the [`<attribution>`] tags of the app are shown to the user, like in the
+    /// permission usage screens.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`<attribution>`]: https://developer.android.com/guide/topics/data/audit-access#attribution-tags
+    #[yaserde(attribute, prefix = "android", rename = "attributionsAreUserVisible")]
+    pub attributions_are_user_visible: Option<VarOrBool>,
     /// The name of the class that implements the application's backup agent, a subclass
     /// of [`BackupAgent`]. The attribute value should be a fully qualified class name
     /// (such as, `"com.example.project.MyBackupAgent"`). However, as a shorthand, if
@@ -209,6 +301,24 @@ pub struct Application {
     /// [`Provide a home screen banner`]: https://developer.android.com/training/tv/start/start#banner
     #[yaserde(attribute, prefix = "android")]
     pub banner: Option<Resource<DrawableResource>>,
+    /// Whether the app can communicate with its instance in the other profile of the
+    /// user, like the work profile, once the user allows it.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 30.
+    #[yaserde(attribute, prefix = "android", rename = "crossProfile")]
+    pub cross_profile: Option<VarOrBool>,
+    /// The XML resource with the [`data extraction rules`] of the app, controlling which
+    /// files are included in cloud backups and device to device transfers. It replaces
+    /// [`fullBackupContent`] for apps targeting API Level 31 or higher.
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`data extraction rules`]: https://developer.android.com/guide/topics/data/autobackup#include-exclude-android-12
+    /// [`fullBackupContent`]: crate::Application#structfield.full_backup_content
+    #[yaserde(attribute, prefix = "android", rename = "dataExtractionRules")]
+    pub data_extraction_rules: Option<Resource<XmlResource>>,
     /// Whether or not the application can be debugged, even when running on a device in
     /// user mode — "`true`" if it can be, and "`false`" if not. The default value is
     /// "`false`".
@@ -246,6 +356,17 @@ pub struct Application {
     /// The default value is "`true`".
     #[yaserde(attribute, prefix = "android")]
     pub enabled: Option<VarOrBool>,
+    /// Whether the app opts in to the [`predictive back gesture`], its activities
+    /// receiving back events with `OnBackInvokedCallback` rather than
+    /// `onBackPressed()`. Activities can override it with their own attribute.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 33.
+    ///
+    /// [`predictive back gesture`]: https://developer.android.com/guide/navigation/custom-back/predictive-back-gesture
+    #[yaserde(attribute, prefix = "android", rename = "enableOnBackInvokedCallback")]
+    pub enable_on_back_invoked_callback: Option<VarOrBool>,
     /// Whether or not the package installer extracts native libraries from the APK to the
     /// filesystem. If set to "`false`", then your native libraries must be page aligned
     /// and stored uncompressed in the APK. Although your APK might be larger, your
@@ -297,7 +418,7 @@ pub struct Application {
     ///
     /// [`GWP-ASan`]: https://developer.android.com/ndk/guides/gwp-asan
     #[yaserde(attribute, prefix = "android", rename = "gwpAsanMode")]
-    pub gwp_asan_mode: Option<GwpAsanMode>,
+    pub gwp_asan_mode: Option<VarOr<GwpAsanMode>>,
     /// Whether or not the application contains any code — "`true`" if it does, and
     /// "`false`" if not. When the value is "`false`", the system does not try to load
     /// any application code when launching components.
@@ -411,6 +532,14 @@ pub struct Application {
     /// [`<provider>`]: crate::Provider
     #[yaserde(attribute, prefix = "android")]
     pub label: Option<StringResourceOrString>,
+    /// The XML resource listing the languages the app supports, used by the system to
+    /// offer [`per-app language preferences`].
+    ///
+    /// This attribute was introduced in API Level 33.
+    ///
+    /// [`per-app language preferences`]: https://developer.android.com/guide/topics/resources/app-languages
+    #[yaserde(attribute, prefix = "android", rename = "localeConfig")]
+    pub locale_config: Option<Resource<XmlResource>>,
     /// A logo for the application as whole, and the default logo for activities. This
     /// attribute must be set as a reference to a drawable resource containing the
     /// image (for example `"@drawable/logo"`).
@@ -425,6 +554,16 @@ pub struct Application {
     /// [`<activity>`]: crate::Activity
     #[yaserde(attribute, prefix = "android", rename = "manageSpaceActivity")]
     pub manage_space_activity: Option<String>,
+    /// Whether the [`Arm Memory Tagging Extension`] is enabled for the native code of
+    /// the app, on devices supporting it.
+    ///
+    /// The default value is "`default`".
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`Arm Memory Tagging Extension`]: https://developer.android.com/ndk/guides/arm-mte
+    #[yaserde(attribute, prefix = "android", rename = "memtagMode")]
+    pub memtag_mode: Option<VarOr<MemtagMode>>,
     /// The fully qualified name of an [`Application`] subclass implemented for the
     /// application. When the application process is started, this class is instantiated
     /// before any of the application's components.
@@ -435,6 +574,13 @@ pub struct Application {
     /// [`Application`]: https://developer.android.com/reference/android/app/Application
     #[yaserde(attribute, prefix = "android")]
     pub name: Option<String>,
+    /// Whether the native heap allocations of the app are zero initialized.
+    ///
+    /// The default value is decided by the platform.
+    ///
+    /// This attribute was introduced in API Level 31.
+    #[yaserde(attribute, prefix = "android", rename = "nativeHeapZeroInitialized")]
+    pub native_heap_zero_initialized: Option<VarOrBool>,
     /// Specifies the name of the XML file that contains your application's [`Network
     /// Security Configuration`]. The value must be a reference to the XML resource file
     /// containing the configuration.
@@ -462,6 +608,21 @@ pub struct Application {
     /// certain system applications.
     #[yaserde(attribute, prefix = "android")]
     pub persistent: Option<VarOrBool>,
+    /// Whether the app keeps the legacy storage model, set with
+    /// [`requestLegacyExternalStorage`], when it is updated to target API Level 30,
+    /// until it is reinstalled.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 30.
+    ///
+    /// [`requestLegacyExternalStorage`]: crate::Application#structfield.request_legacy_external_storage
+    #[yaserde(
+        attribute,
+        prefix = "android",
+        rename = "preserveLegacyExternalStorage"
+    )]
+    pub preserve_legacy_external_storage: Option<VarOrBool>,
     /// The name of a process where all components of the application should run. Each
     /// component can override this default by setting its own `process` attribute.
     ///
@@ -500,6 +661,28 @@ pub struct Application {
     /// [`scoped storage`]: https://developer.android.com/training/data-storage#scoped-storage
     #[yaserde(attribute, prefix = "android", rename = "requestLegacyExternalStorage")]
     pub request_legacy_external_storage: Option<VarOrBool>,
+    /// Whether the app asks to access external storage directly rather than through
+    /// the media provider, for better file path performance.
+    ///
+    /// This attribute was introduced in API Level 31.
+    #[yaserde(
+        attribute,
+        prefix = "android",
+        rename = "requestRawExternalStorageAccess"
+    )]
+    pub request_raw_external_storage_access: Option<VarOrBool>,
+    /// Whether the enabled state of the components of the app is reset to the one of
+    /// the manifest when the user clears the data of the app.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 33.
+    #[yaserde(
+        attribute,
+        prefix = "android",
+        rename = "resetEnabledSettingsOnAppDataCleared"
+    )]
+    pub reset_enabled_settings_on_app_data_cleared: Option<VarOrBool>,
     /// Specifies the account type required by the application in order to function. If
     /// your app requires an [`Account`], the value for this attribute must correspond to
     /// the account authenticator type used by your app (as defined by
@@ -575,6 +758,14 @@ pub struct Application {
     /// crate::Application#structfield.required_account_type
     #[yaserde(attribute, prefix = "android", rename = "restrictedAccountType")]
     pub restricted_account_type: Option<String>,
+    /// A round icon for the application, used instead of [`icon`] by launchers that
+    /// display round icons.
+    ///
+    /// This attribute was introduced in API Level 25.
+    ///
+    /// [`icon`]: crate::Application#structfield.icon
+    #[yaserde(attribute, prefix = "android", rename = "roundIcon")]
+    pub round_icon: Option<MipmapOrDrawableResource>,
     /// Declares whether your application is willing to support right-to-left (RTL)
     /// layouts. If set to "`true`" and [`targetSdkVersion`] is set to 17 or higher,
     /// various RTL APIs will be activated and used by the system so your app can
@@ -630,7 +821,7 @@ pub struct Application {
     ///
     /// [`Adding the App Bar`]: https://developer.android.com/training/appbar
     #[yaserde(attribute, prefix = "android", rename = "uiOptions")]
-    pub ui_options: Option<UiOptions>,
+    pub ui_options: Option<VarOr<UiOptions>>,
     /// Indicates whether the app intends to use cleartext network traffic, such as
     /// cleartext HTTP. The default value for apps that target API level 27 or lower is
     /// "`true`". Apps that target API level 28 or higher default to "`false`".
@@ -674,6 +865,13 @@ pub struct Application {
     /// [`StrictMode.VmPolicy.Builder.detectCleartextNetwork()`]: https://developer.android.com/reference/android/os/StrictMode.VmPolicy.Builder#detectCleartextNetwork()
     #[yaserde(attribute, prefix = "android", rename = "usesCleartextTraffic")]
     pub uses_cleartext_traffic: Option<VarOrBool>,
+    /// Whether the app can use [`non-SDK interfaces`]. Only system apps can set it.
+    ///
+    /// This attribute was introduced in API Level 28.
+    ///
+    /// [`non-SDK interfaces`]: https://developer.android.com/guide/app-compatibility/restrictions-non-sdk-interfaces
+    #[yaserde(attribute, prefix = "android", rename = "usesNonSdkApi")]
+    pub uses_non_sdk_api: Option<VarOrBool>,
     /// Indicates whether the app would like the virtual machine (VM) to operate in safe
     /// mode. The default value is "`false`".
     ///
@@ -684,6 +882,15 @@ pub struct Application {
     /// ART ahead-of-time (AOT) compiler.
     #[yaserde(attribute, prefix = "android", rename = "vmSafeMode")]
     pub vm_safe_mode: Option<VarOrBool>,
+    /// The fully qualified name of a subclass of [`ZygotePreload`], run in the app zygote
+    /// of the services using [`useAppZygote`] to preload their code.
+    ///
+    /// This attribute was introduced in API Level 29.
+    ///
+    /// [`ZygotePreload`]: https://developer.android.com/reference/android/app/ZygotePreload
+    /// [`useAppZygote`]: crate::Service#structfield.use_app_zygote
+    #[yaserde(attribute, prefix = "android", rename = "zygotePreloadName")]
+    pub zygote_preload_name: Option<String>,
     /// Optional `<profileable>` tag.
     pub profileable: Option<Profileable>,
     /// List of `<activity>` tags.
@@ -706,6 +913,9 @@ pub struct Application {
     #[yaserde(rename = "meta-data")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub meta_data: Vec<MetaData>,
+    /// List of `<property>` tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub property: Vec<Property>,
     /// List of `<uses-library>` tags.
     #[yaserde(rename = "uses-library")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -714,6 +924,40 @@ pub struct Application {
     #[yaserde(rename = "uses-native-library")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub uses_native_library: Vec<UsesNativeLibrary>,
+    /// List of `<uses-sdk-library>` tags.
+    #[yaserde(rename = "uses-sdk-library")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uses_sdk_library: Vec<UsesSdkLibrary>,
+    /// List of `<uses-static-library>` tags.
+    #[yaserde(rename = "uses-static-library")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uses_static_library: Vec<UsesStaticLibrary>,
+    /// List of `<uses-package>` tags.
+    #[yaserde(rename = "uses-package")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uses_package: Vec<UsesPackage>,
+    /// Optional `<sdk-library>` tag.
+    #[yaserde(rename = "sdk-library")]
+    pub sdk_library: Option<SdkLibrary>,
+    /// Optional `<static-library>` tag.
+    #[yaserde(rename = "static-library")]
+    pub static_library: Option<StaticLibrary>,
+    /// List of `<apex-system-service>` tags.
+    #[yaserde(rename = "apex-system-service")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub apex_system_service: Vec<ApexSystemService>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Application>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 impl Application {
@@ -731,6 +975,9 @@ impl Application {
 #[serde(rename_all = "camelCase")]
 #[derive(Default)]
 pub enum GwpAsanMode {
+    /// The default mode of the platform, which is "`never`" for non-system apps.
+    #[yaserde(rename = "default")]
+    Default,
     /// Always disabled: This setting completely disables GWP-ASan in your app and is the
     /// default for non-system apps.
     #[yaserde(rename = "never")]
@@ -749,3 +996,56 @@ pub enum GwpAsanMode {
     #[yaserde(rename = "always")]
     Always,
 }
+
+/// Category of an app, declared with the `android:appCategory` attribute of
+/// [`<application>`](crate::Application).
+///
+/// There's no default category, the attribute is meant to be set explicitly.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum AppCategory {
+    /// Apps which are primarily accessibility apps, such as screen readers.
+    Accessibility,
+    /// Apps which primarily work with audio or music, such as music players.
+    Audio,
+    /// Apps which are primarily games.
+    Game,
+    /// Apps which primarily work with images or photos, such as camera or gallery apps.
+    Image,
+    /// Apps which are primarily maps apps, such as navigation apps.
+    Maps,
+    /// Apps which are primarily news apps, such as newspapers, magazines or sports apps.
+    News,
+    /// Apps which are primarily productivity apps, such as cloud storage or workplace
+    /// apps.
+    Productivity,
+    /// Apps which are primarily social apps, such as messaging, communication, email or
+    /// social network apps.
+    Social,
+    /// Apps which primarily work with video or movies, such as streaming video apps.
+    Video,
+}
+
+/// Mode of the [`Arm Memory Tagging Extension`], which detects memory safety bugs of
+/// native code.
+///
+/// [`Arm Memory Tagging Extension`]: https://developer.android.com/ndk/guides/arm-mte
+#[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Default)]
+pub enum MemtagMode {
+    /// The default mode of the platform, usually off.
+    #[yaserde(rename = "default")]
+    #[default]
+    Default,
+    /// Memory tagging is disabled.
+    #[yaserde(rename = "off")]
+    Off,
+    /// Memory tagging faults are reported asynchronously, with little overhead but
+    /// without the exact location of the fault.
+    #[yaserde(rename = "async")]
+    Async,
+    /// Memory tagging faults are reported synchronously, at the faulting instruction.
+    #[yaserde(rename = "sync")]
+    Sync,
+}
@@ -1,3 +1,4 @@
+use crate::var_or::is_var;
 use serde::{
     de::{self, DeserializeOwned, Visitor},
     ser::Error,
@@ -30,23 +31,36 @@ impl Delimiter for VerticalBar {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct Comma;
+
+impl Delimiter for Comma {
+    fn delimiter_symbol() -> &'static str {
+        ","
+    }
+}
+
+/// Values of an attribute holding a list, like
+/// `android:configChanges="orientation|locale"`.
+///
+/// Items set to `${placeholder}`, substituted by the build, are kept apart from the
+/// values and written back after them.
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
 pub struct AttributeList<D: Delimiter, T: Serialize + DeserializeOwned> {
     vec: Vec<T>,
+    vars: Vec<String>,
     phantom: PhantomData<D>,
 }
 
 impl<D: Delimiter, T: Serialize + DeserializeOwned> AttributeList<D, T> {
     pub fn new() -> Self {
-        AttributeList {
-            vec: Vec::new(),
-            phantom: PhantomData,
-        }
+        Self::from_vec(Vec::new())
     }
 
     pub fn from_vec(vec: Vec<T>) -> Self {
         AttributeList {
             vec,
+            vars: Vec::new(),
             phantom: PhantomData,
         }
     }
@@ -55,17 +69,32 @@ impl<D: Delimiter, T: Serialize + DeserializeOwned> AttributeList<D, T> {
         &self.vec
     }
 
+    /// Items set to placeholders, like `${extraConfigChanges}`.
+    pub fn vars(&self) -> &[String] {
+        &self.vars
+    }
+
+    pub fn push_var(&mut self, var: impl Into<String>) {
+        self.vars.push(var.into());
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.vec.is_empty()
+        self.vec.is_empty() && self.vars.is_empty()
+    }
+
+    fn to_list_string(&self) -> Result<String, String> {
+        let mut items = Vec::new();
+        for value in &self.vec {
+            items.push(serde_plain::to_string(value).map_err(|e| e.to_string())?);
+        }
+        items.extend(self.vars.iter().cloned());
+        Ok(items.join(D::delimiter_symbol()))
     }
 }
 
 impl<D: Delimiter, T: Serialize + DeserializeOwned> From<Vec<T>> for AttributeList<D, T> {
     fn from(vec: Vec<T>) -> Self {
-        AttributeList {
-            vec,
-            phantom: PhantomData,
-        }
+        Self::from_vec(vec)
     }
 }
 
@@ -77,14 +106,7 @@ impl<D: Delimiter, T: Serialize + DeserializeOwned> Serialize for AttributeList<
         if self.is_empty() {
             return Err(S::Error::custom("a value list can't be empty"));
         };
-        serializer.serialize_str(
-            &self
-                .vec()
-                .iter()
-                .map(|v| serde_plain::to_string(v).unwrap())
-                .collect::<Vec<String>>()
-                .join(D::delimiter_symbol()),
-        )
+        serializer.serialize_str(&self.to_list_string().map_err(S::Error::custom)?)
     }
 }
 
@@ -94,14 +116,7 @@ impl<D: Delimiter, T: Serialize + DeserializeOwned> YaSerialize for AttributeLis
         //     println!("1");
         //     return Err("a value list can't be empty".to_string());
         // };
-        let _ret = writer.write(xml::writer::XmlEvent::characters(
-            &self
-                .vec()
-                .iter()
-                .map(|v| serde_plain::to_string(v).unwrap())
-                .collect::<Vec<String>>()
-                .join(D::delimiter_symbol()),
-        ));
+        let _ret = writer.write(xml::writer::XmlEvent::characters(&self.to_list_string()?));
         Ok(())
     }
 
@@ -128,12 +143,15 @@ fn parse_list_with_delimiter<D: Delimiter, T: Serialize + DeserializeOwned>(
             "there is no default value list. at least one value must be specified".to_string(),
         );
     };
-    let values = v
-        .replace(' ', "")
-        .split(D::delimiter_symbol())
-        .map(|s| serde_plain::from_str(s).unwrap())
-        .collect();
-    Ok(AttributeList::from_vec(values))
+    let mut list = AttributeList::new();
+    for item in v.replace(' ', "").split(D::delimiter_symbol()) {
+        match serde_plain::from_str(item) {
+            Ok(value) => list.vec.push(value),
+            Err(_) if is_var(item) => list.vars.push(item.to_string()),
+            Err(_) => return Err(format!("value `{}` is not valid", item)),
+        }
+    }
+    Ok(list)
 }
 
 struct ListVisitor<D: Delimiter, T: Serialize + DeserializeOwned> {
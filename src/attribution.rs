@@ -0,0 +1,99 @@
+use super::extra::{ExtraAttributes, ExtraElements};
+use super::resources::{Resource, StringResource};
+use super::tools::ToolsAttributes;
+use serde::{Deserialize, Serialize};
+
+/// Declares an attribution tag, a logical part of the app to which data accesses are
+/// attributed.
+///
+/// The app passes the tag to [`Context.createAttributionContext()`], and the accesses
+/// to private data made with that context, like the location or the contacts, are
+/// attributed to it when they are [`audited`].
+///
+/// ## XML Syntax
+/// ```xml
+/// <attribution android:tag="string"
+///              android:label="string resource" >
+///     ...
+/// </attribution>
+/// ```
+///
+/// ## Contained in
+/// * [`<manifest>`]
+///
+/// ## Can contain
+/// * [`<inherit-from>`]
+///
+/// ## Introduced in
+/// API Level 30
+///
+/// [`Context.createAttributionContext()`]: https://developer.android.com/reference/android/content/Context#createAttributionContext(java.lang.String)
+/// [`audited`]: https://developer.android.com/guide/topics/data/audit-access
+/// [`<manifest>`]: crate::AndroidManifest
+/// [`<inherit-from>`]: crate::InheritFrom
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct Attribution {
+    /// The attribution tag, at most 50 characters long, unique among the attributions of
+    /// the app.
+    #[yaserde(attribute, prefix = "android")]
+    pub tag: Option<String>,
+    /// A user-readable description of the part of the app the tag represents. The label
+    /// must be set as a reference to a string resource.
+    #[yaserde(attribute, prefix = "android")]
+    pub label: Option<Resource<StringResource>>,
+    /// List of `<inherit-from>` tags.
+    #[yaserde(rename = "inherit-from")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inherit_from: Vec<InheritFrom>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Attribution>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
+
+/// Declares an attribution tag of a previous version of the app that the parent
+/// [`<attribution>`] replaces, so that the data accesses recorded for it are attributed
+/// to the new tag.
+///
+/// ## XML Syntax
+/// ```xml
+/// <inherit-from android:tag="string" />
+/// ```
+///
+/// ## Contained in
+/// * [`<attribution>`]
+///
+/// ## Introduced in
+/// API Level 30
+///
+/// [`<attribution>`]: crate::Attribution
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct InheritFrom {
+    /// The replaced attribution tag.
+    #[yaserde(attribute, prefix = "android")]
+    pub tag: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<InheritFrom>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
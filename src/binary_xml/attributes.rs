@@ -0,0 +1,484 @@
+/// How the value of a manifest attribute is encoded in binary XML.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum AttributeFormat {
+    String,
+    Boolean,
+    Integer,
+    /// Integer conventionally written in hexadecimal, like `glEsVersion`.
+    Hex,
+    Float,
+    Dimension,
+    /// Resource reference or a raw string. Holds the resource type expected by the
+    /// attribute, used when the reference name can't be resolved.
+    Reference(&'static str),
+    Enum(&'static [(&'static str, u32)]),
+    Flags(&'static [(&'static str, u32)]),
+}
+
+/// An attribute of the `android:` namespace together with its framework resource ID.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct AttributeInfo {
+    pub name: &'static str,
+    pub id: u32,
+    pub format: AttributeFormat,
+}
+
+const fn attr(name: &'static str, id: u32, format: AttributeFormat) -> AttributeInfo {
+    AttributeInfo { name, id, format }
+}
+
+use AttributeFormat::*;
+
+/// Manifest attributes of the `android:` namespace, sorted by resource ID.
+///
+/// IDs come from the framework's `public.xml` and are stable across platform releases.
+pub(crate) const ANDROID_ATTRIBUTES: &[AttributeInfo] = &[
+    attr("theme", 0x01010000, Reference("style")),
+    attr("label", 0x01010001, Reference("string")),
+    attr("icon", 0x01010002, Reference("drawable")),
+    attr("name", 0x01010003, String),
+    attr("manageSpaceActivity", 0x01010004, String),
+    attr("allowClearUserData", 0x01010005, Boolean),
+    attr("permission", 0x01010006, String),
+    attr("readPermission", 0x01010007, String),
+    attr("writePermission", 0x01010008, String),
+    attr("protectionLevel", 0x01010009, Flags(PROTECTION_LEVEL)),
+    attr("permissionGroup", 0x0101000a, String),
+    attr("sharedUserId", 0x0101000b, String),
+    attr("hasCode", 0x0101000c, Boolean),
+    attr("persistent", 0x0101000d, Boolean),
+    attr("enabled", 0x0101000e, Boolean),
+    attr("debuggable", 0x0101000f, Boolean),
+    attr("exported", 0x01010010, Boolean),
+    attr("process", 0x01010011, String),
+    attr("taskAffinity", 0x01010012, String),
+    attr("multiprocess", 0x01010013, Boolean),
+    attr("finishOnTaskLaunch", 0x01010014, Boolean),
+    attr("clearTaskOnLaunch", 0x01010015, Boolean),
+    attr("stateNotNeeded", 0x01010016, Boolean),
+    attr("excludeFromRecents", 0x01010017, Boolean),
+    attr("authorities", 0x01010018, String),
+    attr("syncable", 0x01010019, Boolean),
+    attr("initOrder", 0x0101001a, Integer),
+    attr("grantUriPermissions", 0x0101001b, Boolean),
+    attr("priority", 0x0101001c, Integer),
+    attr("launchMode", 0x0101001d, Enum(LAUNCH_MODE)),
+    attr("screenOrientation", 0x0101001e, Enum(SCREEN_ORIENTATION)),
+    attr("configChanges", 0x0101001f, Flags(CONFIG_CHANGES)),
+    attr("description", 0x01010020, Reference("string")),
+    attr("targetPackage", 0x01010021, String),
+    attr("handleProfiling", 0x01010022, Boolean),
+    attr("functionalTest", 0x01010023, Boolean),
+    attr("value", 0x01010024, String),
+    attr("resource", 0x01010025, Reference("xml")),
+    attr("mimeType", 0x01010026, String),
+    attr("scheme", 0x01010027, String),
+    attr("host", 0x01010028, String),
+    attr("port", 0x01010029, String),
+    attr("path", 0x0101002a, String),
+    attr("pathPrefix", 0x0101002b, String),
+    attr("pathPattern", 0x0101002c, String),
+    attr("tag", 0x010100d1, String),
+    attr("minWidth", 0x0101013f, Dimension),
+    attr("minHeight", 0x01010140, Dimension),
+    attr("order", 0x010101ea, Integer),
+    attr("targetActivity", 0x01010202, String),
+    attr("alwaysRetainTaskState", 0x01010203, Boolean),
+    attr("allowTaskReparenting", 0x01010204, Boolean),
+    attr("minSdkVersion", 0x0101020c, Integer),
+    attr("versionCode", 0x0101021b, Integer),
+    attr("versionName", 0x0101021c, String),
+    attr("reqTouchScreen", 0x01010227, Enum(REQ_TOUCH_SCREEN)),
+    attr("reqKeyboardType", 0x01010228, Enum(REQ_KEYBOARD_TYPE)),
+    attr("reqHardKeyboard", 0x01010229, Boolean),
+    attr("reqNavigation", 0x0101022a, Enum(REQ_NAVIGATION)),
+    attr(
+        "windowSoftInputMode",
+        0x0101022b,
+        Flags(WINDOW_SOFT_INPUT_MODE),
+    ),
+    attr("noHistory", 0x0101022d, Boolean),
+    attr("reqFiveWayNav", 0x01010232, Boolean),
+    attr("sharedUserLabel", 0x01010261, Reference("string")),
+    attr("anyDensity", 0x0101026c, Boolean),
+    attr("targetSdkVersion", 0x01010270, Integer),
+    attr("maxSdkVersion", 0x01010271, Integer),
+    attr("testOnly", 0x01010272, Boolean),
+    attr("backupAgent", 0x0101027f, String),
+    attr("allowBackup", 0x01010280, Boolean),
+    attr("glEsVersion", 0x01010281, Hex),
+    attr("smallScreens", 0x01010284, Boolean),
+    attr("normalScreens", 0x01010285, Boolean),
+    attr("largeScreens", 0x01010286, Boolean),
+    attr("resizeable", 0x0101028d, Boolean),
+    attr("required", 0x0101028e, Boolean),
+    attr("killAfterRestore", 0x0101029c, Boolean),
+    attr("installLocation", 0x010102b7, Enum(INSTALL_LOCATION)),
+    attr("vmSafeMode", 0x010102b8, Boolean),
+    attr("restoreAnyVersion", 0x010102ba, Boolean),
+    attr("logo", 0x010102be, Reference("drawable")),
+    attr("xlargeScreens", 0x010102bf, Boolean),
+    attr("immersive", 0x010102c0, Boolean),
+    attr("screenSize", 0x010102ca, Enum(SCREEN_SIZE)),
+    attr("screenDensity", 0x010102cb, Enum(SCREEN_DENSITY)),
+    attr("hardwareAccelerated", 0x010102d3, Boolean),
+    attr("largeHeap", 0x0101035a, Boolean),
+    attr("requiresSmallestWidthDp", 0x01010364, Integer),
+    attr("compatibleWidthLimitDp", 0x01010365, Integer),
+    attr("largestWidthLimitDp", 0x01010366, Integer),
+    attr("uiOptions", 0x01010398, Flags(UI_OPTIONS)),
+    attr("parentActivityName", 0x010103a7, String),
+    attr("isolatedProcess", 0x010103a9, Boolean),
+    attr("supportsRtl", 0x010103af, Boolean),
+    attr("restrictedAccountType", 0x010103d5, String),
+    attr("requiredAccountType", 0x010103d6, String),
+    attr("banner", 0x010103f2, Reference("drawable")),
+    attr("isGame", 0x010103f4, Boolean),
+    attr("allowEmbedded", 0x010103f5, Boolean),
+    attr("persistableMode", 0x0101042d, Enum(PERSISTABLE_MODE)),
+    attr("documentLaunchMode", 0x01010445, Enum(DOCUMENT_LAUNCH_MODE)),
+    attr("maxRecents", 0x01010446, Integer),
+    attr("autoRemoveFromRecents", 0x01010447, Boolean),
+    attr("fullBackupOnly", 0x01010473, Boolean),
+    attr("relinquishTaskIdentity", 0x01010476, Boolean),
+    attr("extractNativeLibs", 0x010104ea, Boolean),
+    attr("fullBackupContent", 0x010104eb, Reference("xml")),
+    attr("usesCleartextTraffic", 0x010104ec, Boolean),
+    attr("lockTaskMode", 0x010104ed, Enum(LOCK_TASK_MODE)),
+    attr("autoVerify", 0x010104ee, Boolean),
+    attr("showForAllUsers", 0x010104ef, Boolean),
+    attr("defaultWidth", 0x010104f4, Dimension),
+    attr("defaultHeight", 0x010104f5, Dimension),
+    attr("resizeableActivity", 0x010104f6, Boolean),
+    attr("supportsPictureInPicture", 0x010104f7, Boolean),
+    attr("directBootAware", 0x01010505, Boolean),
+    attr("version", 0x01010519, Integer),
+    attr("backupInForeground", 0x0101051a, Boolean),
+    attr("enableVrMode", 0x01010525, String),
+    attr("networkSecurityConfig", 0x01010527, Reference("xml")),
+    attr("roundIcon", 0x0101052c, Reference("drawable")),
+    attr("targetProcesses", 0x01010541, String),
+    attr("appCategory", 0x01010545, Enum(APP_CATEGORY)),
+    attr("certDigest", 0x01010548, String),
+    attr("colorMode", 0x0101054a, Enum(COLOR_MODE)),
+    attr("targetSandboxVersion", 0x0101054c, Integer),
+    attr("maxAspectRatio", 0x01010560, Float),
+    attr("showWhenLocked", 0x01010569, Boolean),
+    attr("turnScreenOn", 0x0101056a, Boolean),
+    attr("compileSdkVersion", 0x01010572, Integer),
+    attr("compileSdkVersionCodename", 0x01010573, String),
+    attr("versionMajor", 0x01010577, Integer),
+    attr("appComponentFactory", 0x0101057a, String),
+    attr("packageType", 0x01010587, String),
+    attr("usesNonSdkApi", 0x0101058e, Boolean),
+    attr("shell", 0x01010594, Boolean),
+    attr("useAppZygote", 0x01010597, Boolean),
+    attr(
+        "foregroundServiceType",
+        0x01010599,
+        Flags(FOREGROUND_SERVICE_TYPE),
+    ),
+    attr("hasFragileUserData", 0x0101059a, Boolean),
+    attr("minAspectRatio", 0x0101059b, Float),
+    attr("inheritShowWhenLocked", 0x0101059c, Boolean),
+    attr("zygotePreloadName", 0x0101059d, String),
+    attr("allowAudioPlaybackCapture", 0x01010601, Boolean),
+    attr("requestLegacyExternalStorage", 0x01010603, Boolean),
+    attr("preferMinimalPostProcessing", 0x0101060c, Boolean),
+    attr("crossProfile", 0x0101060e, Boolean),
+    attr("sdkVersion", 0x01010610, Integer),
+    attr("minExtensionVersion", 0x01010611, Integer),
+    attr("allowNativeHeapPointerTagging", 0x01010612, Boolean),
+    attr("preserveLegacyExternalStorage", 0x01010614, Boolean),
+    attr("gwpAsanMode", 0x01010616, Enum(GWP_ASAN_MODE)),
+    attr("pathSuffix", 0x0101061e, String),
+    attr("pathAdvancedPattern", 0x01010620, String),
+    attr("memtagMode", 0x01010624, Enum(MEMTAG_MODE)),
+    attr("nativeHeapZeroInitialized", 0x01010625, Boolean),
+    attr("knownCerts", 0x0101062a, Reference("array")),
+    attr("dataExtractionRules", 0x0101063e, Reference("xml")),
+    attr("attributionTags", 0x01010642, String),
+    attr(
+        "usesPermissionFlags",
+        0x01010644,
+        Flags(USES_PERMISSION_FLAGS),
+    ),
+    attr("requestRawExternalStorageAccess", 0x01010645, Boolean),
+    attr("attributionsAreUserVisible", 0x0101064b, Boolean),
+    attr("sharedUserMaxSdkVersion", 0x0101064d, Integer),
+    attr("canDisplayOnRemoteDevices", 0x01010650, Boolean),
+    attr("resetEnabledSettingsOnAppDataCleared", 0x01010652, Boolean),
+    attr("localeConfig", 0x0101065b, Reference("xml")),
+    attr("allowUntrustedActivityEmbedding", 0x01010669, Boolean),
+    attr(
+        "knownActivityEmbeddingCerts",
+        0x0101066a,
+        Reference("array"),
+    ),
+    attr("enableOnBackInvokedCallback", 0x0101066c, Boolean),
+    attr("requiredDisplayCategory", 0x01010679, String),
+    attr("allowSharedIsolatedProcess", 0x0101067d, Boolean),
+    attr(
+        "requireContentUriPermissionFromCaller",
+        0x0101069b,
+        Enum(REQUIRE_CONTENT_URI_PERMISSION),
+    ),
+    attr("allowCrossUidActivitySwitchFromBelow", 0x010106a1, Boolean),
+];
+
+const APP_CATEGORY: &[(&str, u32)] = &[
+    ("game", 0),
+    ("audio", 1),
+    ("video", 2),
+    ("image", 3),
+    ("social", 4),
+    ("news", 5),
+    ("maps", 6),
+    ("productivity", 7),
+    ("accessibility", 8),
+];
+
+const COLOR_MODE: &[(&str, u32)] = &[("default", 0), ("wideColorGamut", 1), ("hdr", 2)];
+
+const CONFIG_CHANGES: &[(&str, u32)] = &[
+    ("mcc", 0x0001),
+    ("mnc", 0x0002),
+    ("locale", 0x0004),
+    ("touchscreen", 0x0008),
+    ("keyboard", 0x0010),
+    ("keyboardHidden", 0x0020),
+    ("navigation", 0x0040),
+    ("orientation", 0x0080),
+    ("screenLayout", 0x0100),
+    ("uiMode", 0x0200),
+    ("screenSize", 0x0400),
+    ("smallestScreenSize", 0x0800),
+    ("density", 0x1000),
+    ("layoutDirection", 0x2000),
+    ("colorMode", 0x4000),
+    ("grammaticalGender", 0x8000),
+    ("fontWeightAdjustment", 0x1000_0000),
+    ("fontScale", 0x4000_0000),
+];
+
+const DOCUMENT_LAUNCH_MODE: &[(&str, u32)] = &[
+    ("none", 0),
+    ("intoExisting", 1),
+    ("always", 2),
+    ("never", 3),
+];
+
+const FOREGROUND_SERVICE_TYPE: &[(&str, u32)] = &[
+    ("dataSync", 0x0001),
+    ("mediaPlayback", 0x0002),
+    ("phoneCall", 0x0004),
+    ("location", 0x0008),
+    ("connectedDevice", 0x0010),
+    ("mediaProjection", 0x0020),
+    ("camera", 0x0040),
+    ("microphone", 0x0080),
+    ("health", 0x0100),
+    ("remoteMessaging", 0x0200),
+    ("systemExempted", 0x0400),
+    ("shortService", 0x0800),
+    ("mediaProcessing", 0x2000),
+    ("specialUse", 0x4000_0000),
+];
+
+const GWP_ASAN_MODE: &[(&str, u32)] = &[("default", 0xffff_ffff), ("never", 0), ("always", 1)];
+
+const INSTALL_LOCATION: &[(&str, u32)] = &[("auto", 0), ("internalOnly", 1), ("preferExternal", 2)];
+
+const LAUNCH_MODE: &[(&str, u32)] = &[
+    ("standard", 0),
+    ("singleTop", 1),
+    ("singleTask", 2),
+    ("singleInstance", 3),
+    ("singleInstancePerTask", 4),
+];
+
+const LOCK_TASK_MODE: &[(&str, u32)] = &[
+    ("normal", 0),
+    ("never", 1),
+    ("always", 2),
+    ("if_whitelisted", 3),
+];
+
+const MEMTAG_MODE: &[(&str, u32)] = &[
+    ("default", 0xffff_ffff),
+    ("off", 0),
+    ("async", 1),
+    ("sync", 2),
+];
+
+const PERSISTABLE_MODE: &[(&str, u32)] = &[
+    ("persistRootOnly", 0),
+    ("persistNever", 1),
+    ("persistAcrossReboots", 2),
+];
+
+const PROTECTION_LEVEL: &[(&str, u32)] = &[
+    ("normal", 0),
+    ("dangerous", 1),
+    ("signature", 2),
+    ("signatureOrSystem", 3),
+    ("internal", 4),
+    ("privileged", 0x10),
+    ("system", 0x10),
+    ("development", 0x20),
+    ("appop", 0x40),
+    ("pre23", 0x80),
+    ("installer", 0x100),
+    ("verifier", 0x200),
+    ("preinstalled", 0x400),
+    ("setup", 0x800),
+    ("instant", 0x1000),
+    ("runtime", 0x2000),
+    ("oem", 0x4000),
+    ("vendorPrivileged", 0x8000),
+    ("textClassifier", 0x10000),
+    ("configurator", 0x80000),
+    ("incidentReportApprover", 0x100000),
+    ("appPredictor", 0x200000),
+    ("module", 0x400000),
+    ("companion", 0x800000),
+    ("retailDemo", 0x1000000),
+    ("recents", 0x2000000),
+    ("role", 0x4000000),
+    ("knownSigner", 0x8000000),
+];
+
+const REQ_KEYBOARD_TYPE: &[(&str, u32)] = &[
+    ("undefined", 0),
+    ("nokeys", 1),
+    ("qwerty", 2),
+    ("twelvekey", 3),
+];
+
+const REQ_NAVIGATION: &[(&str, u32)] = &[
+    ("undefined", 0),
+    ("nonav", 1),
+    ("dpad", 2),
+    ("trackball", 3),
+    ("wheel", 4),
+];
+
+const REQ_TOUCH_SCREEN: &[(&str, u32)] = &[
+    ("undefined", 0),
+    ("notouch", 1),
+    ("stylus", 2),
+    ("finger", 3),
+];
+
+const REQUIRE_CONTENT_URI_PERMISSION: &[(&str, u32)] = &[
+    ("none", 0),
+    ("read", 1),
+    ("write", 2),
+    ("readOrWrite", 3),
+    ("readAndWrite", 4),
+];
+
+const SCREEN_DENSITY: &[(&str, u32)] = &[
+    ("ldpi", 120),
+    ("mdpi", 160),
+    ("hdpi", 240),
+    ("xhdpi", 320),
+    ("xxhdpi", 480),
+    ("xxxhdpi", 640),
+];
+
+const SCREEN_ORIENTATION: &[(&str, u32)] = &[
+    ("unspecified", 0xffff_ffff),
+    ("landscape", 0),
+    ("portrait", 1),
+    ("user", 2),
+    ("behind", 3),
+    ("sensor", 4),
+    ("nosensor", 5),
+    ("sensorLandscape", 6),
+    ("sensorPortrait", 7),
+    ("reverseLandscape", 8),
+    ("reversePortrait", 9),
+    ("fullSensor", 10),
+    ("userLandscape", 11),
+    ("userPortrait", 12),
+    ("fullUser", 13),
+    ("locked", 14),
+];
+
+const SCREEN_SIZE: &[(&str, u32)] = &[
+    ("small", 200),
+    ("normal", 300),
+    ("large", 400),
+    ("xlarge", 500),
+];
+
+const UI_OPTIONS: &[(&str, u32)] = &[("none", 0), ("splitActionBarWhenNarrow", 1)];
+
+const USES_PERMISSION_FLAGS: &[(&str, u32)] = &[("neverForLocation", 0x10000)];
+
+const WINDOW_SOFT_INPUT_MODE: &[(&str, u32)] = &[
+    ("stateUnspecified", 0x00),
+    ("stateUnchanged", 0x01),
+    ("stateHidden", 0x02),
+    ("stateAlwaysHidden", 0x03),
+    ("stateVisible", 0x04),
+    ("stateAlwaysVisible", 0x05),
+    ("adjustUnspecified", 0x00),
+    ("adjustResize", 0x10),
+    ("adjustPan", 0x20),
+    ("adjustNothing", 0x30),
+];
+
+pub(crate) fn by_id(id: u32) -> Option<&'static AttributeInfo> {
+    ANDROID_ATTRIBUTES
+        .binary_search_by_key(&id, |info| info.id)
+        .ok()
+        .map(|index| &ANDROID_ATTRIBUTES[index])
+}
+
+pub(crate) fn by_name(name: &str) -> Option<&'static AttributeInfo> {
+    ANDROID_ATTRIBUTES.iter().find(|info| info.name == name)
+}
+
+/// Returns the symbolic name of an enum value.
+pub(crate) fn enum_name(values: &[(&'static str, u32)], value: u32) -> Option<&'static str> {
+    values
+        .iter()
+        .find(|(_, v)| *v == value)
+        .map(|(name, _)| *name)
+}
+
+/// Returns the value of an enum name.
+pub(crate) fn enum_value(values: &[(&'static str, u32)], name: &str) -> Option<u32> {
+    values.iter().find(|(n, _)| *n == name).map(|(_, v)| *v)
+}
+
+/// Splits a flags value into symbolic names, `|`-separated, the way `aapt` prints it.
+///
+/// Flags are matched from the largest value down so multi-bit values like
+/// `stateAlwaysHidden` win over their single-bit components. Returns [`None`] if some
+/// bits don't correspond to any flag.
+pub(crate) fn flags_names(
+    values: &[(&'static str, u32)],
+    value: u32,
+) -> Option<std::string::String> {
+    if value == 0 {
+        return enum_name(values, 0).map(str::to_string);
+    }
+    let mut sorted: Vec<_> = values.iter().filter(|(_, v)| *v != 0).collect();
+    sorted.sort_by_key(|(_, v)| std::cmp::Reverse(*v));
+    let mut remaining = value;
+    let mut names = Vec::new();
+    for (name, flag) in sorted {
+        if remaining & flag == *flag {
+            names.push(*name);
+            remaining &= !flag;
+        }
+    }
+    if remaining != 0 {
+        return None;
+    }
+    names.reverse();
+    Some(names.join("|"))
+}
@@ -0,0 +1,337 @@
+use super::attributes::{self, AttributeFormat, AttributeInfo};
+use super::string_pool::StringPool;
+use super::*;
+use crate::xml_tree::{XmlAttribute, XmlElement, XmlNode, ANDROID_NAMESPACE};
+
+/// Decodes binary XML into an [`XmlElement`] tree with attribute values rendered the way
+/// they would be written in a source `AndroidManifest.xml`.
+pub(crate) fn decode(data: &[u8]) -> Result<XmlElement, String> {
+    if read_u16(data, 0)? != RES_XML_TYPE {
+        return Err("not a binary XML document".to_string());
+    }
+    let header_size = read_u16(data, 2)? as usize;
+    let size = (read_u32(data, 4)? as usize).min(data.len());
+    let mut decoder = Decoder::default();
+    let mut offset = header_size;
+    while offset + 8 <= size {
+        let chunk_type = read_u16(data, offset)?;
+        let chunk_size = read_u32(data, offset + 4)? as usize;
+        if chunk_size < 8 || offset + chunk_size > size {
+            return Err(format!("malformed chunk at offset {}", offset));
+        }
+        decoder.chunk(chunk_type, &data[offset..offset + chunk_size])?;
+        offset += chunk_size;
+    }
+    decoder
+        .root
+        .ok_or_else(|| "binary XML document has no root element".to_string())
+}
+
+#[derive(Default)]
+struct Decoder {
+    strings: StringPool,
+    resource_ids: Vec<u32>,
+    /// Namespaces in scope, innermost last.
+    namespaces: Vec<(String, String)>,
+    /// Namespaces started since the last element, declared on the next one.
+    pending_namespaces: Vec<(String, String)>,
+    stack: Vec<XmlElement>,
+    root: Option<XmlElement>,
+}
+
+impl Decoder {
+    fn chunk(&mut self, chunk_type: u16, chunk: &[u8]) -> Result<(), String> {
+        match chunk_type {
+            RES_STRING_POOL_TYPE => self.strings = StringPool::parse(chunk)?,
+            RES_XML_RESOURCE_MAP_TYPE => {
+                let header_size = read_u16(chunk, 2)? as usize;
+                self.resource_ids = (header_size..chunk.len())
+                    .step_by(4)
+                    .map(|offset| read_u32(chunk, offset))
+                    .collect::<Result<_, _>>()?;
+            }
+            RES_XML_START_NAMESPACE_TYPE => {
+                let ext = read_u16(chunk, 2)? as usize;
+                let uri = self.string(read_u32(chunk, ext + 4)?)?;
+                let prefix = if uri == ANDROID_NAMESPACE {
+                    "android".to_string()
+                } else {
+                    self.string(read_u32(chunk, ext)?)?
+                };
+                self.namespaces.push((prefix.clone(), uri.clone()));
+                self.pending_namespaces.push((prefix, uri));
+            }
+            RES_XML_END_NAMESPACE_TYPE => {
+                self.namespaces.pop();
+            }
+            RES_XML_START_ELEMENT_TYPE => {
+                let element = self.start_element(chunk)?;
+                self.stack.push(element);
+            }
+            RES_XML_END_ELEMENT_TYPE => {
+                let element = self
+                    .stack
+                    .pop()
+                    .ok_or_else(|| "unbalanced end of element".to_string())?;
+                match self.stack.last_mut() {
+                    Some(parent) => parent.children.push(XmlNode::Element(element)),
+                    None => self.root = Some(element),
+                }
+            }
+            RES_XML_CDATA_TYPE => {
+                let ext = read_u16(chunk, 2)? as usize;
+                let text = self.string(read_u32(chunk, ext)?)?;
+                if let Some(parent) = self.stack.last_mut() {
+                    parent.children.push(XmlNode::Text(text));
+                }
+            }
+            // Unknown chunks are skipped, like the platform parser does.
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn start_element(&mut self, chunk: &[u8]) -> Result<XmlElement, String> {
+        let ext = read_u16(chunk, 2)? as usize;
+        let mut element = XmlElement::new(self.string(read_u32(chunk, ext + 4)?)?);
+        element.namespaces = std::mem::take(&mut self.pending_namespaces);
+        let attribute_start = read_u16(chunk, ext + 8)? as usize;
+        let attribute_size = read_u16(chunk, ext + 10)? as usize;
+        let attribute_count = read_u16(chunk, ext + 12)? as usize;
+        for i in 0..attribute_count {
+            let offset = ext + attribute_start + i * attribute_size;
+            let namespace = read_u32(chunk, offset)?;
+            let name = read_u32(chunk, offset + 4)?;
+            let raw_value = read_u32(chunk, offset + 8)?;
+            let data_type = *chunk
+                .get(offset + 15)
+                .ok_or_else(|| format!("unexpected end of data at offset {}", offset + 15))?;
+            let data = read_u32(chunk, offset + 16)?;
+
+            let prefix = if namespace == NO_ENTRY {
+                None
+            } else {
+                let uri = self.string(namespace)?;
+                self.namespaces
+                    .iter()
+                    .rev()
+                    .find(|(_, u)| *u == uri)
+                    .map(|(prefix, _)| prefix.clone())
+            };
+            // Attribute names can be stripped or obfuscated, the resource ID is what
+            // the platform actually uses to identify `android:` attributes.
+            let info = self
+                .resource_ids
+                .get(name as usize)
+                .and_then(|id| attributes::by_id(*id));
+            let name = match info {
+                Some(info) => info.name.to_string(),
+                None => self.string(name)?,
+            };
+            let value = if raw_value != NO_ENTRY {
+                self.string(raw_value)?
+            } else if data_type == TYPE_STRING {
+                self.string(data)?
+            } else {
+                typed_value(data_type, data, info)
+            };
+            element.attributes.push(XmlAttribute {
+                prefix,
+                name,
+                value,
+            });
+        }
+        Ok(element)
+    }
+
+    fn string(&self, index: u32) -> Result<String, String> {
+        self.strings.get(index).map(str::to_string)
+    }
+}
+
+/// Renders a typed value the way it would be written in a source `AndroidManifest.xml`.
+/// String values are stored in the string pool and must be looked up by the caller.
+pub(crate) fn typed_value(data_type: u8, data: u32, info: Option<&AttributeInfo>) -> String {
+    let format = info.map(|info| info.format);
+    match data_type {
+        TYPE_NULL => String::new(),
+        TYPE_REFERENCE | TYPE_DYNAMIC_REFERENCE if data == 0 => "@null".to_string(),
+        TYPE_REFERENCE | TYPE_DYNAMIC_REFERENCE => {
+            let package = if data >> 24 == 0x01 { "android:" } else { "" };
+            match format {
+                Some(AttributeFormat::Reference(resource_type)) => {
+                    format!("@{}{}/0x{:08x}", package, resource_type, data)
+                }
+                _ => format!("@{}0x{:08x}", package, data),
+            }
+        }
+        TYPE_ATTRIBUTE => format!("?0x{:08x}", data),
+        TYPE_FLOAT => f32::from_bits(data).to_string(),
+        TYPE_DIMENSION => {
+            const UNITS: [&str; 6] = ["px", "dp", "sp", "pt", "in", "mm"];
+            let unit = UNITS.get((data & 0xf) as usize).unwrap_or(&"");
+            format!("{}{}", complex_to_float(data), unit)
+        }
+        TYPE_FRACTION => {
+            let unit = if data & 0xf == 0 { "%" } else { "%p" };
+            format!("{}{}", complex_to_float(data) * 100.0, unit)
+        }
+        TYPE_INT_BOOLEAN => (data != 0).to_string(),
+        TYPE_INT_DEC | TYPE_INT_HEX => match format {
+            Some(AttributeFormat::Enum(values)) => attributes::enum_name(values, data)
+                .map(str::to_string)
+                .unwrap_or_else(|| (data as i32).to_string()),
+            Some(AttributeFormat::Flags(values)) => {
+                attributes::flags_names(values, data).unwrap_or_else(|| format!("0x{:08x}", data))
+            }
+            _ if data_type == TYPE_INT_HEX => format!("0x{:08x}", data),
+            _ => (data as i32).to_string(),
+        },
+        TYPE_FIRST_COLOR_INT..=TYPE_LAST_COLOR_INT => format!("#{:08x}", data),
+        _ => format!("0x{:08x}", data),
+    }
+}
+
+/// Converts a complex dimension or fraction value to a float.
+pub(super) fn complex_to_float(complex: u32) -> f32 {
+    const RADIX_MULTIPLIERS: [f32; 4] = [
+        1.0 / (1 << 8) as f32,
+        1.0 / (1 << 15) as f32,
+        1.0 / (1 << 23) as f32,
+        1.0 / (1u32 << 31) as f32,
+    ];
+    let mantissa = (complex & 0xffff_ff00) as i32 as f32;
+    mantissa * RADIX_MULTIPLIERS[((complex >> 4) & 0x3) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(chunk_type: u16, header_size: u16, body: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(chunk_type.to_le_bytes());
+        out.extend(header_size.to_le_bytes());
+        out.extend((body.len() as u32 + 8).to_le_bytes());
+        out.extend(body);
+        out
+    }
+
+    fn node(chunk_type: u16, ext: &[u32]) -> Vec<u8> {
+        let mut body = vec![1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff];
+        body.extend(ext.iter().flat_map(|v| v.to_le_bytes()));
+        chunk(chunk_type, 0x10, &body)
+    }
+
+    fn start_element(name: u32, attributes: &[(u32, u32, u8, u32)]) -> Vec<u8> {
+        let mut body = vec![1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff];
+        body.extend(NO_ENTRY.to_le_bytes());
+        body.extend(name.to_le_bytes());
+        for v in [0x14u16, 0x14, attributes.len() as u16, 0, 0, 0] {
+            body.extend(v.to_le_bytes());
+        }
+        for (namespace, name, data_type, data) in attributes {
+            body.extend(namespace.to_le_bytes());
+            body.extend(name.to_le_bytes());
+            let raw_value = if *data_type == TYPE_STRING {
+                *data
+            } else {
+                NO_ENTRY
+            };
+            body.extend(raw_value.to_le_bytes());
+            body.extend([8, 0, 0, *data_type]);
+            body.extend(data.to_le_bytes());
+        }
+        chunk(RES_XML_START_ELEMENT_TYPE, 0x10, &body)
+    }
+
+    fn string_pool(strings: &[&str]) -> Vec<u8> {
+        let mut offsets = Vec::new();
+        let mut data = Vec::new();
+        for string in strings {
+            offsets.extend((data.len() as u32).to_le_bytes());
+            let units: Vec<u16> = string.encode_utf16().collect();
+            data.extend((units.len() as u16).to_le_bytes());
+            data.extend(units.iter().flat_map(|u| u.to_le_bytes()));
+            data.extend([0, 0]);
+        }
+        let mut body = Vec::new();
+        for v in [strings.len() as u32, 0, 0, 0x1c + offsets.len() as u32, 0] {
+            body.extend(v.to_le_bytes());
+        }
+        body.extend(offsets);
+        body.extend(data);
+        chunk(RES_STRING_POOL_TYPE, 0x1c, &body)
+    }
+
+    #[test]
+    fn test_decode_binary_manifest() {
+        // The name of `launchMode` is stripped, as obfuscators do, and must be resolved
+        // through the resource map.
+        let strings = [
+            "versionCode",
+            "debuggable",
+            "name",
+            "",
+            "configChanges",
+            "android",
+            ANDROID_NAMESPACE,
+            "manifest",
+            "package",
+            "com.example",
+            "application",
+            "activity",
+            "com.example.MainActivity",
+        ];
+        let ids: Vec<u8> = [
+            0x0101021bu32,
+            0x0101000f,
+            0x01010003,
+            0x0101001d,
+            0x0101001f,
+        ]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+        let mut body = Vec::new();
+        body.extend(string_pool(&strings));
+        body.extend(chunk(RES_XML_RESOURCE_MAP_TYPE, 8, &ids));
+        body.extend(node(RES_XML_START_NAMESPACE_TYPE, &[5, 6]));
+        body.extend(start_element(
+            7,
+            &[(NO_ENTRY, 8, TYPE_STRING, 9), (6, 0, TYPE_INT_DEC, 3)],
+        ));
+        body.extend(start_element(10, &[(6, 1, TYPE_INT_BOOLEAN, NO_ENTRY)]));
+        body.extend(start_element(
+            11,
+            &[
+                (6, 2, TYPE_STRING, 12),
+                (6, 3, TYPE_INT_DEC, 2),
+                (6, 4, TYPE_INT_HEX, 0xa0),
+            ],
+        ));
+        body.extend(node(RES_XML_END_ELEMENT_TYPE, &[NO_ENTRY, 11]));
+        body.extend(node(RES_XML_END_ELEMENT_TYPE, &[NO_ENTRY, 10]));
+        body.extend(node(RES_XML_END_ELEMENT_TYPE, &[NO_ENTRY, 7]));
+        body.extend(node(RES_XML_END_NAMESPACE_TYPE, &[5, 6]));
+        let document = chunk(RES_XML_TYPE, 8, &body);
+
+        let manifest = crate::from_binary_xml(&document).unwrap();
+        assert_eq!(manifest.package, "com.example");
+        assert_eq!(manifest.version_code, Some(crate::VarOr::Value(3)));
+        assert_eq!(manifest.application.debuggable, Some(true.into()));
+        let activity = &manifest.application.activity[0];
+        assert_eq!(activity.name, "com.example.MainActivity");
+        assert_eq!(
+            activity.launch_mode,
+            Some(crate::VarOr::Value(crate::LaunchMode::SingleTask))
+        );
+        assert_eq!(
+            activity.config_changes,
+            crate::AttributeList::from_vec(vec![
+                crate::ConfigChanges::KeyboardHidden,
+                crate::ConfigChanges::Orientation,
+            ])
+        );
+    }
+}
@@ -0,0 +1,505 @@
+use super::attributes::{self, AttributeFormat, AttributeInfo};
+use super::string_pool::StringPool;
+use super::*;
+use crate::xml_tree::{XmlElement, XmlNode, ANDROID_NAMESPACE};
+use std::collections::HashMap;
+
+/// Source positions aren't tracked, every node is reported on the first line.
+const LINE_NUMBER: u32 = 1;
+
+/// Encodes an [`XmlElement`] tree into binary XML the way `aapt2` compiles a source
+/// `AndroidManifest.xml`.
+///
+/// `android:` attributes are written with their framework resource IDs and values typed
+/// according to the attribute format. References to app resources are looked up in
+/// `resources`, keyed like `mipmap/ic_launcher` or `android:style/Theme.Black`, unless
+/// they already carry a resource ID like `@drawable/0x7f080001`.
+pub(crate) fn encode(
+    root: &XmlElement,
+    resources: &HashMap<String, u32>,
+) -> Result<Vec<u8>, String> {
+    // The platform looks up attributes through the resource map, which is indexed in
+    // parallel to the first entries of the string pool.
+    let mut attributes = Vec::new();
+    collect_attributes(root, &mut Vec::new(), &mut attributes);
+    attributes.sort_unstable_by_key(|info| info.id);
+    attributes.dedup_by_key(|info| info.id);
+
+    let mut encoder = Encoder {
+        resources,
+        strings: Vec::new(),
+        indices: HashMap::new(),
+        namespaces: Vec::new(),
+        body: Vec::new(),
+    };
+    for info in &attributes {
+        encoder.string(info.name);
+    }
+    encoder.element(root)?;
+
+    let mut resource_map = Vec::with_capacity(attributes.len() * 4);
+    for info in &attributes {
+        resource_map.extend(info.id.to_le_bytes());
+    }
+    let mut body = StringPool::new(encoder.strings).to_chunk();
+    body.extend(chunk(RES_XML_RESOURCE_MAP_TYPE, 8, &[], &resource_map));
+    body.extend(encoder.body);
+    Ok(chunk(RES_XML_TYPE, 8, &[], &body))
+}
+
+/// Collects the known `android:` attributes used in the tree.
+fn collect_attributes(
+    element: &XmlElement,
+    scope: &mut Vec<(String, String)>,
+    found: &mut Vec<&'static AttributeInfo>,
+) {
+    let scope_len = scope.len();
+    scope.extend(element.namespaces.iter().cloned());
+    for attribute in &element.attributes {
+        let uri = attribute
+            .prefix
+            .as_deref()
+            .and_then(|prefix| lookup(scope, prefix));
+        if uri == Some(ANDROID_NAMESPACE) {
+            if let Some(info) = attributes::by_name(&attribute.name) {
+                found.push(info);
+            }
+        }
+    }
+    for child in &element.children {
+        if let XmlNode::Element(child) = child {
+            collect_attributes(child, scope, found);
+        }
+    }
+    scope.truncate(scope_len);
+}
+
+fn lookup<'a>(scope: &'a [(String, String)], prefix: &str) -> Option<&'a str> {
+    scope
+        .iter()
+        .rev()
+        .find(|(p, _)| p == prefix)
+        .map(|(_, uri)| uri.as_str())
+}
+
+struct Encoder<'a> {
+    resources: &'a HashMap<String, u32>,
+    strings: Vec<String>,
+    indices: HashMap<String, u32>,
+    /// Namespaces in scope, innermost last.
+    namespaces: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+struct Attribute {
+    namespace: u32,
+    name: u32,
+    raw_value: u32,
+    data_type: u8,
+    data: u32,
+    resource_id: Option<u32>,
+}
+
+impl Encoder<'_> {
+    fn element(&mut self, element: &XmlElement) -> Result<(), String> {
+        for (prefix, uri) in &element.namespaces {
+            let ext = [self.string(prefix), self.string(uri)];
+            self.node(RES_XML_START_NAMESPACE_TYPE, &ext);
+            self.namespaces.push((prefix.clone(), uri.clone()));
+        }
+
+        let (namespace, name) = self.qualified_name(&element.name)?;
+        let mut attributes = Vec::with_capacity(element.attributes.len());
+        for attribute in &element.attributes {
+            let namespace = match &attribute.prefix {
+                Some(prefix) => Some(self.namespace_uri(prefix)?),
+                None => None,
+            };
+            let info = match namespace.as_deref() {
+                Some(ANDROID_NAMESPACE) => attributes::by_name(&attribute.name),
+                _ => None,
+            };
+            let (data_type, data) = match self.typed_value(&attribute.value, info)? {
+                Some(typed) => typed,
+                None => (TYPE_STRING, self.string(&attribute.value)),
+            };
+            attributes.push(Attribute {
+                namespace: namespace.map_or(NO_ENTRY, |uri| self.string(&uri)),
+                name: self.string(&attribute.name),
+                raw_value: if data_type == TYPE_STRING {
+                    data
+                } else {
+                    NO_ENTRY
+                },
+                data_type,
+                data,
+                resource_id: info.map(|info| info.id),
+            });
+        }
+        // Attributes with resource IDs come first, sorted by ID, as the platform's
+        // attribute lookup relies on that order.
+        attributes.sort_by_key(|attribute| attribute.resource_id.unwrap_or(u32::MAX));
+        let special_index = |special: &str| {
+            let index = self.indices.get(special).copied();
+            attributes
+                .iter()
+                .position(|a| a.namespace == NO_ENTRY && Some(a.name) == index)
+                .map_or(0, |position| position as u16 + 1)
+        };
+        let id_index = special_index("id");
+        let class_index = special_index("class");
+        let style_index = special_index("style");
+
+        let mut ext = Vec::with_capacity(20 + attributes.len() * 20);
+        ext.extend(namespace.to_le_bytes());
+        ext.extend(name.to_le_bytes());
+        for value in [
+            0x14,
+            0x14,
+            attributes.len() as u16,
+            id_index,
+            class_index,
+            style_index,
+        ] {
+            ext.extend(value.to_le_bytes());
+        }
+        for attribute in &attributes {
+            ext.extend(attribute.namespace.to_le_bytes());
+            ext.extend(attribute.name.to_le_bytes());
+            ext.extend(attribute.raw_value.to_le_bytes());
+            ext.extend([8, 0, 0, attribute.data_type]);
+            ext.extend(attribute.data.to_le_bytes());
+        }
+        self.body
+            .extend(node_chunk(RES_XML_START_ELEMENT_TYPE, &ext));
+
+        for child in &element.children {
+            match child {
+                XmlNode::Element(child) => self.element(child)?,
+                XmlNode::Text(text) => {
+                    let text = self.string(text);
+                    let mut ext = Vec::with_capacity(12);
+                    ext.extend(text.to_le_bytes());
+                    ext.extend([8, 0, 0, TYPE_NULL]);
+                    ext.extend(0u32.to_le_bytes());
+                    self.body.extend(node_chunk(RES_XML_CDATA_TYPE, &ext));
+                }
+            }
+        }
+
+        self.node(RES_XML_END_ELEMENT_TYPE, &[namespace, name]);
+        for (prefix, uri) in element.namespaces.iter().rev() {
+            let ext = [self.string(prefix), self.string(uri)];
+            self.node(RES_XML_END_NAMESPACE_TYPE, &ext);
+            self.namespaces.pop();
+        }
+        Ok(())
+    }
+
+    /// Splits a possibly prefixed element name into namespace and name string indices.
+    fn qualified_name(&mut self, name: &str) -> Result<(u32, u32), String> {
+        match name.split_once(':') {
+            Some((prefix, local_name)) => {
+                let uri = self.namespace_uri(prefix)?;
+                Ok((self.string(&uri), self.string(local_name)))
+            }
+            None => Ok((NO_ENTRY, self.string(name))),
+        }
+    }
+
+    fn namespace_uri(&self, prefix: &str) -> Result<String, String> {
+        lookup(&self.namespaces, prefix)
+            .map(str::to_string)
+            .ok_or_else(|| format!("namespace prefix `{}` is not declared", prefix))
+    }
+
+    /// Compiles an attribute value into a typed value. Returns [`None`] if the value has
+    /// to be kept as a string.
+    fn typed_value(
+        &self,
+        value: &str,
+        info: Option<&AttributeInfo>,
+    ) -> Result<Option<(u8, u32)>, String> {
+        if let Some(reference) = value.strip_prefix('@') {
+            return Ok(Some((TYPE_REFERENCE, self.resource_id(reference, value)?)));
+        }
+        if let Some(reference) = value.strip_prefix('?') {
+            return Ok(Some((TYPE_ATTRIBUTE, self.resource_id(reference, value)?)));
+        }
+        // Values that don't fit the format, like `${placeholder}`s, are kept as strings.
+        Ok(info.and_then(|info| compile_value(value, info.format)))
+    }
+
+    /// Resolves a resource reference, `value` without its leading `@` or `?`.
+    fn resource_id(&self, reference: &str, value: &str) -> Result<u32, String> {
+        if let Some(id) = known_resource_id(reference, value)? {
+            return Ok(id);
+        }
+        let reference = reference.strip_prefix('+').unwrap_or(reference);
+        self.resources
+            .get(reference)
+            .copied()
+            .ok_or_else(|| format!("unresolved resource reference `{}`", value))
+    }
+
+    /// Returns the string pool index of `string`, adding it to the pool if needed.
+    fn string(&mut self, string: &str) -> u32 {
+        if let Some(index) = self.indices.get(string) {
+            return *index;
+        }
+        let index = self.strings.len() as u32;
+        self.strings.push(string.to_string());
+        self.indices.insert(string.to_string(), index);
+        index
+    }
+
+    fn node(&mut self, chunk_type: u16, ext: &[u32]) {
+        let ext: Vec<u8> = ext.iter().flat_map(|value| value.to_le_bytes()).collect();
+        self.body.extend(node_chunk(chunk_type, &ext));
+    }
+}
+
+/// Compiles a value that isn't a resource reference according to an attribute format.
+/// Returns [`None`] if the value doesn't fit the format.
+pub(crate) fn compile_value(value: &str, format: AttributeFormat) -> Option<(u8, u32)> {
+    match format {
+        AttributeFormat::Boolean => match value {
+            "true" => Some((TYPE_INT_BOOLEAN, 0xffff_ffff)),
+            "false" => Some((TYPE_INT_BOOLEAN, 0)),
+            _ => None,
+        },
+        AttributeFormat::Integer => parse_integer(value),
+        AttributeFormat::Hex => parse_integer(value).map(|(_, data)| (TYPE_INT_HEX, data)),
+        AttributeFormat::Float => value
+            .parse::<f32>()
+            .ok()
+            .map(|float| (TYPE_FLOAT, float.to_bits())),
+        AttributeFormat::Dimension => parse_dimension(value),
+        AttributeFormat::Enum(values) => attributes::enum_value(values, value)
+            .map(|data| (TYPE_INT_DEC, data))
+            .or_else(|| parse_integer(value)),
+        AttributeFormat::Flags(values) => value
+            .split('|')
+            .map(|name| attributes::enum_value(values, name.trim()))
+            .try_fold(0, |flags, flag| flag.map(|flag| flags | flag))
+            .map(|data| (TYPE_INT_HEX, data))
+            .or_else(|| parse_integer(value)),
+        AttributeFormat::String | AttributeFormat::Reference(_) => None,
+    }
+}
+
+/// Returns the resource ID of a reference that can be resolved without a resource
+/// table: `@null`, references carrying their ID like `@drawable/0x7f080001` and
+/// framework attributes. `reference` is `value` without its leading `@` or `?`.
+pub(crate) fn known_resource_id(reference: &str, value: &str) -> Result<Option<u32>, String> {
+    if reference == "null" {
+        return Ok(Some(0));
+    }
+    let reference = reference.strip_prefix('+').unwrap_or(reference);
+    let name = reference.rsplit(['/', ':']).next().unwrap_or(reference);
+    if let Some(id) = name.strip_prefix("0x") {
+        return u32::from_str_radix(id, 16)
+            .map(Some)
+            .map_err(|_| format!("invalid resource ID in reference `{}`", value));
+    }
+    Ok(reference
+        .strip_prefix("android:attr/")
+        .and_then(attributes::by_name)
+        .map(|info| info.id))
+}
+
+fn chunk(chunk_type: u16, header_size: u16, header: &[u8], body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + header.len() + body.len());
+    out.extend(chunk_type.to_le_bytes());
+    out.extend(header_size.to_le_bytes());
+    out.extend(((8 + header.len() + body.len()) as u32).to_le_bytes());
+    out.extend(header);
+    out.extend(body);
+    out
+}
+
+/// Writes an XML tree node chunk: the header holds the line number and a comment.
+fn node_chunk(chunk_type: u16, ext: &[u8]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(8);
+    header.extend(LINE_NUMBER.to_le_bytes());
+    header.extend(NO_ENTRY.to_le_bytes());
+    chunk(chunk_type, 0x10, &header, ext)
+}
+
+fn parse_integer(value: &str) -> Option<(u8, u32)> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16)
+            .ok()
+            .map(|data| (TYPE_INT_HEX, data)),
+        None => value
+            .parse::<i32>()
+            .ok()
+            .map(|data| (TYPE_INT_DEC, data as u32)),
+    }
+}
+
+/// Parses a dimension like `16dp` into a complex value.
+fn parse_dimension(value: &str) -> Option<(u8, u32)> {
+    const UNITS: [(&str, u32); 7] = [
+        ("px", 0),
+        ("dip", 1),
+        ("dp", 1),
+        ("sp", 2),
+        ("pt", 3),
+        ("in", 4),
+        ("mm", 5),
+    ];
+    let (number, unit) = UNITS
+        .iter()
+        .find_map(|(name, unit)| value.strip_suffix(name).map(|number| (number, *unit)))?;
+    let number = number.trim().parse::<f32>().ok()?;
+    Some((TYPE_DIMENSION, float_to_complex(number) | unit))
+}
+
+/// Converts a float to a complex value with the most precise radix that can hold it,
+/// the inverse of `complex_to_float`.
+fn float_to_complex(value: f32) -> u32 {
+    let negative = value < 0.0;
+    let bits = (value.abs() as f64 * (1u64 << 23) as f64 + 0.5) as u64;
+    let (radix, shift) = if bits & 0x7f_ffff == 0 {
+        (0, 23)
+    } else if bits & !0x7f_ffff == 0 {
+        (3, 0)
+    } else if bits & !0x7fff_ffff == 0 {
+        (2, 8)
+    } else if bits & !0x7f_ffff_ffff == 0 {
+        (1, 16)
+    } else {
+        (0, 23)
+    };
+    let mut mantissa = ((bits >> shift) & 0xff_ffff) as u32;
+    if negative {
+        mantissa = mantissa.wrapping_neg() & 0xff_ffff;
+    }
+    (radix << 4) | (mantissa << 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::decoder::complex_to_float;
+    use super::*;
+
+    #[test]
+    fn test_encode_binary_manifest() {
+        let given_xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example" android:versionCode="7" android:versionName="1.0">
+    <uses-sdk android:minSdkVersion="21" android:targetSdkVersion="33" />
+    <uses-feature android:glEsVersion="0x00020000" android:required="true" />
+    <permission android:name="com.example.permission.SYSTEM" android:protectionLevel="signature|system" />
+    <application android:label="@string/app_name" android:icon="@mipmap/0x7f0d0000" android:debuggable="${debuggable}" android:memtagMode="sync" android:appCategory="game" android:enableOnBackInvokedCallback="true">
+        <activity android:name=".MainActivity" android:launchMode="singleTask" android:configChanges="keyboardHidden|orientation|grammaticalGender" android:minAspectRatio="1.5" android:requireContentUriPermissionFromCaller="readOrWrite" android:allowCrossUidActivitySwitchFromBelow="false" android:enableVrMode="com.example.VrListener" android:requiredDisplayCategory="automotive_displays" android:theme="@android:style/Theme.Black.NoTitleBar" android:exported="true" />
+    </application>
+</manifest>"#;
+        let manifest = crate::from_str(given_xml).unwrap();
+        assert!(crate::to_binary_xml(&manifest).is_err());
+
+        let resources = [
+            ("string/app_name".to_string(), 0x7f100000),
+            (
+                "android:style/Theme.Black.NoTitleBar".to_string(),
+                0x01030007,
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let bytes = crate::to_binary_xml_with_resources(&manifest, &resources).unwrap();
+        let root = decode(&bytes).unwrap();
+        let application = root
+            .children
+            .iter()
+            .find_map(|child| match child {
+                XmlNode::Element(element) if element.name == "application" => Some(element),
+                _ => None,
+            })
+            .unwrap();
+        let value = |element: &XmlElement, name: &str| {
+            element
+                .attributes
+                .iter()
+                .find(|attribute| attribute.name == name)
+                .map(|attribute| attribute.value.clone())
+        };
+        assert_eq!(
+            value(application, "label"),
+            Some("@string/0x7f100000".into())
+        );
+        assert_eq!(
+            value(application, "icon"),
+            Some("@drawable/0x7f0d0000".into())
+        );
+
+        let decoded = crate::from_binary_xml(&bytes).unwrap();
+        let activity = &decoded.application.activity[0];
+        assert_eq!(
+            activity.launch_mode,
+            manifest.application.activity[0].launch_mode
+        );
+        assert_eq!(
+            activity.config_changes,
+            manifest.application.activity[0].config_changes
+        );
+        assert_eq!(activity.exported, Some(true.into()));
+        assert_eq!(activity.min_aspect_ratio, Some(crate::VarOr::Value(1.5)));
+        assert_eq!(
+            activity.require_content_uri_permission_from_caller,
+            Some(crate::VarOr::Value(
+                crate::RequireContentUriPermission::ReadOrWrite
+            ))
+        );
+        assert_eq!(
+            activity.allow_cross_uid_activity_switch_from_below,
+            Some(false.into())
+        );
+        assert_eq!(
+            activity.enable_vr_mode.as_deref(),
+            Some("com.example.VrListener")
+        );
+        assert_eq!(
+            activity.required_display_category.as_deref(),
+            Some("automotive_displays")
+        );
+        assert_eq!(
+            decoded.application.app_category,
+            Some(crate::VarOr::Value(crate::AppCategory::Game))
+        );
+        assert_eq!(
+            decoded.application.memtag_mode,
+            Some(crate::VarOr::Value(crate::MemtagMode::Sync))
+        );
+        assert_eq!(
+            decoded.application.enable_on_back_invoked_callback,
+            Some(true.into())
+        );
+        assert_eq!(decoded.package, manifest.package);
+        assert_eq!(decoded.version_code, Some(crate::VarOr::Value(7)));
+        assert_eq!(decoded.uses_sdk, manifest.uses_sdk);
+        assert_eq!(decoded.uses_feature, manifest.uses_feature);
+        assert_eq!(
+            decoded.permission[0]
+                .protection_level
+                .as_ref()
+                .and_then(crate::VarOr::value)
+                .map(ToString::to_string),
+            Some("signature|privileged".to_string())
+        );
+        assert_eq!(
+            decoded.application.debuggable,
+            manifest.application.debuggable
+        );
+    }
+
+    #[test]
+    fn test_float_to_complex() {
+        for value in [0.0, 1.0, 16.0, -4.0, 0.5, 1.25, 12345.5] {
+            assert_eq!(complex_to_float(float_to_complex(value)), value);
+        }
+    }
+}
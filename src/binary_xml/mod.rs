@@ -0,0 +1,48 @@
+//! Android binary XML (AXML), the format `aapt`/`aapt2` compile `AndroidManifest.xml`
+//! into before packaging it into an APK.
+
+pub(crate) mod attributes;
+mod decoder;
+mod encoder;
+mod string_pool;
+
+pub(crate) use decoder::{decode, typed_value};
+pub(crate) use encoder::{compile_value, encode, known_resource_id};
+
+pub(crate) const RES_STRING_POOL_TYPE: u16 = 0x0001;
+pub(crate) const RES_XML_TYPE: u16 = 0x0003;
+pub(crate) const RES_XML_START_NAMESPACE_TYPE: u16 = 0x0100;
+pub(crate) const RES_XML_END_NAMESPACE_TYPE: u16 = 0x0101;
+pub(crate) const RES_XML_START_ELEMENT_TYPE: u16 = 0x0102;
+pub(crate) const RES_XML_END_ELEMENT_TYPE: u16 = 0x0103;
+pub(crate) const RES_XML_CDATA_TYPE: u16 = 0x0104;
+pub(crate) const RES_XML_RESOURCE_MAP_TYPE: u16 = 0x0180;
+
+pub(crate) const TYPE_NULL: u8 = 0x00;
+pub(crate) const TYPE_REFERENCE: u8 = 0x01;
+pub(crate) const TYPE_ATTRIBUTE: u8 = 0x02;
+pub(crate) const TYPE_STRING: u8 = 0x03;
+pub(crate) const TYPE_FLOAT: u8 = 0x04;
+pub(crate) const TYPE_DIMENSION: u8 = 0x05;
+pub(crate) const TYPE_FRACTION: u8 = 0x06;
+pub(crate) const TYPE_DYNAMIC_REFERENCE: u8 = 0x07;
+pub(crate) const TYPE_INT_DEC: u8 = 0x10;
+pub(crate) const TYPE_INT_HEX: u8 = 0x11;
+pub(crate) const TYPE_INT_BOOLEAN: u8 = 0x12;
+pub(crate) const TYPE_FIRST_COLOR_INT: u8 = 0x1c;
+pub(crate) const TYPE_LAST_COLOR_INT: u8 = 0x1f;
+
+/// Marks an absent string pool reference.
+pub(crate) const NO_ENTRY: u32 = 0xffff_ffff;
+
+pub(crate) fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| format!("unexpected end of data at offset {}", offset))
+}
+
+pub(crate) fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| format!("unexpected end of data at offset {}", offset))
+}
@@ -0,0 +1,115 @@
+use super::{read_u16, read_u32, RES_STRING_POOL_TYPE};
+
+const UTF8_FLAG: u32 = 1 << 8;
+
+/// Strings of a `ResStringPool` chunk.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub(crate) struct StringPool {
+    strings: Vec<String>,
+}
+
+impl StringPool {
+    /// Parses a string pool chunk. `chunk` must start at the chunk header.
+    pub fn parse(chunk: &[u8]) -> Result<Self, String> {
+        let header_size = read_u16(chunk, 2)? as usize;
+        let string_count = read_u32(chunk, 8)? as usize;
+        let flags = read_u32(chunk, 16)?;
+        let strings_start = read_u32(chunk, 20)? as usize;
+        let mut strings = Vec::with_capacity(string_count.min(chunk.len() / 4));
+        for i in 0..string_count {
+            let offset = read_u32(chunk, header_size + i * 4)? as usize;
+            let position = strings_start + offset;
+            let string = if flags & UTF8_FLAG != 0 {
+                read_utf8(chunk, position)?
+            } else {
+                read_utf16(chunk, position)?
+            };
+            strings.push(string);
+        }
+        Ok(Self { strings })
+    }
+
+    pub fn new(strings: Vec<String>) -> Self {
+        Self { strings }
+    }
+
+    /// Writes the pool as a UTF-16 string pool chunk without styles, the encoding
+    /// `aapt2` uses for compiled XML.
+    pub fn to_chunk(&self) -> Vec<u8> {
+        const HEADER_SIZE: usize = 0x1c;
+        let mut offsets = Vec::with_capacity(self.strings.len() * 4);
+        let mut data = Vec::new();
+        for string in &self.strings {
+            offsets.extend((data.len() as u32).to_le_bytes());
+            let units: Vec<u16> = string.encode_utf16().collect();
+            if units.len() > 0x7fff {
+                data.extend(((units.len() >> 16) as u16 | 0x8000).to_le_bytes());
+            }
+            data.extend((units.len() as u16).to_le_bytes());
+            data.extend(units.iter().flat_map(|unit| unit.to_le_bytes()));
+            data.extend([0, 0]);
+        }
+        data.resize((data.len() + 3) & !3, 0);
+        let strings_start = HEADER_SIZE + offsets.len();
+        let mut chunk = Vec::with_capacity(strings_start + data.len());
+        chunk.extend(RES_STRING_POOL_TYPE.to_le_bytes());
+        chunk.extend((HEADER_SIZE as u16).to_le_bytes());
+        chunk.extend(((strings_start + data.len()) as u32).to_le_bytes());
+        chunk.extend((self.strings.len() as u32).to_le_bytes());
+        // Style count, flags, strings start and styles start.
+        chunk.extend(0u32.to_le_bytes());
+        chunk.extend(0u32.to_le_bytes());
+        chunk.extend((strings_start as u32).to_le_bytes());
+        chunk.extend(0u32.to_le_bytes());
+        chunk.extend(offsets);
+        chunk.extend(data);
+        chunk
+    }
+
+    pub fn get(&self, index: u32) -> Result<&str, String> {
+        self.strings
+            .get(index as usize)
+            .map(String::as_str)
+            .ok_or_else(|| format!("string pool index {} is out of bounds", index))
+    }
+}
+
+fn read_utf8(data: &[u8], mut position: usize) -> Result<String, String> {
+    // The UTF-16 length precedes the byte length, we only need the latter.
+    let (_, size) = read_utf8_length(data, position)?;
+    position += size;
+    let (length, size) = read_utf8_length(data, position)?;
+    position += size;
+    let bytes = data
+        .get(position..position + length)
+        .ok_or_else(|| format!("string at offset {} is out of bounds", position))?;
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+fn read_utf8_length(data: &[u8], position: usize) -> Result<(usize, usize), String> {
+    let first = *data
+        .get(position)
+        .ok_or_else(|| format!("unexpected end of data at offset {}", position))?
+        as usize;
+    if first & 0x80 == 0 {
+        return Ok((first, 1));
+    }
+    let second = *data
+        .get(position + 1)
+        .ok_or_else(|| format!("unexpected end of data at offset {}", position + 1))?
+        as usize;
+    Ok((((first & 0x7f) << 8) | second, 2))
+}
+
+fn read_utf16(data: &[u8], mut position: usize) -> Result<String, String> {
+    let mut length = read_u16(data, position)? as usize;
+    position += 2;
+    if length & 0x8000 != 0 {
+        length = ((length & 0x7fff) << 16) | read_u16(data, position)? as usize;
+        position += 2;
+    }
+    let units = (0..length)
+        .map(|i| read_u16(data, position + i * 2))
+        .collect::<Result<Vec<u16>, String>>()?;
+    Ok(String::from_utf16_lossy(&units))
+}
@@ -0,0 +1,650 @@
+//! Builders for manifests and their components, as an alternative to struct literals
+//! with `..Default::default()`.
+//!
+//! Required values are arguments of the `builder()` functions, and `build()` checks the
+//! result, failing with [`Error::FailedToBuild`].
+
+use crate::error::{Error, Result};
+use crate::validate::{validate, Severity};
+use crate::{
+    Action, Activity, AndroidManifest, Application, AttributeList, Category, Data,
+    ForegroundServiceType, IntentFilter, LaunchMode, MetaData, MipmapOrDrawableResource, Provider,
+    Receiver, Resource, Service, StringResourceOrString, StyleResource, UsesFeature,
+    UsesPermission, UsesSdk, VarOr,
+};
+
+impl AndroidManifest {
+    /// Returns a builder of a manifest, which is checked with [`validate`] when built.
+    ///
+    /// ```
+    /// # use android_manifest::*;
+    /// let manifest = AndroidManifest::builder("com.example")
+    ///     .version_code(1)
+    ///     .version_name("1.0")
+    ///     .min_sdk_version(21)
+    ///     .target_sdk_version(34)
+    ///     .uses_permission("android.permission.INTERNET")
+    ///     .activity(
+    ///         Activity::builder(".MainActivity")
+    ///             .exported(true)
+    ///             .intent_filter(IntentFilter::launcher())
+    ///             .build()
+    ///             .unwrap(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(manifest.application.activity[0].name, ".MainActivity");
+    /// ```
+    pub fn builder(package: impl Into<String>) -> ManifestBuilder {
+        ManifestBuilder {
+            manifest: AndroidManifest {
+                package: package.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder of an [`AndroidManifest`], created with [`AndroidManifest::builder`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ManifestBuilder {
+    manifest: AndroidManifest,
+}
+
+impl ManifestBuilder {
+    pub fn version_code(mut self, version_code: u32) -> Self {
+        self.manifest.version_code = Some(VarOr::Value(version_code));
+        self
+    }
+
+    pub fn version_name(mut self, version_name: impl Into<String>) -> Self {
+        self.manifest.version_name = Some(version_name.into());
+        self
+    }
+
+    pub fn min_sdk_version(mut self, min_sdk_version: u32) -> Self {
+        self.uses_sdk().min_sdk_version = Some(VarOr::Value(min_sdk_version));
+        self
+    }
+
+    pub fn target_sdk_version(mut self, target_sdk_version: u32) -> Self {
+        self.uses_sdk().target_sdk_version = Some(VarOr::Value(target_sdk_version));
+        self
+    }
+
+    pub fn max_sdk_version(mut self, max_sdk_version: u32) -> Self {
+        self.uses_sdk().max_sdk_version = Some(VarOr::Value(max_sdk_version));
+        self
+    }
+
+    fn uses_sdk(&mut self) -> &mut UsesSdk {
+        self.manifest.uses_sdk.get_or_insert_with(Default::default)
+    }
+
+    /// Adds a `<uses-permission>`, like `android.permission.INTERNET`.
+    pub fn uses_permission(mut self, name: impl Into<String>) -> Self {
+        self.manifest.uses_permission.push(UsesPermission {
+            name: Some(name.into()),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Adds a `<uses-feature>`, like `android.hardware.camera`.
+    pub fn uses_feature(mut self, name: impl Into<String>, required: bool) -> Self {
+        self.manifest.uses_feature.push(UsesFeature {
+            name: Some(name.into()),
+            required: Some(required.into()),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Replaces the `<application>`, including the components added so far.
+    pub fn application(mut self, application: Application) -> Self {
+        self.manifest.application = application;
+        self
+    }
+
+    pub fn label(mut self, label: StringResourceOrString) -> Self {
+        self.manifest.application.label = Some(label);
+        self
+    }
+
+    pub fn icon(mut self, icon: MipmapOrDrawableResource) -> Self {
+        self.manifest.application.icon = Some(icon);
+        self
+    }
+
+    pub fn theme(mut self, theme: Resource<StyleResource>) -> Self {
+        self.manifest.application.theme = Some(theme);
+        self
+    }
+
+    pub fn activity(mut self, activity: Activity) -> Self {
+        self.manifest.application.activity.push(activity);
+        self
+    }
+
+    pub fn service(mut self, service: Service) -> Self {
+        self.manifest.application.service.push(service);
+        self
+    }
+
+    pub fn receiver(mut self, receiver: Receiver) -> Self {
+        self.manifest.application.receiver.push(receiver);
+        self
+    }
+
+    pub fn provider(mut self, provider: Provider) -> Self {
+        self.manifest.application.provider.push(provider);
+        self
+    }
+
+    /// Adds a `<meta-data>` with a value to the `<application>`.
+    pub fn meta_data(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.manifest
+            .application
+            .meta_data
+            .push(meta_data(name, value));
+        self
+    }
+
+    /// Returns the manifest, or fails with the diagnostics of [`validate`] that are
+    /// errors.
+    pub fn build(self) -> Result<AndroidManifest> {
+        if self.manifest.package.is_empty() {
+            return Err(Error::FailedToBuild("package is empty".to_string()));
+        }
+        let errors = validate(&self.manifest)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(Error::FailedToBuild(errors.join("; ")));
+        }
+        Ok(self.manifest)
+    }
+}
+
+impl Activity {
+    /// Returns a builder of an `<activity>` named `name`, like `.MainActivity`.
+    pub fn builder(name: impl Into<String>) -> ActivityBuilder {
+        ActivityBuilder {
+            activity: Activity {
+                name: name.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder of an [`Activity`], created with [`Activity::builder`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ActivityBuilder {
+    activity: Activity,
+}
+
+impl ActivityBuilder {
+    pub fn label(mut self, label: StringResourceOrString) -> Self {
+        self.activity.label = Some(label);
+        self
+    }
+
+    pub fn icon(mut self, icon: MipmapOrDrawableResource) -> Self {
+        self.activity.icon = Some(icon);
+        self
+    }
+
+    pub fn theme(mut self, theme: Resource<StyleResource>) -> Self {
+        self.activity.theme = Some(theme);
+        self
+    }
+
+    pub fn exported(mut self, exported: bool) -> Self {
+        self.activity.exported = Some(exported.into());
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.activity.enabled = Some(enabled.into());
+        self
+    }
+
+    pub fn permission(mut self, permission: impl Into<String>) -> Self {
+        self.activity.permission = Some(permission.into());
+        self
+    }
+
+    pub fn process(mut self, process: impl Into<String>) -> Self {
+        self.activity.process = Some(process.into());
+        self
+    }
+
+    pub fn launch_mode(mut self, launch_mode: LaunchMode) -> Self {
+        self.activity.launch_mode = Some(VarOr::Value(launch_mode));
+        self
+    }
+
+    pub fn parent_activity_name(mut self, parent_activity_name: impl Into<String>) -> Self {
+        self.activity.parent_activity_name = Some(parent_activity_name.into());
+        self
+    }
+
+    pub fn intent_filter(mut self, intent_filter: IntentFilter) -> Self {
+        self.activity.intent_filter.push(intent_filter);
+        self
+    }
+
+    pub fn meta_data(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.activity.meta_data.push(meta_data(name, value));
+        self
+    }
+
+    /// Returns the activity, or fails if its name is empty.
+    pub fn build(self) -> Result<Activity> {
+        check_name("activity", &self.activity.name)?;
+        Ok(self.activity)
+    }
+}
+
+impl Service {
+    /// Returns a builder of a `<service>` named `name`, like `.SyncService`.
+    pub fn builder(name: impl Into<String>) -> ServiceBuilder {
+        ServiceBuilder {
+            service: Service {
+                name: name.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder of a [`Service`], created with [`Service::builder`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ServiceBuilder {
+    service: Service,
+}
+
+impl ServiceBuilder {
+    pub fn label(mut self, label: StringResourceOrString) -> Self {
+        self.service.label = Some(label);
+        self
+    }
+
+    pub fn exported(mut self, exported: bool) -> Self {
+        self.service.exported = Some(exported.into());
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.service.enabled = Some(enabled.into());
+        self
+    }
+
+    pub fn permission(mut self, permission: impl Into<String>) -> Self {
+        self.service.permission = Some(permission.into());
+        self
+    }
+
+    pub fn process(mut self, process: impl Into<String>) -> Self {
+        self.service.process = Some(process.into());
+        self
+    }
+
+    /// Adds a foreground service type.
+    pub fn foreground_service_type(
+        mut self,
+        foreground_service_type: ForegroundServiceType,
+    ) -> Self {
+        let mut types = self.service.foreground_service_type.vec().clone();
+        types.push(foreground_service_type);
+        self.service.foreground_service_type = AttributeList::from_vec(types);
+        self
+    }
+
+    pub fn intent_filter(mut self, intent_filter: IntentFilter) -> Self {
+        self.service.intent_filter.push(intent_filter);
+        self
+    }
+
+    pub fn meta_data(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.service.meta_data.push(meta_data(name, value));
+        self
+    }
+
+    /// Returns the service, or fails if its name is empty.
+    pub fn build(self) -> Result<Service> {
+        check_name("service", &self.service.name)?;
+        Ok(self.service)
+    }
+}
+
+impl Receiver {
+    /// Returns a builder of a `<receiver>` named `name`, like `.BootReceiver`.
+    pub fn builder(name: impl Into<String>) -> ReceiverBuilder {
+        ReceiverBuilder {
+            receiver: Receiver {
+                name: name.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder of a [`Receiver`], created with [`Receiver::builder`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReceiverBuilder {
+    receiver: Receiver,
+}
+
+impl ReceiverBuilder {
+    pub fn label(mut self, label: StringResourceOrString) -> Self {
+        self.receiver.label = Some(label);
+        self
+    }
+
+    pub fn exported(mut self, exported: bool) -> Self {
+        self.receiver.exported = Some(exported.into());
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.receiver.enabled = Some(enabled.into());
+        self
+    }
+
+    pub fn permission(mut self, permission: impl Into<String>) -> Self {
+        self.receiver.permission = Some(permission.into());
+        self
+    }
+
+    pub fn process(mut self, process: impl Into<String>) -> Self {
+        self.receiver.process = Some(process.into());
+        self
+    }
+
+    pub fn intent_filter(mut self, intent_filter: IntentFilter) -> Self {
+        self.receiver.intent_filter.push(intent_filter);
+        self
+    }
+
+    pub fn meta_data(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.receiver.meta_data.push(meta_data(name, value));
+        self
+    }
+
+    /// Returns the receiver, or fails if its name is empty.
+    pub fn build(self) -> Result<Receiver> {
+        check_name("receiver", &self.receiver.name)?;
+        Ok(self.receiver)
+    }
+}
+
+impl Provider {
+    /// Returns a builder of a `<provider>` named `name`, like `.FileProvider`, serving
+    /// the URI authority `authority`.
+    pub fn builder(name: impl Into<String>, authority: impl Into<String>) -> ProviderBuilder {
+        ProviderBuilder {
+            provider: Provider {
+                name: name.into(),
+                authorities: AttributeList::from_vec(vec![authority.into()]),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builder of a [`Provider`], created with [`Provider::builder`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProviderBuilder {
+    provider: Provider,
+}
+
+impl ProviderBuilder {
+    /// Adds another URI authority.
+    pub fn authority(mut self, authority: impl Into<String>) -> Self {
+        let mut authorities = self.provider.authorities.vec().clone();
+        authorities.push(authority.into());
+        self.provider.authorities = AttributeList::from_vec(authorities);
+        self
+    }
+
+    pub fn label(mut self, label: StringResourceOrString) -> Self {
+        self.provider.label = Some(label);
+        self
+    }
+
+    pub fn exported(mut self, exported: bool) -> Self {
+        self.provider.exported = Some(exported.into());
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.provider.enabled = Some(enabled.into());
+        self
+    }
+
+    pub fn grant_uri_permissions(mut self, grant_uri_permissions: bool) -> Self {
+        self.provider.grant_uri_permissions = Some(grant_uri_permissions.into());
+        self
+    }
+
+    pub fn permission(mut self, permission: impl Into<String>) -> Self {
+        self.provider.permission = Some(permission.into());
+        self
+    }
+
+    pub fn read_permission(mut self, read_permission: impl Into<String>) -> Self {
+        self.provider.read_permission = Some(read_permission.into());
+        self
+    }
+
+    pub fn write_permission(mut self, write_permission: impl Into<String>) -> Self {
+        self.provider.write_permission = Some(write_permission.into());
+        self
+    }
+
+    pub fn process(mut self, process: impl Into<String>) -> Self {
+        self.provider.process = Some(process.into());
+        self
+    }
+
+    pub fn meta_data(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.provider.meta_data.push(meta_data(name, value));
+        self
+    }
+
+    /// Returns the provider, or fails if its name or one of its authorities is empty.
+    pub fn build(self) -> Result<Provider> {
+        check_name("provider", &self.provider.name)?;
+        if self.provider.authorities.vec().iter().any(String::is_empty) {
+            return Err(Error::FailedToBuild(format!(
+                "provider `{}` has an empty authority",
+                self.provider.name
+            )));
+        }
+        Ok(self.provider)
+    }
+}
+
+impl IntentFilter {
+    /// Returns a builder of an `<intent-filter>`.
+    pub fn builder() -> IntentFilterBuilder {
+        IntentFilterBuilder {
+            intent_filter: IntentFilter::default(),
+        }
+    }
+
+    /// Returns the `<intent-filter>` of the activity started from the launcher, with the
+    /// `android.intent.action.MAIN` action and the `android.intent.category.LAUNCHER`
+    /// category.
+    pub fn launcher() -> IntentFilter {
+        IntentFilter {
+            action: vec![action("android.intent.action.MAIN")],
+            category: vec![category("android.intent.category.LAUNCHER")],
+            ..Default::default()
+        }
+    }
+}
+
+/// Builder of an [`IntentFilter`], created with [`IntentFilter::builder`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct IntentFilterBuilder {
+    intent_filter: IntentFilter,
+}
+
+impl IntentFilterBuilder {
+    /// Adds an action, like `android.intent.action.VIEW`.
+    pub fn action(mut self, name: impl Into<String>) -> Self {
+        self.intent_filter.action.push(action(name));
+        self
+    }
+
+    /// Adds a category, like `android.intent.category.BROWSABLE`.
+    pub fn category(mut self, name: impl Into<String>) -> Self {
+        self.intent_filter.category.push(category(name));
+        self
+    }
+
+    pub fn data(mut self, data: Data) -> Self {
+        self.intent_filter.data.push(data);
+        self
+    }
+
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.intent_filter.priority = Some(VarOr::Value(priority));
+        self
+    }
+
+    pub fn auto_verify(mut self, auto_verify: bool) -> Self {
+        self.intent_filter.auto_verify = Some(auto_verify);
+        self
+    }
+
+    /// Returns the intent filter, or fails if it has no action, as it would match no
+    /// intent.
+    pub fn build(self) -> Result<IntentFilter> {
+        if self.intent_filter.action.is_empty() {
+            return Err(Error::FailedToBuild(
+                "intent filter has no action".to_string(),
+            ));
+        }
+        Ok(self.intent_filter)
+    }
+}
+
+fn check_name(element: &str, name: &str) -> Result<()> {
+    match name.is_empty() {
+        true => Err(Error::FailedToBuild(format!("{} name is empty", element))),
+        false => Ok(()),
+    }
+}
+
+fn action(name: impl Into<String>) -> Action {
+    Action {
+        name: Some(name.into()),
+        ..Default::default()
+    }
+}
+
+fn category(name: impl Into<String>) -> Category {
+    Category {
+        name: Some(name.into()),
+        ..Default::default()
+    }
+}
+
+fn meta_data(name: impl Into<String>, value: impl Into<String>) -> MetaData {
+    MetaData {
+        name: Some(name.into()),
+        value: Some(value.into()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_manifest() {
+        let manifest = AndroidManifest::builder("com.example")
+            .version_code(3)
+            .target_sdk_version(33)
+            .activity(
+                Activity::builder(".MainActivity")
+                    .exported(true)
+                    .launch_mode(LaunchMode::SingleTask)
+                    .intent_filter(IntentFilter::launcher())
+                    .intent_filter(
+                        IntentFilter::builder()
+                            .action("android.intent.action.VIEW")
+                            .category("android.intent.category.DEFAULT")
+                            .category("android.intent.category.BROWSABLE")
+                            .data(Data {
+                                scheme: Some("https".to_string()),
+                                host: Some("example.com".to_string()),
+                                ..Default::default()
+                            })
+                            .auto_verify(true)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .provider(
+                Provider::builder(".FileProvider", "com.example.files")
+                    .exported(false)
+                    .grant_uri_permissions(true)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let expected = crate::from_str(
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example" android:versionCode="3">
+    <application>
+        <activity android:exported="true" android:launchMode="singleTask" android:name=".MainActivity">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+                <category android:name="android.intent.category.LAUNCHER" />
+            </intent-filter>
+            <intent-filter android:autoVerify="true">
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.DEFAULT" />
+                <category android:name="android.intent.category.BROWSABLE" />
+                <data android:scheme="https" android:host="example.com" />
+            </intent-filter>
+        </activity>
+        <provider android:authorities="com.example.files" android:exported="false" android:grantUriPermissions="true" android:name=".FileProvider" />
+    </application>
+    <uses-sdk android:targetSdkVersion="33" />
+</manifest>"#,
+        )
+        .unwrap();
+        assert_eq!(manifest, expected);
+    }
+
+    #[test]
+    fn test_build_invalid_manifest() {
+        assert!(Activity::builder("").build().is_err());
+        assert!(IntentFilter::builder()
+            .category("android.intent.category.DEFAULT")
+            .build()
+            .is_err());
+        let error = AndroidManifest::builder("com.example")
+            .target_sdk_version(31)
+            .activity(
+                Activity::builder(".MainActivity")
+                    .intent_filter(IntentFilter::launcher())
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("[MissingExported]"));
+    }
+}
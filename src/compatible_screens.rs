@@ -1,3 +1,4 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes};
 use serde::{Deserialize, Serialize};
 
 /// Specifies each screen configuration with which the application is
@@ -62,6 +63,18 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone)]
 pub struct CompatibleScreens {
     pub screen: Vec<Screen>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<CompatibleScreens>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 /// Specifies a single screen configuration with which the application is
@@ -138,6 +151,18 @@ pub struct Screen {
     /// [`Supporting Multiple Screens`]: https://developer.android.com/guide/practices/screens_support#range
     #[yaserde(attribute, prefix = "android", rename = "screenDensity")]
     pub screen_density: String,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Screen>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 /// Android runs on a variety of devices that have different screen sizes and pixel
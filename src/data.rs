@@ -1,3 +1,5 @@
+use crate::pattern_matcher::path_matchers;
+use crate::{ExtraAttributes, ExtraElements, PatternMatcher, ToolsAttributes};
 use serde::{Deserialize, Serialize};
 
 /// Adds a data specification to an intent filter.
@@ -54,6 +56,8 @@ use serde::{Deserialize, Serialize};
 ///     android:path="string"
 ///     android:pathPattern="string"
 ///     android:pathPrefix="string"
+///     android:pathAdvancedPattern="string"
+///     android:pathSuffix="string"
 ///     android:mimeType="string" />
 /// ```
 ///
@@ -151,6 +155,21 @@ pub struct Data {
     pub path_pattern: Option<String>,
     #[yaserde(attribute, prefix = "android", rename = "pathPrefix")]
     pub path_prefix: Option<String>,
+    /// A path pattern with character sets and quantifiers, matched as
+    /// [`PATTERN_ADVANCED_GLOB`].
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`PATTERN_ADVANCED_GLOB`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_ADVANCED_GLOB
+    #[yaserde(attribute, prefix = "android", rename = "pathAdvancedPattern")]
+    pub path_advanced_pattern: Option<String>,
+    /// The final part of the path, matched as [`PATTERN_SUFFIX`].
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`PATTERN_SUFFIX`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_SUFFIX
+    #[yaserde(attribute, prefix = "android", rename = "pathSuffix")]
+    pub path_suffix: Option<String>,
     /// A MIME media type, such as `image/jpeg` or `audio/mpeg4-generic`. The
     /// subtype can be the asterisk wildcard (*) to indicate that any subtype
     /// matches.
@@ -164,4 +183,29 @@ pub struct Data {
     /// MIME types using lowercase letters.
     #[yaserde(attribute, prefix = "android", rename = "mimeType")]
     pub mime_type: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Data>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
+
+impl Data {
+    /// Returns the matchers of the path attributes of this `<data>`.
+    pub fn path_matchers(&self) -> Vec<PatternMatcher> {
+        path_matchers(
+            &self.path,
+            &self.path_prefix,
+            &self.path_pattern,
+            &self.path_advanced_pattern,
+            &self.path_suffix,
+        )
+    }
 }
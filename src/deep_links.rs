@@ -0,0 +1,284 @@
+//! Web links handled by a manifest: the `http` and `https` URLs of its browsable intent
+//! filters, and the hosts that must serve a Digital Asset Links file for the filters
+//! with `android:autoVerify="true"` (Android App Links).
+
+use crate::{AndroidManifest, ComponentKind, IntentFilter, PatternType};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const ACTION_VIEW: &str = "android.intent.action.VIEW";
+const CATEGORY_BROWSABLE: &str = "android.intent.category.BROWSABLE";
+
+/// A URL pattern that an activity claims with a `VIEW` and `BROWSABLE` intent filter.
+///
+/// Displays as a URL, with the path pattern as written in the manifest, a `*` after a
+/// prefix and before a suffix, like `https://example.com/products/*`.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub struct DeepLink {
+    pub kind: ComponentKind,
+    /// Name of the activity or activity alias, as written in the manifest.
+    pub component: String,
+    /// `http` or `https`.
+    pub scheme: String,
+    /// Host, which can start with a `*` wildcard, like `*.example.com`.
+    pub host: String,
+    pub port: Option<String>,
+    /// Path pattern, or `None` for all the paths of the host.
+    pub path: Option<String>,
+    pub path_type: Option<PatternType>,
+    /// Whether the intent filter asks Android to verify the host, making the link an
+    /// Android App Link.
+    pub auto_verify: bool,
+}
+
+impl fmt::Display for DeepLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", self.scheme, self.host)?;
+        if let Some(port) = &self.port {
+            write!(f, ":{}", port)?;
+        }
+        match (&self.path, self.path_type) {
+            (Some(path), Some(PatternType::Prefix)) => write!(f, "{}*", path),
+            (Some(path), Some(PatternType::Suffix)) => write!(f, "/*{}", path),
+            (Some(path), _) => f.write_str(path),
+            (None, _) => f.write_str("/*"),
+        }
+    }
+}
+
+/// A statement of a Digital Asset Links file, allowing an app to handle all the URLs of
+/// the host serving it.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub struct AssetStatement {
+    pub relation: Vec<String>,
+    pub target: AssetTarget,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub struct AssetTarget {
+    pub namespace: String,
+    pub package_name: String,
+    /// SHA-256 fingerprints of the signing certificates, like `14:6D:E9:...`.
+    pub sha256_cert_fingerprints: Vec<String>,
+}
+
+impl AndroidManifest {
+    /// Returns the URL patterns of the activities and activity aliases with intent
+    /// filters that have the `android.intent.action.VIEW` action, the
+    /// `android.intent.category.BROWSABLE` category and `http` or `https` schemes.
+    ///
+    /// Like Android, the `<data>` elements of an intent filter are combined, so a filter
+    /// claims every path for every host of every scheme. Filters without host are
+    /// skipped.
+    ///
+    /// ```
+    /// # use android_manifest::*;
+    /// let manifest = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    ///     <application>
+    ///         <activity android:name=".ProductActivity" android:exported="true">
+    ///             <intent-filter android:autoVerify="true">
+    ///                 <action android:name="android.intent.action.VIEW" />
+    ///                 <category android:name="android.intent.category.DEFAULT" />
+    ///                 <category android:name="android.intent.category.BROWSABLE" />
+    ///                 <data android:scheme="https" android:host="example.com" android:pathPrefix="/products/" />
+    ///             </intent-filter>
+    ///         </activity>
+    ///     </application>
+    /// </manifest>"#).unwrap();
+    /// let links = manifest.deep_links();
+    /// assert_eq!(links[0].to_string(), "https://example.com/products/*");
+    /// assert_eq!(manifest.asset_links_hosts(), vec!["example.com"]);
+    /// ```
+    pub fn deep_links(&self) -> Vec<DeepLink> {
+        let application = &self.application;
+        let mut links = Vec::new();
+        for activity in &application.activity {
+            for intent_filter in &activity.intent_filter {
+                push_links(
+                    &mut links,
+                    ComponentKind::Activity,
+                    &activity.name,
+                    intent_filter,
+                );
+            }
+        }
+        for alias in &application.activity_alias {
+            for intent_filter in &alias.intent_filter {
+                push_links(
+                    &mut links,
+                    ComponentKind::ActivityAlias,
+                    alias.name.as_deref().unwrap_or_default(),
+                    intent_filter,
+                );
+            }
+        }
+        links
+    }
+
+    /// Returns the hosts that must serve `/.well-known/assetlinks.json` for the
+    /// [`deep_links`](Self::deep_links) with `auto_verify` to be verified, sorted and
+    /// without duplicates.
+    ///
+    /// A wildcard host like `*.example.com` is verified with its root domain,
+    /// `example.com`.
+    pub fn asset_links_hosts(&self) -> Vec<String> {
+        let mut hosts = self
+            .deep_links()
+            .into_iter()
+            .filter(|link| link.auto_verify)
+            .map(|link| match link.host.strip_prefix("*.") {
+                Some(domain) => domain.to_string(),
+                None => link.host,
+            })
+            .collect::<Vec<_>>();
+        hosts.sort();
+        hosts.dedup();
+        hosts
+    }
+
+    /// Returns the statement that each of the
+    /// [`asset_links_hosts`](Self::asset_links_hosts) must list in its
+    /// `assetlinks.json`, for the app signed with the certificates of
+    /// `sha256_cert_fingerprints`.
+    pub fn asset_statement(&self, sha256_cert_fingerprints: &[&str]) -> AssetStatement {
+        AssetStatement {
+            relation: vec!["delegate_permission/common.handle_all_urls".to_string()],
+            target: AssetTarget {
+                namespace: "android_app".to_string(),
+                package_name: self.package.clone(),
+                sha256_cert_fingerprints: sha256_cert_fingerprints
+                    .iter()
+                    .map(|fingerprint| fingerprint.to_string())
+                    .collect(),
+            },
+        }
+    }
+}
+
+fn push_links(
+    links: &mut Vec<DeepLink>,
+    kind: ComponentKind,
+    component: &str,
+    intent_filter: &IntentFilter,
+) {
+    let has_action = intent_filter
+        .action
+        .iter()
+        .any(|action| action.name.as_deref() == Some(ACTION_VIEW));
+    let has_category = intent_filter
+        .category
+        .iter()
+        .any(|category| category.name.as_deref() == Some(CATEGORY_BROWSABLE));
+    if !has_action || !has_category {
+        return;
+    }
+    let schemes = intent_filter
+        .data
+        .iter()
+        .filter_map(|data| data.scheme.as_deref())
+        .filter(|scheme| *scheme == "http" || *scheme == "https");
+    let authorities = intent_filter
+        .data
+        .iter()
+        .filter_map(|data| {
+            data.host
+                .as_deref()
+                .map(|host| (host, data.port.as_deref()))
+        })
+        .collect::<Vec<_>>();
+    let paths = intent_filter
+        .data
+        .iter()
+        .flat_map(|data| data.path_matchers())
+        .map(|matcher| {
+            (
+                Some(matcher.pattern().to_string()),
+                Some(matcher.pattern_type()),
+            )
+        })
+        .collect::<Vec<_>>();
+    let paths = match paths.is_empty() {
+        true => vec![(None, None)],
+        false => paths,
+    };
+    let auto_verify = intent_filter.auto_verify == Some(true);
+    for scheme in schemes {
+        for (host, port) in &authorities {
+            for (path, path_type) in &paths {
+                links.push(DeepLink {
+                    kind,
+                    component: component.to_string(),
+                    scheme: scheme.to_string(),
+                    host: host.to_string(),
+                    port: port.map(str::to_string),
+                    path: path.clone(),
+                    path_type: *path_type,
+                    auto_verify,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_deep_links() {
+        let manifest = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application>
+        <activity android:name=".MainActivity" android:exported="true">
+            <intent-filter android:autoVerify="true">
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.DEFAULT" />
+                <category android:name="android.intent.category.BROWSABLE" />
+                <data android:scheme="http" />
+                <data android:scheme="https" />
+                <data android:host="*.example.com" />
+                <data android:host="example.org" android:port="8443" />
+                <data android:pathPattern="/item/.*" />
+                <data android:pathSuffix=".pdf" />
+            </intent-filter>
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.DEFAULT" />
+                <category android:name="android.intent.category.BROWSABLE" />
+                <data android:scheme="example" android:host="open" />
+            </intent-filter>
+        </activity>
+        <activity-alias android:name=".Shortcut" android:targetActivity=".MainActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.BROWSABLE" />
+                <data android:scheme="https" android:host="share.example.net" />
+            </intent-filter>
+        </activity-alias>
+    </application>
+</manifest>"#).unwrap();
+        let links = manifest
+            .deep_links()
+            .iter()
+            .map(|link| (link.to_string(), link.auto_verify))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            vec![
+                ("http://*.example.com/item/.*".to_string(), true),
+                ("http://*.example.com/*.pdf".to_string(), true),
+                ("http://example.org:8443/item/.*".to_string(), true),
+                ("http://example.org:8443/*.pdf".to_string(), true),
+                ("https://*.example.com/item/.*".to_string(), true),
+                ("https://*.example.com/*.pdf".to_string(), true),
+                ("https://example.org:8443/item/.*".to_string(), true),
+                ("https://example.org:8443/*.pdf".to_string(), true),
+                ("https://share.example.net/*".to_string(), false),
+            ]
+        );
+        assert_eq!(
+            manifest.asset_links_hosts(),
+            vec!["example.com".to_string(), "example.org".to_string()]
+        );
+        let statement = manifest.asset_statement(&["14:6D:E9"]);
+        assert_eq!(statement.target.package_name, "com.example");
+        assert_eq!(statement.target.sha256_cert_fingerprints, vec!["14:6D:E9"]);
+    }
+}
@@ -0,0 +1,708 @@
+//! Device filtering of Google Play: whether Play would offer the app to a device, given
+//! the requirements declared by its manifest.
+
+use crate::validate::declared_target_sdk_version;
+use crate::{
+    AndroidManifest, ReqKeyboardType, ReqNavigation, ReqTouchScreen, ScreenSize,
+    SupportsGlTextureName, VarOr, VarOrBool,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const CAMERA: &str = "android.hardware.camera";
+const CAMERA_AUTOFOCUS: &str = "android.hardware.camera.autofocus";
+const BLUETOOTH: &str = "android.hardware.bluetooth";
+const LOCATION: &str = "android.hardware.location";
+const LOCATION_GPS: &str = "android.hardware.location.gps";
+const LOCATION_NETWORK: &str = "android.hardware.location.network";
+const MICROPHONE: &str = "android.hardware.microphone";
+const TELEPHONY: &str = "android.hardware.telephony";
+const WIFI: &str = "android.hardware.wifi";
+
+/// Features that Google Play takes as required by apps requesting a permission, from
+/// the [`permissions that imply feature requirements`]. The last field is whether the
+/// feature is only implied for apps targeting API level 20 or lower.
+///
+/// [`permissions that imply feature requirements`]: https://developer.android.com/guide/topics/manifest/uses-feature-element#permissions-features
+const IMPLIED_FEATURES: &[(&str, &str, bool)] = &[
+    ("android.permission.ACCESS_COARSE_LOCATION", LOCATION, false),
+    (
+        "android.permission.ACCESS_COARSE_LOCATION",
+        LOCATION_NETWORK,
+        true,
+    ),
+    ("android.permission.ACCESS_FINE_LOCATION", LOCATION, false),
+    (
+        "android.permission.ACCESS_FINE_LOCATION",
+        LOCATION_GPS,
+        true,
+    ),
+    (
+        "android.permission.ACCESS_LOCATION_EXTRA_COMMANDS",
+        LOCATION,
+        false,
+    ),
+    ("android.permission.ACCESS_MOCK_LOCATION", LOCATION, false),
+    ("android.permission.ACCESS_WIFI_STATE", WIFI, false),
+    ("android.permission.BLUETOOTH", BLUETOOTH, false),
+    ("android.permission.BLUETOOTH_ADMIN", BLUETOOTH, false),
+    ("android.permission.CALL_PHONE", TELEPHONY, false),
+    ("android.permission.CALL_PRIVILEGED", TELEPHONY, false),
+    ("android.permission.CAMERA", CAMERA, false),
+    ("android.permission.CAMERA", CAMERA_AUTOFOCUS, false),
+    (
+        "android.permission.CHANGE_WIFI_MULTICAST_STATE",
+        WIFI,
+        false,
+    ),
+    ("android.permission.CHANGE_WIFI_STATE", WIFI, false),
+    (
+        "android.permission.INSTALL_LOCATION_PROVIDER",
+        LOCATION,
+        false,
+    ),
+    ("android.permission.MODIFY_PHONE_STATE", TELEPHONY, false),
+    (
+        "android.permission.PROCESS_OUTGOING_CALLS",
+        TELEPHONY,
+        false,
+    ),
+    ("android.permission.READ_SMS", TELEPHONY, false),
+    ("android.permission.RECEIVE_MMS", TELEPHONY, false),
+    ("android.permission.RECEIVE_SMS", TELEPHONY, false),
+    ("android.permission.RECEIVE_WAP_PUSH", TELEPHONY, false),
+    ("android.permission.RECORD_AUDIO", MICROPHONE, false),
+    ("android.permission.SEND_SMS", TELEPHONY, false),
+    ("android.permission.WRITE_APN_SETTINGS", TELEPHONY, false),
+    ("android.permission.WRITE_SMS", TELEPHONY, false),
+];
+
+/// The properties of a device that Google Play filters apps on.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Default, Clone)]
+pub struct DeviceProfile {
+    /// API level of the device.
+    pub sdk_version: u32,
+    /// Hardware and software features, like `android.hardware.camera`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+    /// Supported OpenGL ES version, with the major version in the upper 16 bits and the
+    /// minor version in the lower 16 bits, like `0x00030002` for OpenGL ES 3.2.
+    pub gl_es_version: u32,
+    pub screen_size: ScreenSize,
+    /// Screen density in dpi, like `420`.
+    pub screen_density: u32,
+    /// Supported GL texture compression formats.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub texture_formats: Vec<SupportsGlTextureName>,
+    /// Shared libraries installed on the device, like `org.apache.http.legacy`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_libraries: Vec<String>,
+    /// Hardware keyboard, `nokeys` when there's none.
+    pub keyboard: ReqKeyboardType,
+    /// Navigation device, `nonav` when there's none.
+    pub navigation: ReqNavigation,
+    /// Touch screen, `notouch` when there's none.
+    pub touchscreen: ReqTouchScreen,
+}
+
+impl DeviceProfile {
+    /// Returns a profile of a device running API level `sdk_version`, with a normal mdpi
+    /// screen operated with a finger, OpenGL ES 2.0 and no other features.
+    pub fn new(sdk_version: u32) -> Self {
+        Self {
+            sdk_version,
+            gl_es_version: 0x00020000,
+            screen_density: 160,
+            keyboard: ReqKeyboardType::Nokeys,
+            navigation: ReqNavigation::Nonav,
+            touchscreen: ReqTouchScreen::Finger,
+            ..Default::default()
+        }
+    }
+
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    pub fn gl_es_version(mut self, gl_es_version: u32) -> Self {
+        self.gl_es_version = gl_es_version;
+        self
+    }
+
+    pub fn screen(mut self, screen_size: ScreenSize, screen_density: u32) -> Self {
+        self.screen_size = screen_size;
+        self.screen_density = screen_density;
+        self
+    }
+
+    pub fn texture_format(mut self, texture_format: SupportsGlTextureName) -> Self {
+        self.texture_formats.push(texture_format);
+        self
+    }
+
+    pub fn shared_library(mut self, shared_library: impl Into<String>) -> Self {
+        self.shared_libraries.push(shared_library.into());
+        self
+    }
+
+    pub fn keyboard(mut self, keyboard: ReqKeyboardType) -> Self {
+        self.keyboard = keyboard;
+        self
+    }
+
+    pub fn navigation(mut self, navigation: ReqNavigation) -> Self {
+        self.navigation = navigation;
+        self
+    }
+
+    pub fn touchscreen(mut self, touchscreen: ReqTouchScreen) -> Self {
+        self.touchscreen = touchscreen;
+        self
+    }
+}
+
+/// Why Google Play wouldn't offer the app to a device.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub enum IncompatibilityReason {
+    /// The device runs an API level lower than `android:minSdkVersion`.
+    MinSdkVersion(u32),
+    /// The device runs an API level higher than `android:maxSdkVersion`.
+    MaxSdkVersion(u32),
+    /// A required `<uses-feature>` isn't available on the device.
+    MissingFeature(String),
+    /// The device supports an OpenGL ES version lower than the required one.
+    GlEsVersion(u32),
+    /// `<supports-screens>` leaves out the screen size of the device and the smaller
+    /// ones.
+    ScreenSize(ScreenSize),
+    /// No `<screen>` of `<compatible-screens>` matches the screen size and density of
+    /// the device.
+    CompatibleScreens,
+    /// The device supports none of the texture formats of `<supports-gl-texture>`.
+    TextureFormats,
+    /// The device doesn't have a five-way navigation control.
+    FiveWayNavigation,
+    /// The device doesn't have a hardware keyboard.
+    HardKeyboard,
+    /// The device doesn't have the required keyboard type.
+    KeyboardType(ReqKeyboardType),
+    /// The device doesn't have the required navigation device.
+    Navigation(ReqNavigation),
+    /// The device doesn't have the required touch screen.
+    TouchScreen(ReqTouchScreen),
+    /// A required `<uses-library>` isn't installed on the device.
+    MissingLibrary(String),
+}
+
+impl fmt::Display for IncompatibilityReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MinSdkVersion(version) => write!(f, "requires API level {} or higher", version),
+            Self::MaxSdkVersion(version) => write!(f, "requires API level {} or lower", version),
+            Self::MissingFeature(name) => write!(f, "requires feature `{}`", name),
+            Self::GlEsVersion(version) => write!(
+                f,
+                "requires OpenGL ES {}.{}",
+                version >> 16,
+                version & 0xffff
+            ),
+            Self::ScreenSize(size) => write!(
+                f,
+                "doesn't support {} screens",
+                serde_plain::to_string(size).unwrap_or_default()
+            ),
+            Self::CompatibleScreens => f.write_str("screen isn't in <compatible-screens>"),
+            Self::TextureFormats => f.write_str("supports none of the GL texture formats"),
+            Self::FiveWayNavigation => f.write_str("requires a five-way navigation control"),
+            Self::HardKeyboard => f.write_str("requires a hardware keyboard"),
+            Self::KeyboardType(keyboard) => write!(
+                f,
+                "requires a {} keyboard",
+                serde_plain::to_string(keyboard).unwrap_or_default()
+            ),
+            Self::Navigation(navigation) => write!(
+                f,
+                "requires {} navigation",
+                serde_plain::to_string(navigation).unwrap_or_default()
+            ),
+            Self::TouchScreen(touchscreen) => write!(
+                f,
+                "requires a {} touch screen",
+                serde_plain::to_string(touchscreen).unwrap_or_default()
+            ),
+            Self::MissingLibrary(name) => write!(f, "requires library `{}`", name),
+        }
+    }
+}
+
+/// Whether Google Play would offer the app to a device.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub enum Compatibility {
+    Compatible,
+    /// The app is filtered out, for these reasons.
+    Incompatible(Vec<IncompatibilityReason>),
+}
+
+impl Compatibility {
+    pub fn is_compatible(&self) -> bool {
+        matches!(self, Self::Compatible)
+    }
+
+    /// Returns the reasons the app is filtered out, empty when it's compatible.
+    pub fn reasons(&self) -> &[IncompatibilityReason] {
+        match self {
+            Self::Compatible => &[],
+            Self::Incompatible(reasons) => reasons,
+        }
+    }
+}
+
+impl AndroidManifest {
+    /// Returns whether Google Play would offer the app to `device`, following the
+    /// [`filters on the manifest`].
+    ///
+    /// Screen sizes are only filtered out when smaller than all the sizes of
+    /// `<supports-screens>`, as larger screens run the app in compatibility mode, and
+    /// `android:requiresSmallestWidthDp` is ignored like Play does. Features implied by
+    /// the requested permissions, like `android.hardware.camera` by
+    /// `android.permission.CAMERA`, are required unless a `<uses-feature>` declares them,
+    /// with `android:required="false"` to opt out. Attributes set to placeholders are
+    /// ignored.
+    ///
+    /// ```
+    /// # use android_manifest::*;
+    /// let manifest = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    ///     <uses-sdk android:minSdkVersion="24" android:targetSdkVersion="34" />
+    ///     <uses-feature android:name="android.hardware.camera.any" />
+    ///     <uses-feature android:glEsVersion="0x00030000" />
+    ///     <application />
+    /// </manifest>"#).unwrap();
+    /// let phone = DeviceProfile::new(34)
+    ///     .feature("android.hardware.camera.any")
+    ///     .gl_es_version(0x00030002);
+    /// assert_eq!(manifest.device_compatibility(&phone), Compatibility::Compatible);
+    /// let tv = DeviceProfile::new(23).screen(ScreenSize::Xlarge, 320);
+    /// assert_eq!(
+    ///     manifest.device_compatibility(&tv).reasons(),
+    ///     [
+    ///         IncompatibilityReason::MinSdkVersion(24),
+    ///         IncompatibilityReason::MissingFeature("android.hardware.camera.any".to_string()),
+    ///         IncompatibilityReason::GlEsVersion(0x00030000),
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// [`filters on the manifest`]: https://developer.android.com/google/play/filters
+    pub fn device_compatibility(&self, device: &DeviceProfile) -> Compatibility {
+        let mut reasons = Vec::new();
+        self.sdk_compatibility(device, &mut reasons);
+        self.feature_compatibility(device, &mut reasons);
+        self.screen_compatibility(device, &mut reasons);
+        self.texture_compatibility(device, &mut reasons);
+        self.configuration_compatibility(device, &mut reasons);
+        for uses_library in &self.application.uses_library {
+            let name = match &uses_library.name {
+                Some(name) if is_required(&uses_library.required) => name,
+                _ => continue,
+            };
+            if !device.shared_libraries.contains(name) {
+                reasons.push(IncompatibilityReason::MissingLibrary(name.clone()));
+            }
+        }
+        match reasons.is_empty() {
+            true => Compatibility::Compatible,
+            false => Compatibility::Incompatible(reasons),
+        }
+    }
+
+    fn sdk_compatibility(&self, device: &DeviceProfile, reasons: &mut Vec<IncompatibilityReason>) {
+        let Some(uses_sdk) = &self.uses_sdk else {
+            return;
+        };
+        let min_sdk_version = uses_sdk.min_sdk_version.as_ref().and_then(|v| v.value());
+        if let Some(&min_sdk_version) = min_sdk_version {
+            if device.sdk_version < min_sdk_version {
+                reasons.push(IncompatibilityReason::MinSdkVersion(min_sdk_version));
+            }
+        }
+        let max_sdk_version = uses_sdk.max_sdk_version.as_ref().and_then(|v| v.value());
+        if let Some(&max_sdk_version) = max_sdk_version {
+            if device.sdk_version > max_sdk_version {
+                reasons.push(IncompatibilityReason::MaxSdkVersion(max_sdk_version));
+            }
+        }
+    }
+
+    /// Returns the features that Google Play takes as required because of the requested
+    /// permissions, leaving out the ones a `<uses-feature>` declares, required or not.
+    ///
+    /// Location features specific to a provider are only implied when targeting API
+    /// level 20 or lower, and not when the target SDK version isn't set.
+    ///
+    /// ```
+    /// # use android_manifest::*;
+    /// let manifest = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    ///     <uses-permission android:name="android.permission.CAMERA" />
+    ///     <uses-feature android:name="android.hardware.camera" android:required="false" />
+    ///     <application />
+    /// </manifest>"#).unwrap();
+    /// assert_eq!(manifest.implied_features(), ["android.hardware.camera.autofocus"]);
+    /// ```
+    pub fn implied_features(&self) -> Vec<&'static str> {
+        let legacy = declared_target_sdk_version(self).is_some_and(|version| version <= 20);
+        let requested = self
+            .uses_permission
+            .iter()
+            .map(|uses_permission| &uses_permission.name)
+            .chain(
+                self.uses_permission_sdk_23
+                    .iter()
+                    .map(|uses_permission| &uses_permission.name),
+            )
+            .flatten()
+            .collect::<Vec<_>>();
+        let mut features = Vec::new();
+        for &(permission, feature, legacy_only) in IMPLIED_FEATURES {
+            let declared = self
+                .uses_feature
+                .iter()
+                .any(|uses_feature| uses_feature.name.as_deref() == Some(feature));
+            if (legacy || !legacy_only)
+                && !declared
+                && !features.contains(&feature)
+                && requested.iter().any(|name| name.as_str() == permission)
+            {
+                features.push(feature);
+            }
+        }
+        features
+    }
+
+    /// Only the highest required OpenGL ES version counts.
+    fn feature_compatibility(
+        &self,
+        device: &DeviceProfile,
+        reasons: &mut Vec<IncompatibilityReason>,
+    ) {
+        let required = self
+            .uses_feature
+            .iter()
+            .filter(|uses_feature| is_required(&uses_feature.required));
+        let mut gl_es_version = 0;
+        for uses_feature in required {
+            if let Some(name) = &uses_feature.name {
+                if !device.features.contains(name) {
+                    reasons.push(IncompatibilityReason::MissingFeature(name.clone()));
+                }
+            }
+            if let Some(version) = uses_feature.gl_es_version.as_deref().and_then(parse_hex) {
+                gl_es_version = gl_es_version.max(version);
+            }
+        }
+        for feature in self.implied_features() {
+            if !device.features.iter().any(|name| name == feature) {
+                reasons.push(IncompatibilityReason::MissingFeature(feature.to_string()));
+            }
+        }
+        if device.gl_es_version < gl_es_version {
+            reasons.push(IncompatibilityReason::GlEsVersion(gl_es_version));
+        }
+    }
+
+    /// Screen sizes default to supported, and the first `<supports-screens>` setting a
+    /// size wins.
+    fn screen_compatibility(
+        &self,
+        device: &DeviceProfile,
+        reasons: &mut Vec<IncompatibilityReason>,
+    ) {
+        let supports = |size: &ScreenSize| {
+            self.supports_screens
+                .iter()
+                .find_map(|supports_screens| {
+                    let value = match size {
+                        ScreenSize::Small => &supports_screens.small_screens,
+                        ScreenSize::Normal => &supports_screens.normal_screens,
+                        ScreenSize::Large => &supports_screens.large_screens,
+                        ScreenSize::Xlarge => &supports_screens.xlarge_screens,
+                    };
+                    match value {
+                        Some(VarOrBool::Bool(value)) => Some(*value),
+                        _ => None,
+                    }
+                })
+                .unwrap_or(true)
+        };
+        let smallest = [
+            ScreenSize::Small,
+            ScreenSize::Normal,
+            ScreenSize::Large,
+            ScreenSize::Xlarge,
+        ]
+        .into_iter()
+        .position(|size| supports(&size));
+        if smallest.is_none_or(|smallest| screen_size_rank(&device.screen_size) < smallest) {
+            reasons.push(IncompatibilityReason::ScreenSize(
+                device.screen_size.clone(),
+            ));
+        }
+        if let Some(compatible_screens) = &self.compatible_screens {
+            let matches = compatible_screens.screen.iter().any(|screen| {
+                screen.screen_size == device.screen_size
+                    && screen_density_dpi(&screen.screen_density) == Some(device.screen_density)
+            });
+            if !matches {
+                reasons.push(IncompatibilityReason::CompatibleScreens);
+            }
+        }
+    }
+
+    fn texture_compatibility(
+        &self,
+        device: &DeviceProfile,
+        reasons: &mut Vec<IncompatibilityReason>,
+    ) {
+        let texture_formats = self
+            .supports_gl_texture
+            .iter()
+            .filter_map(|supports_gl_texture| supports_gl_texture.name.as_ref()?.value())
+            .collect::<Vec<_>>();
+        if !texture_formats.is_empty()
+            && !texture_formats
+                .iter()
+                .any(|texture_format| device.texture_formats.contains(texture_format))
+        {
+            reasons.push(IncompatibilityReason::TextureFormats);
+        }
+    }
+
+    /// `undefined` and the values meaning no device, like `nokeys`, aren't
+    /// requirements.
+    fn configuration_compatibility(
+        &self,
+        device: &DeviceProfile,
+        reasons: &mut Vec<IncompatibilityReason>,
+    ) {
+        let Some(uses_configuration) = &self.uses_configuration else {
+            return;
+        };
+        let five_way_navigation = matches!(
+            device.navigation,
+            ReqNavigation::Dpad | ReqNavigation::Trackball
+        );
+        if uses_configuration.req_five_way_nav == Some(VarOrBool::Bool(true))
+            && !five_way_navigation
+        {
+            reasons.push(IncompatibilityReason::FiveWayNavigation);
+        }
+        let hard_keyboard = !matches!(
+            device.keyboard,
+            ReqKeyboardType::Undefined | ReqKeyboardType::Nokeys
+        );
+        if uses_configuration.req_hard_keyboard == Some(VarOrBool::Bool(true)) && !hard_keyboard {
+            reasons.push(IncompatibilityReason::HardKeyboard);
+        }
+        match uses_configuration
+            .req_keyboard_type
+            .as_ref()
+            .and_then(VarOr::value)
+        {
+            Some(keyboard @ (ReqKeyboardType::Qwerty | ReqKeyboardType::Twelvekey))
+                if keyboard != &device.keyboard =>
+            {
+                reasons.push(IncompatibilityReason::KeyboardType(keyboard.clone()));
+            }
+            _ => {}
+        }
+        match uses_configuration
+            .req_navigation
+            .as_ref()
+            .and_then(VarOr::value)
+        {
+            Some(
+                navigation
+                @ (ReqNavigation::Dpad | ReqNavigation::Trackball | ReqNavigation::Wheel),
+            ) if navigation != &device.navigation => {
+                reasons.push(IncompatibilityReason::Navigation(navigation.clone()));
+            }
+            _ => {}
+        }
+        match uses_configuration
+            .req_touch_screen
+            .as_ref()
+            .and_then(VarOr::value)
+        {
+            Some(touchscreen @ (ReqTouchScreen::Stylus | ReqTouchScreen::Finger))
+                if touchscreen != &device.touchscreen =>
+            {
+                reasons.push(IncompatibilityReason::TouchScreen(touchscreen.clone()));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Elements are required unless `android:required="false"`.
+fn is_required(required: &Option<VarOrBool>) -> bool {
+    required != &Some(VarOrBool::Bool(false))
+}
+
+fn parse_hex(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))?;
+    u32::from_str_radix(digits, 16).ok()
+}
+
+fn screen_size_rank(screen_size: &ScreenSize) -> usize {
+    match screen_size {
+        ScreenSize::Small => 0,
+        ScreenSize::Normal => 1,
+        ScreenSize::Large => 2,
+        ScreenSize::Xlarge => 3,
+    }
+}
+
+/// Density in dpi of a `<screen>`, given as a bucket name or in dpi.
+fn screen_density_dpi(screen_density: &str) -> Option<u32> {
+    match screen_density {
+        "ldpi" => Some(120),
+        "mdpi" => Some(160),
+        "tvdpi" => Some(213),
+        "hdpi" => Some(240),
+        "xhdpi" => Some(320),
+        "xxhdpi" => Some(480),
+        "xxxhdpi" => Some(640),
+        density => density.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_compatibility() {
+        let manifest = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-sdk android:minSdkVersion="21" android:targetSdkVersion="34" android:maxSdkVersion="33" />
+    <uses-configuration android:reqFiveWayNav="true" android:reqTouchScreen="finger" />
+    <uses-feature android:name="android.hardware.bluetooth" android:required="false" />
+    <supports-screens android:smallScreens="false" />
+    <compatible-screens>
+        <screen android:screenSize="normal" android:screenDensity="xhdpi" />
+        <screen android:screenSize="large" android:screenDensity="420" />
+    </compatible-screens>
+    <supports-gl-texture android:name="GL_OES_compressed_ETC1_RGB8_texture" />
+    <application>
+        <uses-library android:name="com.google.android.maps" />
+        <uses-library android:name="org.apache.http.legacy" android:required="false" />
+    </application>
+</manifest>"#)
+        .unwrap();
+        let device = DeviceProfile::new(33)
+            .screen(ScreenSize::Large, 420)
+            .navigation(ReqNavigation::Dpad)
+            .texture_format(SupportsGlTextureName::GL_OES_compressed_ETC1_RGB8_texture)
+            .shared_library("com.google.android.maps");
+        assert!(manifest.device_compatibility(&device).is_compatible());
+        let device = DeviceProfile::new(34)
+            .screen(ScreenSize::Small, 320)
+            .touchscreen(ReqTouchScreen::Notouch);
+        assert_eq!(
+            manifest.device_compatibility(&device),
+            Compatibility::Incompatible(vec![
+                IncompatibilityReason::MaxSdkVersion(33),
+                IncompatibilityReason::ScreenSize(ScreenSize::Small),
+                IncompatibilityReason::CompatibleScreens,
+                IncompatibilityReason::TextureFormats,
+                IncompatibilityReason::FiveWayNavigation,
+                IncompatibilityReason::TouchScreen(ReqTouchScreen::Finger),
+                IncompatibilityReason::MissingLibrary("com.google.android.maps".to_string()),
+            ])
+        );
+        assert_eq!(
+            IncompatibilityReason::ScreenSize(ScreenSize::Small).to_string(),
+            "doesn't support small screens"
+        );
+    }
+
+    #[test]
+    fn test_device_compatibility_defaults() {
+        let manifest = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-feature android:glEsVersion="0x00020000" />
+    <uses-feature android:glEsVersion="0x00030001" />
+    <uses-feature android:glEsVersion="0x00030000" android:required="false" />
+    <supports-screens android:xlargeScreens="false" />
+    <application />
+</manifest>"#)
+        .unwrap();
+        // Without <uses-sdk>, any API level is allowed, and screen sizes that
+        // <supports-screens> leaves out default to supported.
+        let device = DeviceProfile::new(1)
+            .screen(ScreenSize::Small, 120)
+            .gl_es_version(0x00030001);
+        assert!(manifest.device_compatibility(&device).is_compatible());
+        let device = DeviceProfile::new(36)
+            .screen(ScreenSize::Xlarge, 320)
+            .gl_es_version(0x00030000);
+        assert_eq!(
+            manifest.device_compatibility(&device).reasons(),
+            [IncompatibilityReason::GlEsVersion(0x00030001)]
+        );
+    }
+
+    #[test]
+    fn test_implied_features() {
+        let manifest = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-permission android:name="android.permission.CAMERA" />
+    <uses-permission android:name="android.permission.ACCESS_FINE_LOCATION" />
+    <uses-permission android:name="android.permission.BLUETOOTH" />
+    <uses-permission android:name="android.permission.BLUETOOTH_ADMIN" />
+    <uses-permission-sdk-23 android:name="android.permission.RECORD_AUDIO" />
+    <uses-feature android:name="android.hardware.location" android:required="false" />
+    <application />
+</manifest>"#)
+        .unwrap();
+        assert_eq!(
+            manifest.implied_features(),
+            [
+                "android.hardware.bluetooth",
+                "android.hardware.camera",
+                "android.hardware.camera.autofocus",
+                "android.hardware.microphone",
+            ]
+        );
+        let missing = |feature: &str| IncompatibilityReason::MissingFeature(feature.to_string());
+        let device = DeviceProfile::new(34).feature("android.hardware.bluetooth");
+        assert_eq!(
+            manifest.device_compatibility(&device).reasons(),
+            [
+                missing("android.hardware.camera"),
+                missing("android.hardware.camera.autofocus"),
+                missing("android.hardware.microphone"),
+            ]
+        );
+
+        let mut legacy = manifest.clone();
+        legacy.uses_sdk = Some(crate::UsesSdk {
+            target_sdk_version: Some(VarOr::Value(20)),
+            ..Default::default()
+        });
+        legacy.uses_feature.clear();
+        assert_eq!(
+            legacy.implied_features(),
+            [
+                "android.hardware.location",
+                "android.hardware.location.gps",
+                "android.hardware.bluetooth",
+                "android.hardware.camera",
+                "android.hardware.camera.autofocus",
+                "android.hardware.microphone",
+            ]
+        );
+    }
+}
@@ -0,0 +1,466 @@
+//! Comparison of two versions of a manifest, classifying each change by the risk it
+//! brings to a release.
+
+use crate::error::Result;
+use crate::merge::{describe, is_same_element, key, tree};
+use crate::xml_tree::{XmlAttribute, XmlElement};
+use crate::AndroidManifest;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How much attention a [`Change`] deserves when reviewing a release.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum Risk {
+    Low,
+    Medium,
+    High,
+}
+
+impl fmt::Display for Risk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Low => f.write_str("low"),
+            Self::Medium => f.write_str("medium"),
+            Self::High => f.write_str("high"),
+        }
+    }
+}
+
+/// What a [`Change`] means for the app.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeCategory {
+    /// A `<uses-permission>` was added.
+    PermissionAdded,
+    /// A `<uses-permission>` was removed.
+    PermissionRemoved,
+    /// An attribute of a `<uses-permission>` changed, like `android:maxSdkVersion`.
+    PermissionChanged,
+    /// A permission, permission group or permission tree declared by the app changed.
+    PermissionDefinitionChanged,
+    /// Other apps can reach different components: an exported component was added, or
+    /// the intent filters, `android:exported`, `android:enabled` or permissions of a
+    /// component changed.
+    ExportSurfaceChanged,
+    /// An attribute weakening or hardening the app changed, like `android:debuggable`,
+    /// `android:allowBackup` or `android:usesCleartextTraffic`.
+    SecurityFlagChanged,
+    /// A component that isn't exported was added.
+    ComponentAdded,
+    /// A component was removed.
+    ComponentRemoved,
+    /// The minimum, target or maximum SDK version changed, which changes the platform
+    /// behaviors the app opts in to.
+    SdkVersionChanged,
+    /// The hardware or software features used changed, which changes the devices the app
+    /// is available on.
+    FeatureChanged,
+    /// `android:versionCode` or `android:versionName` changed.
+    VersionChanged,
+    Other,
+}
+
+impl ChangeCategory {
+    pub fn risk(&self) -> Risk {
+        match self {
+            Self::PermissionAdded | Self::ExportSurfaceChanged | Self::SecurityFlagChanged => {
+                Risk::High
+            }
+            Self::PermissionChanged
+            | Self::PermissionDefinitionChanged
+            | Self::ComponentAdded
+            | Self::ComponentRemoved
+            | Self::SdkVersionChanged
+            | Self::FeatureChanged => Risk::Medium,
+            Self::PermissionRemoved | Self::VersionChanged | Self::Other => Risk::Low,
+        }
+    }
+}
+
+impl fmt::Display for ChangeCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_plain::to_string(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// What changed on an element.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    ElementAdded,
+    ElementRemoved,
+    /// An attribute, named with its prefix like `android:exported`, was added.
+    AttributeAdded {
+        name: String,
+        value: String,
+    },
+    AttributeRemoved {
+        name: String,
+        value: String,
+    },
+    AttributeModified {
+        name: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ElementAdded => f.write_str("added"),
+            Self::ElementRemoved => f.write_str("removed"),
+            Self::AttributeAdded { name, value } => write!(f, "{} added as `{}`", name, value),
+            Self::AttributeRemoved { name, value } => {
+                write!(f, "{} removed, was `{}`", name, value)
+            }
+            Self::AttributeModified { name, old, new } => {
+                write!(f, "{} changed from `{}` to `{}`", name, old, new)
+            }
+        }
+    }
+}
+
+/// A difference between two manifests.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub struct Change {
+    /// Path of the element, like
+    /// `manifest/application/activity#com.example.MainActivity`. Elements with a name
+    /// are identified by it, class names being fully qualified.
+    pub path: String,
+    pub kind: ChangeKind,
+    pub category: ChangeCategory,
+}
+
+impl Change {
+    pub fn risk(&self) -> Risk {
+        self.category.risk()
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {} [{}]",
+            self.risk(),
+            self.path,
+            self.kind,
+            self.category
+        )
+    }
+}
+
+/// Result of [`diff`], with the changes in manifest order.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Default, Clone)]
+pub struct ManifestDiff {
+    pub changes: Vec<Change>,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Highest risk among the changes, `None` if there is no change.
+    pub fn max_risk(&self) -> Option<Risk> {
+        self.changes.iter().map(Change::risk).max()
+    }
+
+    /// Changes of the given category.
+    pub fn changes_in(&self, category: ChangeCategory) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(move |change| change.category == category)
+    }
+}
+
+impl fmt::Display for ManifestDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two versions of a manifest, matching elements by name rather than position.
+///
+/// Elements without a name, like `<intent-filter>`, are matched by their whole content,
+/// so a modified intent filter shows as removed and added. `tools:` attributes are
+/// ignored.
+///
+/// ```
+/// # use android_manifest::*;
+/// let old = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+///     <application />
+/// </manifest>"#).unwrap();
+/// let new = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+///     <uses-permission android:name="android.permission.CAMERA" />
+///     <application />
+/// </manifest>"#).unwrap();
+/// let diff = diff(&old, &new).unwrap();
+/// assert_eq!(diff.changes[0].category, ChangeCategory::PermissionAdded);
+/// assert_eq!(diff.max_risk(), Some(Risk::High));
+/// ```
+pub fn diff(old: &AndroidManifest, new: &AndroidManifest) -> Result<ManifestDiff> {
+    let old = tree(old, &old.package)?;
+    let new = tree(new, &new.package)?;
+    let mut changes = Vec::new();
+    element(&mut changes, "manifest", &old, &new);
+    Ok(ManifestDiff { changes })
+}
+
+fn element(changes: &mut Vec<Change>, path: &str, old: &XmlElement, new: &XmlElement) {
+    let name = |attribute: &XmlAttribute| match &attribute.prefix {
+        Some(prefix) => format!("{}:{}", prefix, attribute.name),
+        None => attribute.name.clone(),
+    };
+    let is_tools = |attribute: &&XmlAttribute| attribute.prefix.as_deref() == Some("tools");
+    for attribute in old.attributes.iter().filter(|a| !is_tools(a)) {
+        let kind = match new.attribute(attribute.prefix.as_deref(), &attribute.name) {
+            Some(value) if value == attribute.value => continue,
+            Some(value) => ChangeKind::AttributeModified {
+                name: name(attribute),
+                old: attribute.value.clone(),
+                new: value.to_string(),
+            },
+            None => ChangeKind::AttributeRemoved {
+                name: name(attribute),
+                value: attribute.value.clone(),
+            },
+        };
+        push(changes, path, new, kind);
+    }
+    for attribute in new.attributes.iter().filter(|a| !is_tools(a)) {
+        if old
+            .attribute(attribute.prefix.as_deref(), &attribute.name)
+            .is_none()
+        {
+            let kind = ChangeKind::AttributeAdded {
+                name: name(attribute),
+                value: attribute.value.clone(),
+            };
+            push(changes, path, new, kind);
+        }
+    }
+    let new_children = new.elements().collect::<Vec<_>>();
+    let mut matched = vec![false; new_children.len()];
+    for old_child in old.elements() {
+        let old_key = key(old_child);
+        let found = new_children
+            .iter()
+            .enumerate()
+            .position(|(index, new_child)| {
+                !matched[index]
+                    && new_child.name == old_child.name
+                    && match &old_key {
+                        Some(old_key) => key(new_child).as_ref() == Some(old_key),
+                        None => is_same_element(old_child, new_child),
+                    }
+            });
+        let child_path = format!("{}/{}", path, describe(old_child));
+        match found {
+            Some(index) => {
+                matched[index] = true;
+                element(changes, &child_path, old_child, new_children[index]);
+            }
+            None => push(changes, &child_path, old_child, ChangeKind::ElementRemoved),
+        }
+    }
+    for (new_child, _) in new_children
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+    {
+        let child_path = format!("{}/{}", path, describe(new_child));
+        push(changes, &child_path, new_child, ChangeKind::ElementAdded);
+    }
+}
+
+fn push(changes: &mut Vec<Change>, path: &str, element: &XmlElement, kind: ChangeKind) {
+    let category = classify(path, element, &kind);
+    changes.push(Change {
+        path: path.to_string(),
+        kind,
+        category,
+    });
+}
+
+const COMPONENTS: &[&str] = &[
+    "activity",
+    "activity-alias",
+    "service",
+    "receiver",
+    "provider",
+];
+
+/// Categorizes a change of `element`, whose path is `path`.
+fn classify(path: &str, element: &XmlElement, kind: &ChangeKind) -> ChangeCategory {
+    let parent = path
+        .rsplit('/')
+        .nth(1)
+        .map(|parent| parent.split('#').next().unwrap_or_default())
+        .unwrap_or_default();
+    let attribute = match kind {
+        ChangeKind::AttributeAdded { name, .. }
+        | ChangeKind::AttributeRemoved { name, .. }
+        | ChangeKind::AttributeModified { name, .. } => Some(name.as_str()),
+        ChangeKind::ElementAdded | ChangeKind::ElementRemoved => None,
+    };
+    let added = kind == &ChangeKind::ElementAdded;
+    match (element.name.as_str(), attribute) {
+        ("uses-permission" | "uses-permission-sdk-23", None) if added => {
+            ChangeCategory::PermissionAdded
+        }
+        ("uses-permission" | "uses-permission-sdk-23", None) => ChangeCategory::PermissionRemoved,
+        ("uses-permission" | "uses-permission-sdk-23", Some(_)) => {
+            ChangeCategory::PermissionChanged
+        }
+        ("permission" | "permission-group" | "permission-tree", _) => {
+            ChangeCategory::PermissionDefinitionChanged
+        }
+        (name, None) if COMPONENTS.contains(&name) => match added && is_exported(element) {
+            true => ChangeCategory::ExportSurfaceChanged,
+            false if added => ChangeCategory::ComponentAdded,
+            false => ChangeCategory::ComponentRemoved,
+        },
+        (
+            name,
+            Some(
+                "android:exported"
+                | "android:enabled"
+                | "android:permission"
+                | "android:readPermission"
+                | "android:writePermission"
+                | "android:grantUriPermissions",
+            ),
+        ) if COMPONENTS.contains(&name) => ChangeCategory::ExportSurfaceChanged,
+        ("intent-filter" | "grant-uri-permission" | "path-permission", None)
+            if COMPONENTS.contains(&parent) =>
+        {
+            ChangeCategory::ExportSurfaceChanged
+        }
+        (
+            "application",
+            Some(
+                "android:debuggable"
+                | "android:allowBackup"
+                | "android:usesCleartextTraffic"
+                | "android:networkSecurityConfig"
+                | "android:testOnly"
+                | "android:requestLegacyExternalStorage"
+                | "android:fullBackupContent",
+            ),
+        ) => ChangeCategory::SecurityFlagChanged,
+        ("application", Some("android:permission")) => ChangeCategory::ExportSurfaceChanged,
+        ("manifest", Some("android:sharedUserId")) => ChangeCategory::SecurityFlagChanged,
+        ("manifest", Some("android:versionCode" | "android:versionName")) => {
+            ChangeCategory::VersionChanged
+        }
+        ("uses-sdk", _) => ChangeCategory::SdkVersionChanged,
+        ("uses-feature" | "uses-library" | "uses-native-library" | "supports-gl-texture", _) => {
+            ChangeCategory::FeatureChanged
+        }
+        _ => ChangeCategory::Other,
+    }
+}
+
+/// Whether other apps can start a component, `android:exported` defaulting to whether it
+/// has intent filters.
+fn is_exported(element: &XmlElement) -> bool {
+    match element.attribute(Some("android"), "exported") {
+        Some(exported) => exported == "true",
+        None => element
+            .elements()
+            .any(|child| child.name == "intent-filter"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_manifests() {
+        let old = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" xmlns:tools="http://schemas.android.com/tools" package="com.example" android:versionCode="1">
+    <uses-sdk android:minSdkVersion="21" android:targetSdkVersion="33" />
+    <uses-permission android:name="android.permission.INTERNET" />
+    <uses-permission android:name="android.permission.READ_CONTACTS" />
+    <application android:allowBackup="false">
+        <activity android:name=".MainActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+                <category android:name="android.intent.category.LAUNCHER" />
+            </intent-filter>
+        </activity>
+        <activity android:name=".SettingsActivity" android:exported="false" />
+        <service android:name=".LegacyService" />
+    </application>
+</manifest>"#).unwrap();
+        let new = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" xmlns:tools="http://schemas.android.com/tools" package="com.example" android:versionCode="2">
+    <uses-sdk android:minSdkVersion="21" android:targetSdkVersion="34" />
+    <uses-permission android:name="android.permission.CAMERA" />
+    <uses-permission android:name="android.permission.INTERNET" />
+    <application android:allowBackup="true">
+        <activity android:name="com.example.SettingsActivity" android:exported="true" tools:node="merge" />
+        <activity android:name=".MainActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+                <category android:name="android.intent.category.LAUNCHER" />
+            </intent-filter>
+        </activity>
+        <receiver android:name=".BootReceiver" android:exported="false" />
+    </application>
+</manifest>"#).unwrap();
+        let diff = diff(&old, &new).unwrap();
+        let changes = diff
+            .changes
+            .iter()
+            .map(|change| (change.path.as_str(), change.category))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                ("manifest", ChangeCategory::VersionChanged),
+                ("manifest/application", ChangeCategory::SecurityFlagChanged),
+                (
+                    "manifest/application/activity#com.example.SettingsActivity",
+                    ChangeCategory::ExportSurfaceChanged
+                ),
+                (
+                    "manifest/application/service#com.example.LegacyService",
+                    ChangeCategory::ComponentRemoved
+                ),
+                (
+                    "manifest/application/receiver#com.example.BootReceiver",
+                    ChangeCategory::ComponentAdded
+                ),
+                ("manifest/uses-sdk", ChangeCategory::SdkVersionChanged),
+                (
+                    "manifest/uses-permission#android.permission.READ_CONTACTS",
+                    ChangeCategory::PermissionRemoved
+                ),
+                (
+                    "manifest/uses-permission#android.permission.CAMERA",
+                    ChangeCategory::PermissionAdded
+                ),
+            ]
+        );
+        assert_eq!(
+            diff.changes[2].to_string(),
+            "high: manifest/application/activity#com.example.SettingsActivity: \
+             android:exported changed from `false` to `true` [export-surface-changed]"
+        );
+        let json = serde_json::to_value(&diff.changes[2]).unwrap();
+        assert_eq!(json["category"], "export-surface-changed");
+        assert_eq!(
+            json["kind"]["attribute-modified"]["name"],
+            "android:exported"
+        );
+        assert_eq!(serde_json::to_value(Risk::High).unwrap(), "high");
+        assert_eq!(diff.max_risk(), Some(Risk::High));
+        assert!(super::diff(&old, &old).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,635 @@
+use crate::error::{DeserializeError, Error, Result};
+use crate::AndroidManifest;
+use std::fmt;
+use std::ops::Range;
+use xml::common::Position;
+use xml::reader::EventReader;
+
+/// An `AndroidManifest.xml` document that keeps the original text, including comments,
+/// element order, whitespace and quoting, so attributes can be changed without
+/// reformatting the rest of the file.
+///
+/// ```
+/// # use android_manifest::{ManifestDocument, VarOr};
+/// let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+/// <!-- Checked-in manifest -->
+/// <manifest xmlns:android="http://schemas.android.com/apk/res/android"
+///     package="com.example"
+///     android:versionCode="7">
+///     <application android:label="Example" />
+/// </manifest>"#;
+/// let mut document = ManifestDocument::parse(xml).unwrap();
+/// document.set_version_code(8).unwrap();
+/// assert_eq!(document.as_str(), xml.replace(r#"versionCode="7""#, r#"versionCode="8""#));
+/// assert_eq!(document.manifest().unwrap().version_code, Some(VarOr::Value(8)));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ManifestDocument {
+    text: String,
+    elements: Vec<ElementSpan>,
+}
+
+/// Identifies an element of a [`ManifestDocument`]. It stays valid while attributes are
+/// edited.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ElementId(usize);
+
+/// Location of an element's start tag in the document text.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct ElementSpan {
+    name: String,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// End of the element name, where attributes are inserted when it has none.
+    name_end: usize,
+    attributes: Vec<AttributeSpan>,
+}
+
+/// Location of an attribute in the document text.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct AttributeSpan {
+    /// Qualified name, like `android:versionCode`.
+    name: String,
+    /// Unescaped value.
+    value: String,
+    /// From the start of the name to the closing quote, inclusive.
+    span: Range<usize>,
+    /// Between the quotes.
+    value_span: Range<usize>,
+    quote: char,
+}
+
+impl ManifestDocument {
+    /// Parses the text of an `AndroidManifest.xml` file.
+    pub fn parse(text: impl Into<String>) -> Result<Self> {
+        let text = text.into();
+        // Let the XML parser report syntax errors, the scanner relies on well-formed
+        // input.
+        if let Err(message) = crate::xml_tree::XmlElement::parse(&text) {
+            return Err(Error::FailedToDeserialize(locate_error(&text, message)));
+        }
+        let elements =
+            scan(&text).map_err(|e| Error::FailedToDeserialize(DeserializeError::new(e)))?;
+        Ok(Self { text, elements })
+    }
+
+    /// Deserializes the document into an [`AndroidManifest`].
+    pub fn manifest(&self) -> Result<AndroidManifest> {
+        crate::from_str(&self.text)
+    }
+
+    /// The document text, with all edits applied.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// The `<manifest>` element.
+    pub fn root(&self) -> ElementId {
+        ElementId(0)
+    }
+
+    /// Qualified name of the element, like `activity`.
+    pub fn name(&self, element: ElementId) -> &str {
+        &self.elements[element.0].name
+    }
+
+    pub fn parent(&self, element: ElementId) -> Option<ElementId> {
+        self.elements[element.0].parent.map(ElementId)
+    }
+
+    pub fn children(&self, element: ElementId) -> impl Iterator<Item = ElementId> + '_ {
+        self.elements[element.0]
+            .children
+            .iter()
+            .copied()
+            .map(ElementId)
+    }
+
+    /// Finds the elements at a `/` separated path of element names below `<manifest>`,
+    /// like `application/activity`. An empty path returns the root.
+    pub fn find_all(&self, path: &str) -> Vec<ElementId> {
+        let mut elements = vec![self.root()];
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            elements = elements
+                .into_iter()
+                .flat_map(|element| self.children(element))
+                .filter(|child| self.name(*child) == name)
+                .collect();
+        }
+        elements
+    }
+
+    /// Returns the first element at `path`, see [`find_all`](Self::find_all).
+    pub fn find(&self, path: &str) -> Option<ElementId> {
+        self.find_all(path).into_iter().next()
+    }
+
+    /// Finds the element at a path like `manifest/application/activity[2]`, the form
+    /// used by diagnostics and audit findings, where elements are numbered from 1 when
+    /// there are several of the same kind.
+    pub fn find_path(&self, path: &str) -> Option<ElementId> {
+        let mut names = path.split('/');
+        let root = self.root();
+        if names.next() != Some(self.name(root)) {
+            return None;
+        }
+        names.try_fold(root, |element, name| {
+            let (name, position) = match name.strip_suffix(']').and_then(|n| n.split_once('[')) {
+                Some((name, position)) => (name, position.parse::<usize>().ok()?.checked_sub(1)?),
+                None => (name, 0),
+            };
+            self.children(element)
+                .filter(|child| self.name(*child) == name)
+                .nth(position)
+        })
+    }
+
+    /// Line and column of the start tag of the element, both starting at 1. Columns
+    /// count characters.
+    pub fn position(&self, element: ElementId) -> (usize, usize) {
+        let element = &self.elements[element.0];
+        line_column(&self.text, element.name_end - element.name.len() - 1)
+    }
+
+    /// Returns the unescaped value of an attribute by its qualified name, like
+    /// `android:name`.
+    pub fn attribute(&self, element: ElementId, name: &str) -> Option<&str> {
+        self.elements[element.0]
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+            .map(|attribute| attribute.value.as_str())
+    }
+
+    /// Sets an attribute by its qualified name, like `android:versionCode`.
+    ///
+    /// An existing value is replaced in place. A new attribute is appended to the start
+    /// tag, on its own line if the other attributes are, and with the same quotes.
+    pub fn set_attribute(&mut self, element: ElementId, name: &str, value: &str) -> Result<()> {
+        let span = &self.elements[element.0];
+        if let Some(attribute) = span.attributes.iter().find(|a| a.name == name) {
+            let value = escape(value, attribute.quote);
+            let range = attribute.value_span.clone();
+            return self.replace(range, &value);
+        }
+        if let Some((prefix, _)) = name.split_once(':') {
+            if prefix != "xmlns" && !self.is_declared(element, prefix) {
+                return Err(Error::FailedToEditDocument(format!(
+                    "namespace prefix `{}` of attribute `{}` is not declared",
+                    prefix, name
+                )));
+            }
+        }
+        let (position, separator, quote) = match span.attributes.last() {
+            Some(last) => {
+                let separator = &self.text[whitespace_before(&self.text, last.span.start)];
+                let separator = match separator.contains('\n') {
+                    true => separator.to_string(),
+                    false => " ".to_string(),
+                };
+                (last.span.end, separator, last.quote)
+            }
+            None => (span.name_end, " ".to_string(), '"'),
+        };
+        let attribute = format!(
+            "{}{}={}{}{}",
+            separator,
+            name,
+            quote,
+            escape(value, quote),
+            quote
+        );
+        self.replace(position..position, &attribute)
+    }
+
+    /// Removes an attribute by its qualified name, along with the whitespace before it.
+    /// Returns whether the attribute was present.
+    pub fn remove_attribute(&mut self, element: ElementId, name: &str) -> Result<bool> {
+        let span = &self.elements[element.0];
+        match span.attributes.iter().find(|a| a.name == name) {
+            Some(attribute) => {
+                let start = whitespace_before(&self.text, attribute.span.start).start;
+                let range = start..attribute.span.end;
+                self.replace(range, "")?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Sets `android:versionCode` on `<manifest>`.
+    pub fn set_version_code(&mut self, version_code: u32) -> Result<()> {
+        let root = self.root();
+        self.set_attribute(root, "android:versionCode", &version_code.to_string())
+    }
+
+    /// Sets `android:versionName` on `<manifest>`.
+    pub fn set_version_name(&mut self, version_name: &str) -> Result<()> {
+        let root = self.root();
+        self.set_attribute(root, "android:versionName", version_name)
+    }
+
+    fn is_declared(&self, element: ElementId, prefix: &str) -> bool {
+        let declaration = format!("xmlns:{}", prefix);
+        let mut current = Some(element);
+        while let Some(element) = current {
+            if self.attribute(element, &declaration).is_some() {
+                return true;
+            }
+            current = self.parent(element);
+        }
+        prefix == "xml"
+    }
+
+    fn replace(&mut self, range: Range<usize>, replacement: &str) -> Result<()> {
+        let mut text = self.text.clone();
+        text.replace_range(range, replacement);
+        // Edits never add or remove elements, so element IDs stay the same.
+        self.elements = scan(&text).map_err(Error::FailedToEditDocument)?;
+        self.text = text;
+        Ok(())
+    }
+}
+
+impl fmt::Display for ManifestDocument {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl std::str::FromStr for ManifestDocument {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// Traces a failure to deserialize `text` back to an XML syntax error or to the attribute
+/// that caused it.
+pub(crate) fn locate_error(text: &str, message: String) -> DeserializeError {
+    let mut error = DeserializeError::new(message);
+    for event in EventReader::from_str(text) {
+        if let Err(e) = event {
+            error.line = Some(e.position().row as usize + 1);
+            error.column = Some(e.position().column as usize + 1);
+            return error;
+        }
+    }
+    let elements = match scan(text) {
+        Ok(elements) => elements,
+        Err(_) => return error,
+    };
+    let attributes: Vec<(usize, &AttributeSpan)> = elements
+        .iter()
+        .enumerate()
+        .flat_map(|(index, element)| element.attributes.iter().map(move |a| (index, a)))
+        .filter(|(_, a)| a.name != "xmlns" && !a.name.starts_with("xmlns:"))
+        .collect();
+    // Only keeping the first `count` attributes, find the smallest count that fails.
+    // Invalid values are the only way attributes make deserializing fail, so the last
+    // attribute kept is the culprit.
+    let fails = |count: usize| {
+        let mut text = text.to_string();
+        for (_, attribute) in attributes[count..].iter().rev() {
+            let start = whitespace_before(&text, attribute.span.start).start;
+            text.replace_range(start..attribute.span.end, "");
+        }
+        crate::from_str_unlocated(&text).is_err()
+    };
+    if attributes.is_empty() || fails(0) || !fails(attributes.len()) {
+        return error;
+    }
+    let (mut passing, mut failing) = (0, attributes.len());
+    while failing - passing > 1 {
+        let count = (passing + failing) / 2;
+        match fails(count) {
+            true => failing = count,
+            false => passing = count,
+        }
+    }
+    let (element, attribute) = attributes[failing - 1];
+    let (line, column) = line_column(text, attribute.span.start);
+    error.line = Some(line);
+    error.column = Some(column);
+    error.path = Some(format!("{}/@{}", path(&elements, element), attribute.name));
+    error.value = Some(attribute.value.clone());
+    error
+}
+
+/// Line and column of `offset` in `text`, both starting at 1.
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Path of an element, like `manifest/application/activity[2]`.
+fn path(elements: &[ElementSpan], index: usize) -> String {
+    let element = &elements[index];
+    let parent = match element.parent {
+        Some(parent) => parent,
+        None => return element.name.clone(),
+    };
+    let siblings: Vec<usize> = elements[parent]
+        .children
+        .iter()
+        .copied()
+        .filter(|&sibling| elements[sibling].name == element.name)
+        .collect();
+    let mut path = format!("{}/{}", self::path(elements, parent), element.name);
+    if siblings.len() > 1 {
+        let position = siblings
+            .iter()
+            .position(|&sibling| sibling == index)
+            .unwrap();
+        path.push_str(&format!("[{}]", position + 1));
+    }
+    path
+}
+
+/// Returns the range of whitespace that ends at `end`.
+fn whitespace_before(text: &str, end: usize) -> Range<usize> {
+    let start = text[..end].trim_end().len();
+    start..end
+}
+
+/// Finds the start tags of all elements and the spans of their attributes.
+fn scan(text: &str) -> std::result::Result<Vec<ElementSpan>, String> {
+    let mut elements: Vec<ElementSpan> = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find('<').map(|i| offset + i) {
+        let rest = &text[start..];
+        offset = if rest.starts_with("<!--") {
+            skip_past(text, start, "-->")?
+        } else if rest.starts_with("<![CDATA[") {
+            skip_past(text, start, "]]>")?
+        } else if rest.starts_with("<?") {
+            skip_past(text, start, "?>")?
+        } else if rest.starts_with("<!") {
+            skip_declaration(text, start)?
+        } else if rest.starts_with("</") {
+            stack.pop();
+            skip_past(text, start, ">")?
+        } else {
+            let (element, end, self_closing) = start_tag(text, start)?;
+            let index = elements.len();
+            let parent = stack.last().copied();
+            if let Some(parent) = parent {
+                elements[parent].children.push(index);
+            }
+            elements.push(ElementSpan { parent, ..element });
+            if !self_closing {
+                stack.push(index);
+            }
+            end
+        };
+    }
+    if elements.is_empty() {
+        return Err("XML document has no root element".to_string());
+    }
+    Ok(elements)
+}
+
+fn skip_past(text: &str, start: usize, delimiter: &str) -> std::result::Result<usize, String> {
+    text[start..]
+        .find(delimiter)
+        .map(|i| start + i + delimiter.len())
+        .ok_or_else(|| format!("unterminated markup at offset {}", start))
+}
+
+/// Skips a `<!DOCTYPE ...>` declaration, which may hold an internal subset in brackets.
+fn skip_declaration(text: &str, start: usize) -> std::result::Result<usize, String> {
+    let mut depth = 0;
+    for (i, c) in text[start..].char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            '>' if depth == 0 => return Ok(start + i + 1),
+            _ => {}
+        }
+    }
+    Err(format!("unterminated declaration at offset {}", start))
+}
+
+/// Scans a start tag. Returns the element, the offset after the tag and whether the tag
+/// is self-closing.
+fn start_tag(text: &str, start: usize) -> std::result::Result<(ElementSpan, usize, bool), String> {
+    let bytes = text.as_bytes();
+    let is_name_end = |b: u8| b.is_ascii_whitespace() || matches!(b, b'/' | b'>' | b'=');
+    let mut i = start + 1;
+    while i < bytes.len() && !is_name_end(bytes[i]) {
+        i += 1;
+    }
+    let mut element = ElementSpan {
+        name: text[start + 1..i].to_string(),
+        parent: None,
+        children: Vec::new(),
+        name_end: i,
+        attributes: Vec::new(),
+    };
+    let unterminated = || format!("unterminated start tag at offset {}", start);
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        match bytes.get(i) {
+            Some(b'>') => return Ok((element, i + 1, false)),
+            Some(b'/') if bytes.get(i + 1) == Some(&b'>') => return Ok((element, i + 2, true)),
+            Some(_) => {}
+            None => return Err(unterminated()),
+        }
+        let name_start = i;
+        while i < bytes.len() && !is_name_end(bytes[i]) {
+            i += 1;
+        }
+        let name = text[name_start..i].to_string();
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if bytes.get(i) != Some(&b'=') {
+            return Err(format!("attribute `{}` has no value at offset {}", name, i));
+        }
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let quote = match bytes.get(i) {
+            Some(&quote @ (b'"' | b'\'')) => quote,
+            _ => return Err(format!("attribute `{}` isn't quoted at offset {}", name, i)),
+        };
+        let value_start = i + 1;
+        let value_end = text[value_start..]
+            .find(quote as char)
+            .map(|j| value_start + j)
+            .ok_or_else(unterminated)?;
+        i = value_end + 1;
+        element.attributes.push(AttributeSpan {
+            name,
+            value: unescape(&text[value_start..value_end]),
+            span: name_start..i,
+            value_span: value_start..value_end,
+            quote: quote as char,
+        });
+    }
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let decoded = entity.and_then(|(entity, end)| {
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => match entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                    None => entity
+                        .strip_prefix('#')
+                        .and_then(|decimal| decimal.parse().ok())
+                        .and_then(char::from_u32),
+                },
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn escape(value: &str, quote: char) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '"' if quote == '"' => result.push_str("&quot;"),
+            '\'' if quote == '\'' => result.push_str("&apos;"),
+            '\n' => result.push_str("&#10;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_error_location() {
+        let given_xml = r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application android:label="Example">
+        <activity android:name=".MainActivity" android:exported="true" />
+        <activity android:name=".SettingsActivity"
+            android:exported="maybe" />
+    </application>
+</manifest>"#;
+        let error = match crate::from_str(given_xml) {
+            Err(Error::FailedToDeserialize(error)) => error,
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert_eq!(
+            error.path.as_deref(),
+            Some("manifest/application/activity[2]/@android:exported")
+        );
+        assert_eq!((error.line, error.column), (Some(5), Some(13)));
+        assert_eq!(error.value.as_deref(), Some("maybe"));
+
+        let error = match ManifestDocument::parse("<manifest>\n  <application>\n</manifest>") {
+            Err(Error::FailedToDeserialize(error)) => error,
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.path, None);
+    }
+
+    #[test]
+    fn test_edit_preserves_formatting() {
+        let given_xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<!-- Release manifest, keep the order. -->
+<manifest xmlns:android="http://schemas.android.com/apk/res/android"
+    package="com.example"
+    android:versionCode='7'>
+
+    <uses-permission android:name="android.permission.INTERNET"/>
+    <application android:label="Example" android:debuggable="true">
+        <!-- Entry point -->
+        <activity android:name=".MainActivity"></activity>
+    </application>
+</manifest>
+"#;
+        let mut document = ManifestDocument::parse(given_xml).unwrap();
+        let application = document.find("application").unwrap();
+        assert_eq!(
+            document.attribute(application, "android:label"),
+            Some("Example")
+        );
+        let activity = document.find("application/activity").unwrap();
+        assert_eq!(document.parent(activity), Some(application));
+        assert_eq!(
+            document.find_path("manifest/application/activity"),
+            Some(activity)
+        );
+        assert_eq!(document.find_path("manifest/application/activity[2]"), None);
+        assert_eq!(document.position(activity), (10, 9));
+
+        document.set_version_code(8).unwrap();
+        document.set_version_name("1.1 \"beta\"").unwrap();
+        assert!(document
+            .remove_attribute(application, "android:debuggable")
+            .unwrap());
+        document
+            .set_attribute(activity, "android:exported", "true")
+            .unwrap();
+        assert!(document
+            .set_attribute(activity, "dist:instant", "true")
+            .is_err());
+
+        let expected_xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<!-- Release manifest, keep the order. -->
+<manifest xmlns:android="http://schemas.android.com/apk/res/android"
+    package="com.example"
+    android:versionCode='8'
+    android:versionName='1.1 "beta"'>
+
+    <uses-permission android:name="android.permission.INTERNET"/>
+    <application android:label="Example">
+        <!-- Entry point -->
+        <activity android:name=".MainActivity" android:exported="true"></activity>
+    </application>
+</manifest>
+"#;
+        assert_eq!(document.as_str(), expected_xml);
+        let manifest = document.manifest().unwrap();
+        assert_eq!(manifest.version_code, Some(crate::VarOr::Value(8)));
+        assert_eq!(manifest.version_name, Some("1.1 \"beta\"".to_string()));
+    }
+}
@@ -1,4 +1,5 @@
 use displaydoc::Display;
+use std::fmt;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -8,5 +9,69 @@ pub enum Error {
     /// Failed to serialize AndroidManifest.xml. Error: {0}
     FailedToSerialize(String),
     /// Failed to deserialize AndroidManifest.xml. Error: {0}
-    FailedToDeserialize(String),
+    FailedToDeserialize(DeserializeError),
+    /// Failed to decode binary AndroidManifest.xml. Error: {0}
+    FailedToDecodeBinaryXml(String),
+    /// Failed to encode AndroidManifest.xml as binary XML. Error: {0}
+    FailedToEncodeBinaryXml(String),
+    /// Failed to decode protobuf AndroidManifest.xml. Error: {0}
+    FailedToDecodeProtoXml(String),
+    /// Failed to encode AndroidManifest.xml as protobuf XML. Error: {0}
+    FailedToEncodeProtoXml(String),
+    /// Failed to read AndroidManifest.xml from archive. Error: {0}
+    FailedToReadArchive(String),
+    /// Failed to merge AndroidManifest.xml. Error: {0}
+    FailedToMerge(String),
+    /// Failed to edit AndroidManifest.xml document. Error: {0}
+    FailedToEditDocument(String),
+    /// Failed to build AndroidManifest.xml. Error: {0}
+    FailedToBuild(String),
+    /// Failed to parse path pattern. Error: {0}
+    InvalidPattern(String),
 }
+
+/// Where and why deserializing an `AndroidManifest.xml` failed.
+///
+/// The location is known when the failure can be traced back to an XML syntax error or
+/// to the value of a single attribute.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct DeserializeError {
+    pub message: String,
+    /// Path of the element or attribute, like
+    /// `manifest/application/activity[2]/@android:exported`. Elements are numbered from 1
+    /// when they have siblings of the same name.
+    pub path: Option<String>,
+    /// Line number, starting at 1.
+    pub line: Option<usize>,
+    /// Column number in characters, starting at 1.
+    pub column: Option<usize>,
+    /// The offending attribute value.
+    pub value: Option<String>,
+}
+
+impl DeserializeError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, "{}:{}: ", line, column)?;
+        }
+        f.write_str(&self.message)?;
+        if let Some(path) = &self.path {
+            write!(f, " at `{}`", path)?;
+        }
+        if let Some(value) = &self.value {
+            write!(f, " (value `{}`)", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DeserializeError {}
@@ -0,0 +1,47 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
+use serde::{Deserialize, Serialize};
+
+/// Declares the minimum version of an [`SDK extension`] the application requires.
+///
+/// SDK extensions add APIs to existing Android versions through system updates. The
+/// application can't be installed on a device whose extension `sdkVersion` is older than
+/// `minExtensionVersion`.
+///
+/// ## XML Syntax
+/// ```xml
+/// <extension-sdk android:sdkVersion="integer"
+///                android:minExtensionVersion="integer" />
+/// ```
+///
+/// ## Contained in
+/// * [`<uses-sdk>`]
+///
+/// ## Introduced in
+/// API Level 30
+///
+/// [`SDK extension`]: https://developer.android.com/guide/sdk-extensions
+/// [`<uses-sdk>`]: crate::UsesSdk
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct ExtensionSdk {
+    /// The API level of the extended Android version, like `30` for the extensions of
+    /// Android 11.
+    #[yaserde(attribute, prefix = "android", rename = "sdkVersion")]
+    pub sdk_version: Option<VarOr<u32>>,
+    /// The minimum version of the extension.
+    #[yaserde(attribute, prefix = "android", rename = "minExtensionVersion")]
+    pub min_extension_version: Option<VarOr<u32>>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<ExtensionSdk>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
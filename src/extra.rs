@@ -0,0 +1,518 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+use xml::attribute::OwnedAttribute;
+use xml::name::OwnedName;
+use xml::namespace::Namespace;
+use xml::reader::{EventReader, XmlEvent};
+use xml::writer::EmitterConfig;
+use yaserde::{YaDeserialize, YaSerialize};
+
+thread_local! {
+    /// Set while an element struct is deserialized to find out whether it models an
+    /// attribute, so that [`ExtraAttributes`] of the probed struct don't probe again.
+    static PROBING: Cell<bool> = const { Cell::new(false) };
+
+    /// Whether an element struct models an attribute, by struct and by the prefix and
+    /// local name of the attribute, so each attribute name is only probed once.
+    static MODELED: RefCell<HashMap<ModeledKey, bool>> = RefCell::new(HashMap::new());
+}
+
+/// Element struct, attribute prefix and attribute local name.
+type ModeledKey = (TypeId, Option<String>, String);
+
+/// Sets [`PROBING`] while it's alive, and restores it when dropped, even when
+/// deserializing panics.
+struct ProbingGuard(bool);
+
+impl ProbingGuard {
+    fn new() -> Self {
+        Self(PROBING.with(|probing| probing.replace(true)))
+    }
+}
+
+impl Drop for ProbingGuard {
+    fn drop(&mut self) {
+        PROBING.with(|probing| probing.set(self.0));
+    }
+}
+
+/// An attribute that isn't modeled by the struct of its element.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Default, Clone)]
+pub struct ExtraAttribute {
+    /// Namespace prefix, like `android`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Namespace URI the prefix is bound to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Local name of the attribute.
+    pub name: String,
+    pub value: String,
+}
+
+impl ExtraAttribute {
+    /// Creates an `android:` attribute.
+    pub fn android(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            prefix: Some("android".to_string()),
+            namespace: Some(crate::xml_tree::ANDROID_NAMESPACE.to_string()),
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Attributes of an element that this crate doesn't know about yet, like attributes
+/// added in a newer Android release.
+///
+/// They are kept when the manifest is deserialized and written back as they were when
+/// it's serialized again, so a round trip through [`from_str`](crate::from_str) and
+/// [`to_string`](crate::to_string) doesn't strip them.
+///
+/// The type parameter is the struct of the element, it's used to tell which attributes
+/// the struct already models.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExtraAttributes<T> {
+    vec: Vec<ExtraAttribute>,
+    element: PhantomData<T>,
+}
+
+impl<T> ExtraAttributes<T> {
+    pub fn new() -> Self {
+        Self::from_vec(Vec::new())
+    }
+
+    pub fn from_vec(vec: Vec<ExtraAttribute>) -> Self {
+        ExtraAttributes {
+            vec,
+            element: PhantomData,
+        }
+    }
+
+    pub fn vec(&self) -> &Vec<ExtraAttribute> {
+        &self.vec
+    }
+
+    pub fn vec_mut(&mut self) -> &mut Vec<ExtraAttribute> {
+        &mut self.vec
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns the value of the attribute with the given local name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vec
+            .iter()
+            .find(|attribute| attribute.name == name)
+            .map(|attribute| attribute.value.as_str())
+    }
+}
+
+impl<T> Default for ExtraAttributes<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<ExtraAttribute>> for ExtraAttributes<T> {
+    fn from(vec: Vec<ExtraAttribute>) -> Self {
+        Self::from_vec(vec)
+    }
+}
+
+impl<T> Serialize for ExtraAttributes<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.vec.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ExtraAttributes<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<ExtraAttribute>::deserialize(deserializer).map(Self::from_vec)
+    }
+}
+
+impl<T> YaSerialize for ExtraAttributes<T> {
+    fn serialize<W: Write>(&self, _writer: &mut yaserde::ser::Serializer<W>) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        mut attributes: Vec<OwnedAttribute>,
+        mut namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        for attribute in &self.vec {
+            if let (Some(prefix), Some(uri)) = (&attribute.prefix, &attribute.namespace) {
+                namespace.put(prefix.as_str(), uri.as_str());
+            }
+            let name = OwnedName {
+                local_name: attribute.name.clone(),
+                namespace: attribute.namespace.clone(),
+                prefix: attribute.prefix.clone(),
+            };
+            attributes.push(OwnedAttribute::new(name, attribute.value.as_str()));
+        }
+        Ok((attributes, namespace))
+    }
+}
+
+impl<T: YaDeserialize + PartialEq + 'static> YaDeserialize for ExtraAttributes<T> {
+    fn deserialize<R: Read>(reader: &mut yaserde::de::Deserializer<R>) -> Result<Self, String> {
+        // Flattened fields are given the start element of their struct with all of its
+        // attributes.
+        let (name, attributes, namespace) = loop {
+            match reader.next_event()? {
+                XmlEvent::StartElement {
+                    name,
+                    attributes,
+                    namespace,
+                } => break (name, attributes, namespace),
+                XmlEvent::EndDocument => return Ok(Self::new()),
+                _ => {}
+            }
+        };
+        if attributes.is_empty() || PROBING.with(Cell::get) {
+            return Ok(Self::new());
+        }
+        let mut empty = None;
+        let mut extra = Vec::new();
+        for attribute in attributes {
+            let key = (
+                TypeId::of::<T>(),
+                attribute.name.prefix.clone(),
+                attribute.name.local_name.clone(),
+            );
+            let cached = MODELED.with(|modeled| modeled.borrow().get(&key).copied());
+            let modeled = match cached {
+                Some(modeled) => modeled,
+                None => {
+                    if empty.is_none() {
+                        empty = Some(probe::<T>(&name, None, &namespace)?);
+                    }
+                    let empty = empty.as_ref().expect("empty element is probed");
+                    let modeled = is_modeled(empty, &name, &attribute, &namespace)?;
+                    MODELED.with(|cache| cache.borrow_mut().insert(key, modeled));
+                    modeled
+                }
+            };
+            if !modeled {
+                extra.push(ExtraAttribute {
+                    prefix: attribute.name.prefix,
+                    namespace: attribute.name.namespace,
+                    name: attribute.name.local_name,
+                    value: attribute.value,
+                });
+            }
+        }
+        Ok(Self::from_vec(extra))
+    }
+}
+
+/// Checks whether `T` has a field for the attribute by deserializing an element holding
+/// only that attribute. yaserde silently ignores the attributes a struct doesn't model,
+/// so these deserialize to the same value as an element without attributes. The answer
+/// only depends on the attribute name, so it's cached.
+fn is_modeled<T: YaDeserialize + PartialEq>(
+    empty: &Result<T, String>,
+    name: &OwnedName,
+    attribute: &OwnedAttribute,
+    namespace: &Namespace,
+) -> Result<bool, String> {
+    // An attribute set to the default value of its field can't be told apart from an
+    // ignored one, so values that no field defaults to are tried as well.
+    for value in [attribute.value.as_str(), "x", "true"] {
+        let attribute = OwnedAttribute::new(attribute.name.clone(), value);
+        match (probe::<T>(name, Some(&attribute), namespace)?, empty) {
+            (Ok(element), Ok(empty)) if &element == empty => {}
+            _ => return Ok(true),
+        }
+    }
+    Ok(false)
+}
+
+/// Deserializes `T` from an element with at most one attribute. The outer result holds
+/// errors writing the element.
+fn probe<T: YaDeserialize>(
+    name: &OwnedName,
+    attribute: Option<&OwnedAttribute>,
+    namespace: &Namespace,
+) -> Result<Result<T, String>, String> {
+    let mut buf = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(&mut buf);
+    writer
+        .write(xml::writer::XmlEvent::StartElement {
+            name: name.borrow(),
+            attributes: attribute.iter().map(|a| a.borrow()).collect(),
+            namespace: Cow::Borrowed(namespace),
+        })
+        .and_then(|_| writer.write(xml::writer::XmlEvent::end_element()))
+        .map_err(|e| e.to_string())?;
+    let xml = String::from_utf8(buf).map_err(|e| e.to_string())?;
+    let _probing = ProbingGuard::new();
+    Ok(yaserde::de::from_str::<T>(&xml))
+}
+
+/// A child element that this crate doesn't know about yet, kept as an XML fragment.
+///
+/// Namespaces used by the fragment are declared on its root.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+#[serde(transparent)]
+pub struct ExtraElement {
+    xml: String,
+}
+
+impl ExtraElement {
+    /// Creates an element from an XML fragment holding a single root element.
+    pub fn new(xml: impl Into<String>) -> Self {
+        Self { xml: xml.into() }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.xml
+    }
+}
+
+/// Child elements of an element that this crate doesn't know about yet, like elements
+/// added in a newer Android release.
+///
+/// Like [`ExtraAttributes`], they are written back as they were when the manifest is
+/// serialized again.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Default, Clone)]
+#[serde(transparent)]
+pub struct ExtraElements {
+    vec: Vec<ExtraElement>,
+}
+
+impl ExtraElements {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_vec(vec: Vec<ExtraElement>) -> Self {
+        ExtraElements { vec }
+    }
+
+    pub fn vec(&self) -> &Vec<ExtraElement> {
+        &self.vec
+    }
+
+    pub fn vec_mut(&mut self) -> &mut Vec<ExtraElement> {
+        &mut self.vec
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+}
+
+impl From<Vec<ExtraElement>> for ExtraElements {
+    fn from(vec: Vec<ExtraElement>) -> Self {
+        Self::from_vec(vec)
+    }
+}
+
+impl YaSerialize for ExtraElements {
+    fn serialize<W: Write>(&self, writer: &mut yaserde::ser::Serializer<W>) -> Result<(), String> {
+        for element in &self.vec {
+            for event in EventReader::from_str(&element.xml) {
+                let event = event.map_err(|e| e.to_string())?;
+                if matches!(
+                    event,
+                    XmlEvent::StartDocument { .. }
+                        | XmlEvent::EndDocument
+                        | XmlEvent::Whitespace(_)
+                ) {
+                    continue;
+                }
+                if let Some(event) = event.as_writer_event() {
+                    writer.write(event).map_err(|e| e.to_string())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+impl YaDeserialize for ExtraElements {
+    fn deserialize<R: Read>(reader: &mut yaserde::de::Deserializer<R>) -> Result<Self, String> {
+        // Flattened fields are given the start element of their struct followed by the
+        // child elements none of its fields matched.
+        let mut vec = Vec::new();
+        let mut depth = 0;
+        let mut fragment = Vec::new();
+        loop {
+            let event = reader.next_event()?;
+            match event {
+                XmlEvent::StartElement { .. } => depth += 1,
+                XmlEvent::EndElement { .. } => depth -= 1,
+                XmlEvent::EndDocument => break,
+                _ => {}
+            }
+            if depth > 1 || (depth == 1 && !fragment.is_empty()) {
+                fragment.push(event);
+                if depth == 1 {
+                    vec.push(ExtraElement::new(write_fragment(&fragment)?));
+                    fragment.clear();
+                }
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+        Ok(Self::from_vec(vec))
+    }
+}
+
+/// Writes the events of an element, declaring only the namespaces it uses instead of all
+/// the namespaces in scope.
+fn write_fragment(events: &[XmlEvent]) -> Result<String, String> {
+    let mut prefixes = HashSet::new();
+    for event in events {
+        if let XmlEvent::StartElement {
+            name, attributes, ..
+        } = event
+        {
+            prefixes.insert(name.prefix.as_deref());
+            prefixes.extend(attributes.iter().map(|a| a.name.prefix.as_deref()));
+        }
+    }
+    let mut buf = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(&mut buf);
+    for event in events {
+        let event = match event {
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                let mut used = Namespace::empty();
+                for (prefix, uri) in namespace {
+                    let prefix = Some(prefix).filter(|p| !p.is_empty());
+                    let reserved = matches!(prefix, Some("xml" | "xmlns")) || uri.is_empty();
+                    if !reserved && prefixes.contains(&prefix) {
+                        used.put(prefix.unwrap_or_default(), uri);
+                    }
+                }
+                xml::writer::XmlEvent::StartElement {
+                    name: name.borrow(),
+                    attributes: attributes.iter().map(|a| a.borrow()).collect(),
+                    namespace: Cow::Owned(used),
+                }
+            }
+            event => match event.as_writer_event() {
+                Some(event) => event,
+                None => continue,
+            },
+        };
+        writer.write(event).map_err(|e| e.to_string())?;
+    }
+    String::from_utf8(buf).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_xml_round_trip() {
+        let given_xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" xmlns:dist="http://schemas.android.com/apk/distribution" package="com.example" android:versionCode="1" android:compileSdkVersion="34">
+  <dist:module dist:instant="true" />
+  <application android:label="Example" android:pageSizeCompat="enabled" android:isSplitRequired="true">
+    <activity android:name=".MainActivity" android:exported="true" android:intentMatchingFlags="enforceIntentFilter">
+      <property android:name="android.window.PROPERTY_COMPAT_ALLOW_RESIZEABLE" android:value="true" />
+      <intent-filter android:autoVerify="true">
+        <action android:name="android.intent.action.MAIN" />
+        <uri-relative-filter-group android:allow="false">
+          <data android:query="a=b" />
+        </uri-relative-filter-group>
+      </intent-filter>
+    </activity>
+  </application>
+</manifest>"#;
+        let manifest = crate::from_str(given_xml).unwrap();
+        assert_eq!(
+            manifest.extra_attributes.get("compileSdkVersion"),
+            Some("34")
+        );
+        assert_eq!(manifest.extra_elements.vec().len(), 1);
+        let application = &manifest.application;
+        assert_eq!(
+            application.extra_attributes.vec(),
+            &vec![
+                ExtraAttribute::android("pageSizeCompat", "enabled"),
+                ExtraAttribute::android("isSplitRequired", "true"),
+            ]
+        );
+        let activity = &application.activity[0];
+        assert_eq!(activity.extra_attributes.vec().len(), 1);
+        assert!(activity.extra_elements.is_empty());
+        assert_eq!(activity.property.len(), 1);
+        let intent_filter = &activity.intent_filter[0];
+        assert!(intent_filter.extra_attributes.is_empty());
+        assert_eq!(
+            intent_filter.extra_elements.vec(),
+            &vec![ExtraElement::new(
+                r#"<uri-relative-filter-group xmlns:android="http://schemas.android.com/apk/res/android" android:allow="false"><data android:query="a=b" /></uri-relative-filter-group>"#
+            )]
+        );
+        let xml = crate::to_string_pretty(&manifest).unwrap();
+        assert!(xml.contains(r#"<dist:module xmlns:dist="http://schemas.android.com/apk/distribution" dist:instant="true" />"#));
+        assert_eq!(crate::from_str(&xml).unwrap(), manifest);
+        let key = (
+            TypeId::of::<crate::Application>(),
+            Some("android".to_string()),
+            "pageSizeCompat".to_string(),
+        );
+        assert_eq!(
+            MODELED.with(|modeled| modeled.borrow().get(&key).copied()),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_probing_reset_after_panic() {
+        let result = std::panic::catch_unwind(|| {
+            let _probing = ProbingGuard::new();
+            panic!("deserializing failed");
+        });
+        assert!(result.is_err());
+        assert!(!PROBING.with(Cell::get));
+        let manifest = crate::from_str(
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example" android:compileSdkVersion="34">
+  <application />
+</manifest>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            manifest.extra_attributes.get("compileSdkVersion"),
+            Some("34")
+        );
+    }
+}
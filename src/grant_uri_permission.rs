@@ -1,3 +1,5 @@
+use crate::pattern_matcher::path_matchers;
+use crate::{ExtraAttributes, ExtraElements, PatternMatcher, ToolsAttributes};
 use serde::{Deserialize, Serialize};
 
 /// Specifies the subsets of app data that parent content provider can access.
@@ -21,7 +23,9 @@ use serde::{Deserialize, Serialize};
 /// ```xml
 /// <grant-uri-permission android:path="string"
 ///                       android:pathPattern="string"
-///                       android:pathPrefix="string" />
+///                       android:pathPrefix="string"
+///                       android:pathAdvancedPattern="string"
+///                       android:pathSuffix="string" />
 /// ```
 ///
 /// ## Contained in
@@ -69,4 +73,51 @@ pub struct GrantUriPermission {
     pub path_pattern: Option<String>,
     #[yaserde(attribute, prefix = "android", rename = "pathPrefix")]
     pub path_prefix: Option<String>,
+    /// A path pattern with character sets and quantifiers, matched as
+    /// [`PATTERN_ADVANCED_GLOB`].
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`PATTERN_ADVANCED_GLOB`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_ADVANCED_GLOB
+    #[yaserde(attribute, prefix = "android", rename = "pathAdvancedPattern")]
+    pub path_advanced_pattern: Option<String>,
+    /// The final part of a path, matched as [`PATTERN_SUFFIX`].
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`PATTERN_SUFFIX`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_SUFFIX
+    #[yaserde(attribute, prefix = "android", rename = "pathSuffix")]
+    pub path_suffix: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<GrantUriPermission>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
+
+impl GrantUriPermission {
+    /// Returns the matchers of the path attributes of this element.
+    pub fn path_matchers(&self) -> Vec<PatternMatcher> {
+        path_matchers(
+            &self.path,
+            &self.path_prefix,
+            &self.path_pattern,
+            &self.path_advanced_pattern,
+            &self.path_suffix,
+        )
+    }
+
+    /// Returns whether the URI path `path` matches one of the path attributes.
+    pub fn matches_path(&self, path: &str) -> bool {
+        self.path_matchers()
+            .iter()
+            .any(|matcher| matcher.matches(path))
+    }
 }
@@ -1,6 +1,8 @@
 use crate::VarOrBool;
 
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::resources::{MipmapOrDrawableResource, StringResourceOrString};
+use super::tools::ToolsAttributes;
 use serde::{Deserialize, Serialize};
 
 /// Declares an [`Instrumentation`] class that enables you to monitor an application's
@@ -80,4 +82,16 @@ pub struct Instrumentation {
     /// [`android:targetPackage`]: crate::Instrumentation#structfield.target_package
     #[yaserde(attribute, prefix = "android", rename = "targetProcesses")]
     pub target_processes: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Instrumentation>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
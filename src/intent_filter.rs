@@ -1,7 +1,10 @@
 use super::action::Action;
 use super::category::Category;
 use super::data::Data;
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::resources::{MipmapOrDrawableResource, StringResourceOrString};
+use super::tools::ToolsAttributes;
+use super::var_or::VarOr;
 use serde::{Deserialize, Serialize};
 
 /// Specifies the types of intents that an activity, service, or broadcast receiver can
@@ -125,7 +128,7 @@ pub struct IntentFilter {
     /// [`ACTION_SEND_MULTIPLE`]: https://developer.android.com/reference/android/content/Intent#ACTION_SEND_MULTIPLE
     /// [`setPriority()`]: https://developer.android.com/reference/android/content/IntentFilter#setPriority(int)
     #[yaserde(attribute, prefix = "android")]
-    pub priority: Option<u32>,
+    pub priority: Option<VarOr<i32>>,
     /// The order in which the filter should be processed when multiple filters match.
     /// order differs from priority in that priority applies across apps, while order
     /// disambiguates multiple matching filters in a single app.
@@ -137,7 +140,7 @@ pub struct IntentFilter {
     ///
     /// This attribute was introduced in API Level 28.
     #[yaserde(attribute, prefix = "android")]
-    pub order: Option<u32>,
+    pub order: Option<VarOr<u32>>,
     /// List of `<action>` tags.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub action: Vec<Action>,
@@ -155,6 +158,18 @@ pub struct IntentFilter {
     /// [`verify-android-applinks`]: https://developer.android.com/training/app-links/verify-android-applinks
     #[yaserde(attribute, prefix = "android", rename = "autoVerify")]
     pub auto_verify: Option<bool>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<IntentFilter>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 #[cfg(test)]
@@ -164,12 +179,14 @@ mod tests {
     #[test]
     fn test_intent_filter_toml_serialize_deserialize() {
         let value = IntentFilter {
-            order: Some(100),
+            order: Some(VarOr::Value(100)),
             action: vec![Action {
                 name: Some("android.intent.action.MAIN".to_string()),
+                ..Default::default()
             }],
             category: vec![Category {
                 name: Some("android.intent.category.LAUNCHER".to_string()),
+                ..Default::default()
             }],
             ..Default::default()
         };
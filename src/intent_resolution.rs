@@ -0,0 +1,512 @@
+//! Offline resolution of intents against the intent filters of a manifest, following the
+//! matching rules of Android's `IntentFilter.match()`.
+
+use crate::{AndroidManifest, IntentFilter, VarOr, VarOrBool};
+use serde::{Deserialize, Serialize};
+
+/// An intent to resolve, as sent by `startActivity()`, `startService()` or
+/// `sendBroadcast()`.
+///
+/// Categories are matched as given. `startActivity()` adds
+/// `android.intent.category.DEFAULT` to implicit intents, so add that category to only
+/// match the activities that can be started this way.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Default, Clone)]
+pub struct IntentRequest {
+    /// Action, like `android.intent.action.VIEW`.
+    pub action: Option<String>,
+    /// Categories, like `android.intent.category.BROWSABLE`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    /// Data URI, like `https://example.com/products/42`.
+    pub data: Option<String>,
+    /// MIME type, like `image/png`.
+    pub mime_type: Option<String>,
+}
+
+impl IntentRequest {
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: Some(action.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.categories.push(category.into());
+        self
+    }
+
+    pub fn data(mut self, data: impl Into<String>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    pub fn mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+}
+
+/// Kind of the component an intent resolved to.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum ComponentKind {
+    Activity,
+    ActivityAlias,
+    Service,
+    Receiver,
+}
+
+/// A component with an intent filter matching an [`IntentRequest`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IntentMatch<'a> {
+    pub kind: ComponentKind,
+    /// Name of the component, as written in the manifest.
+    pub name: &'a str,
+    /// The first of the component's intent filters with the highest priority among
+    /// those that match.
+    pub intent_filter: &'a IntentFilter,
+    /// Priority of `intent_filter`, 0 by default.
+    pub priority: i32,
+}
+
+impl AndroidManifest {
+    /// Returns the enabled activities, activity aliases, services and receivers that
+    /// have an intent filter matching `intent`, from the highest priority to the lowest,
+    /// in manifest order for equal priorities.
+    ///
+    /// ```
+    /// # use android_manifest::*;
+    /// let manifest = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    ///     <application>
+    ///         <activity android:name=".ProductActivity" android:exported="true">
+    ///             <intent-filter>
+    ///                 <action android:name="android.intent.action.VIEW" />
+    ///                 <category android:name="android.intent.category.DEFAULT" />
+    ///                 <category android:name="android.intent.category.BROWSABLE" />
+    ///                 <data android:scheme="https" android:host="*.example.com" android:pathPrefix="/products/" />
+    ///             </intent-filter>
+    ///         </activity>
+    ///     </application>
+    /// </manifest>"#).unwrap();
+    /// let intent = IntentRequest::new("android.intent.action.VIEW")
+    ///     .category("android.intent.category.BROWSABLE")
+    ///     .data("https://www.example.com/products/42");
+    /// let matches = manifest.resolve_intent(&intent);
+    /// assert_eq!(matches[0].name, ".ProductActivity");
+    /// ```
+    pub fn resolve_intent(&self, intent: &IntentRequest) -> Vec<IntentMatch<'_>> {
+        let application = &self.application;
+        if is_disabled(&application.enabled) {
+            return Vec::new();
+        }
+        let mut matches = Vec::new();
+        for activity in &application.activity {
+            if !is_disabled(&activity.enabled) {
+                push_match(
+                    &mut matches,
+                    ComponentKind::Activity,
+                    &activity.name,
+                    &activity.intent_filter,
+                    intent,
+                );
+            }
+        }
+        for alias in &application.activity_alias {
+            if !is_disabled(&alias.enabled) {
+                push_match(
+                    &mut matches,
+                    ComponentKind::ActivityAlias,
+                    alias.name.as_deref().unwrap_or_default(),
+                    &alias.intent_filter,
+                    intent,
+                );
+            }
+        }
+        for service in &application.service {
+            if !is_disabled(&service.enabled) {
+                push_match(
+                    &mut matches,
+                    ComponentKind::Service,
+                    &service.name,
+                    &service.intent_filter,
+                    intent,
+                );
+            }
+        }
+        for receiver in &application.receiver {
+            if !is_disabled(&receiver.enabled) {
+                push_match(
+                    &mut matches,
+                    ComponentKind::Receiver,
+                    &receiver.name,
+                    &receiver.intent_filter,
+                    intent,
+                );
+            }
+        }
+        matches.sort_by_key(|intent_match| std::cmp::Reverse(intent_match.priority));
+        matches
+    }
+}
+
+impl IntentFilter {
+    /// Returns whether the intent filter matches `intent`, by its action, categories,
+    /// data URI and MIME type.
+    pub fn matches(&self, intent: &IntentRequest) -> bool {
+        self.matches_with(intent, false)
+    }
+
+    /// Like [`matches()`](Self::matches), with a `*` host and a `/*` path in the data
+    /// URI of `intent` matching any host and path, as done for `<intent>` queries.
+    pub(crate) fn matches_wildcards(&self, intent: &IntentRequest) -> bool {
+        self.matches_with(intent, true)
+    }
+
+    fn matches_with(&self, intent: &IntentRequest, wildcards: bool) -> bool {
+        self.matches_action(intent.action.as_deref())
+            && self.matches_categories(&intent.categories)
+            && self.matches_data(
+                intent.mime_type.as_deref(),
+                intent.data.as_deref(),
+                wildcards,
+            )
+    }
+
+    /// Priority of the filter, 0 when unset or a placeholder.
+    pub(crate) fn priority_value(&self) -> i32 {
+        self.priority
+            .as_ref()
+            .and_then(VarOr::value)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// An intent without action matches any filter with at least one action.
+    fn matches_action(&self, action: Option<&str>) -> bool {
+        match action {
+            Some(action) => self
+                .action
+                .iter()
+                .any(|filter_action| filter_action.name.as_deref() == Some(action)),
+            None => !self.action.is_empty(),
+        }
+    }
+
+    fn matches_categories(&self, categories: &[String]) -> bool {
+        categories.iter().all(|category| {
+            self.category
+                .iter()
+                .any(|filter_category| filter_category.name.as_deref() == Some(category))
+        })
+    }
+
+    fn matches_data(&self, mime_type: Option<&str>, data: Option<&str>, wildcards: bool) -> bool {
+        let schemes = self
+            .data
+            .iter()
+            .filter_map(|data| data.scheme.as_deref())
+            .collect::<Vec<_>>();
+        let types = self
+            .data
+            .iter()
+            .filter_map(|data| data.mime_type.as_deref())
+            .collect::<Vec<_>>();
+        let uri = data.map(Uri::parse);
+        if schemes.is_empty() && types.is_empty() {
+            return mime_type.is_none() && data.is_none();
+        }
+        if !schemes.is_empty() {
+            let scheme = uri.as_ref().map(|uri| uri.scheme).unwrap_or_default();
+            if !schemes.contains(&scheme) {
+                return false;
+            }
+            // Ports and paths are only taken into account along with hosts.
+            let authorities = self
+                .data
+                .iter()
+                .filter_map(|data| {
+                    data.host
+                        .as_deref()
+                        .map(|host| (host, data.port.as_deref()))
+                })
+                .collect::<Vec<_>>();
+            if !authorities.is_empty() {
+                let uri = match &uri {
+                    Some(uri) => uri,
+                    None => return false,
+                };
+                let any_host = wildcards && uri.host == "*";
+                if !(any_host
+                    || authorities
+                        .iter()
+                        .any(|(host, port)| matches_authority(host, *port, uri)))
+                {
+                    return false;
+                }
+                if !(wildcards && uri.path == "/*")
+                    && self.has_paths()
+                    && !self.matches_path(uri.path)
+                {
+                    return false;
+                }
+            }
+        } else if let Some(uri) = &uri {
+            if !["", "content", "file"].contains(&uri.scheme) {
+                return false;
+            }
+        }
+        match types.is_empty() {
+            true => mime_type.is_none(),
+            false => mime_type.is_some_and(|mime_type| matches_mime_type(&types, mime_type)),
+        }
+    }
+
+    fn has_paths(&self) -> bool {
+        self.data
+            .iter()
+            .any(|data| !data.path_matchers().is_empty())
+    }
+
+    fn matches_path(&self, path: &str) -> bool {
+        self.data.iter().any(|data| {
+            data.path_matchers()
+                .iter()
+                .any(|matcher| matcher.matches(path))
+        })
+    }
+}
+
+fn push_match<'a>(
+    matches: &mut Vec<IntentMatch<'a>>,
+    kind: ComponentKind,
+    name: &'a str,
+    intent_filters: &'a [IntentFilter],
+    intent: &IntentRequest,
+) {
+    let best = intent_filters
+        .iter()
+        .filter(|intent_filter| intent_filter.matches(intent))
+        .fold(
+            None,
+            |best: Option<&IntentFilter>, intent_filter| match best {
+                Some(best) if best.priority_value() >= intent_filter.priority_value() => Some(best),
+                _ => Some(intent_filter),
+            },
+        );
+    if let Some(intent_filter) = best {
+        matches.push(IntentMatch {
+            kind,
+            name,
+            intent_filter,
+            priority: intent_filter.priority_value(),
+        });
+    }
+}
+
+fn is_disabled(enabled: &Option<VarOrBool>) -> bool {
+    enabled == &Some(VarOrBool::Bool(false))
+}
+
+/// A host starting with `*` matches any host ending with the rest of it, ignoring case.
+/// A port only matches the same explicit port of the URI.
+fn matches_authority(host: &str, port: Option<&str>, uri: &Uri) -> bool {
+    let matches_host = match host.strip_prefix('*') {
+        Some(suffix) => uri
+            .host
+            .len()
+            .checked_sub(suffix.len())
+            .and_then(|start| uri.host.get(start..))
+            .is_some_and(|end| end.eq_ignore_ascii_case(suffix)),
+        None => uri.host.eq_ignore_ascii_case(host),
+    };
+    matches_host
+        && match port.and_then(|port| port.parse::<u16>().ok()) {
+            Some(port) => uri.port == Some(port),
+            None => true,
+        }
+}
+
+/// Matches MIME types with wildcards on either side, like `image/*` or `*/*`.
+pub(crate) fn matches_mime_type(types: &[&str], mime_type: &str) -> bool {
+    if types.contains(&mime_type) {
+        return true;
+    }
+    if mime_type == "*/*" {
+        return true;
+    }
+    let (base, sub) = mime_type.split_once('/').unwrap_or((mime_type, ""));
+    types.iter().any(|filter_type| {
+        let (filter_base, filter_sub) = filter_type.split_once('/').unwrap_or((filter_type, ""));
+        filter_type == &"*"
+            || filter_type == &"*/*"
+            || (filter_base == base && filter_sub == "*")
+            || (sub == "*" && filter_base == base)
+    })
+}
+
+/// The parts of a URI that intent filters match.
+pub(crate) struct Uri<'a> {
+    pub(crate) scheme: &'a str,
+    pub(crate) host: &'a str,
+    pub(crate) port: Option<u16>,
+    pub(crate) path: &'a str,
+}
+
+impl<'a> Uri<'a> {
+    pub(crate) fn parse(uri: &'a str) -> Self {
+        let (scheme, rest) = match uri.split_once(':') {
+            Some((scheme, rest))
+                if !scheme.is_empty()
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)) =>
+            {
+                (scheme, rest)
+            }
+            _ => ("", uri),
+        };
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => match rest.find('/') {
+                Some(index) => (&rest[..index], &rest[index..]),
+                None => (rest, ""),
+            },
+            None => ("", rest),
+        };
+        let authority = authority
+            .rsplit_once('@')
+            .map_or(authority, |(_, authority)| authority);
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => (host, port.parse().ok()),
+            _ => (authority, None),
+        };
+        Self {
+            scheme,
+            host,
+            port,
+            path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_intent() {
+        let manifest = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application>
+        <activity android:name=".MainActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+                <category android:name="android.intent.category.LAUNCHER" />
+            </intent-filter>
+        </activity>
+        <activity android:name=".ViewerActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.DEFAULT" />
+                <data android:mimeType="image/*" />
+            </intent-filter>
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.DEFAULT" />
+                <category android:name="android.intent.category.BROWSABLE" />
+                <data android:scheme="https" android:host="example.com" android:port="8443" />
+                <data android:pathPattern="/items/.*/edit" />
+            </intent-filter>
+        </activity>
+        <activity android:name=".DisabledActivity" android:enabled="false">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.DEFAULT" />
+                <data android:mimeType="image/png" />
+            </intent-filter>
+        </activity>
+        <receiver android:name=".LowReceiver" android:exported="true">
+            <intent-filter android:priority="-10">
+                <action android:name="com.example.SYNC" />
+            </intent-filter>
+        </receiver>
+        <receiver android:name=".HighReceiver" android:exported="true">
+            <intent-filter android:priority="100">
+                <action android:name="com.example.SYNC" />
+            </intent-filter>
+        </receiver>
+    </application>
+</manifest>"#).unwrap();
+        let names = |intent: &IntentRequest| {
+            manifest
+                .resolve_intent(intent)
+                .iter()
+                .map(|intent_match| intent_match.name)
+                .collect::<Vec<_>>()
+        };
+        let view = || {
+            IntentRequest::new("android.intent.action.VIEW")
+                .category("android.intent.category.DEFAULT")
+        };
+        assert_eq!(
+            names(&IntentRequest::new("android.intent.action.MAIN")),
+            vec![".MainActivity"]
+        );
+        assert_eq!(
+            names(&view().mime_type("image/png")),
+            vec![".ViewerActivity"]
+        );
+        assert_eq!(names(&view().mime_type("*/*")), vec![".ViewerActivity"]);
+        assert!(names(&view().mime_type("text/plain")).is_empty());
+        assert!(names(&view()).is_empty());
+        assert_eq!(
+            names(&view().data("https://EXAMPLE.com:8443/items/42/edit?ref=mail")),
+            vec![".ViewerActivity"]
+        );
+        assert!(names(&view().data("https://example.com/items/42/edit")).is_empty());
+        assert!(names(&view().data("https://example.com:8443/items/42")).is_empty());
+        assert!(names(&view().data("http://example.com:8443/items/42/edit")).is_empty());
+        let matches = manifest.resolve_intent(&IntentRequest::new("com.example.SYNC"));
+        assert_eq!(matches[0].name, ".HighReceiver");
+        assert_eq!(matches[0].kind, ComponentKind::Receiver);
+        assert_eq!(matches[0].priority, 100);
+        assert_eq!(matches[1].name, ".LowReceiver");
+        assert_eq!(matches[1].priority, -10);
+    }
+
+    #[test]
+    fn test_wildcard_hosts() {
+        let manifest = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application>
+        <activity android:name=".SuffixActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <data android:scheme="https" android:host="*m" />
+            </intent-filter>
+        </activity>
+        <activity android:name=".SubdomainActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <data android:scheme="https" android:host="*.example.com" />
+            </intent-filter>
+        </activity>
+    </application>
+</manifest>"#).unwrap();
+        let names = |data: &str| {
+            let intent = IntentRequest::new("android.intent.action.VIEW").data(data);
+            manifest
+                .resolve_intent(&intent)
+                .iter()
+                .map(|intent_match| intent_match.name)
+                .collect::<Vec<_>>()
+        };
+        assert!(names("https://xñ/p").is_empty());
+        assert!(names("https://ñ/p").is_empty());
+        assert_eq!(names("https://bücher.com/p"), vec![".SuffixActivity"]);
+        assert_eq!(
+            names("https://cdn.EXAMPLE.com/p"),
+            vec![".SuffixActivity", ".SubdomainActivity"]
+        );
+        assert!(names("https://example.org/p").is_empty());
+    }
+}
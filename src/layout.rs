@@ -1,3 +1,4 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes};
 use serde::{Deserialize, Serialize};
 
 /// Affect how an activity behaves in multi-window mode.
@@ -65,6 +66,18 @@ pub struct Layout {
     /// ```
     #[yaserde(attribute, prefix = "android", rename = "minWidth")]
     pub min_width: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Layout>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 /// Standard constants and tools for placing an object within a potentially
@@ -4,93 +4,272 @@ extern crate yaserde_derive;
 mod action;
 mod activity;
 mod activity_alias;
+mod apex_system_service;
 mod application;
 mod attribute_list;
+mod attribution;
+mod binary_xml;
+mod builder;
 mod category;
 mod compatible_screens;
 mod data;
+mod deep_links;
+mod device_compatibility;
+mod diff;
+mod document;
 pub mod error;
+mod extension_sdk;
+mod extra;
 mod grant_uri_permission;
 mod instrumentation;
 mod intent_filter;
+mod intent_resolution;
 mod layout;
 mod manifest;
+mod merge;
 mod meta_data;
+mod package_visibility;
 mod path_permission;
+mod pattern_matcher;
 mod permission;
 mod permission_group;
 mod permission_tree;
+mod placeholders;
+mod platform_permissions;
 mod profileable;
+mod property;
+mod proto_xml;
 mod provider;
 mod queries;
 mod receiver;
 mod resources;
+mod sdk_library;
+mod security;
 mod service;
+mod static_library;
 mod supports_gl_texture;
 mod supports_screens;
+mod tools;
 mod ui_options;
 mod uses_configuration;
 mod uses_feature;
 mod uses_library;
 mod uses_native_library;
+mod uses_package;
 mod uses_permission;
 mod uses_permission_sdk_23;
 mod uses_sdk;
+mod uses_sdk_library;
+mod uses_static_library;
+mod validate;
+mod var_or;
 mod var_or_bool;
+mod xml_tree;
+
+use std::io::Read;
+use std::path::Path;
 
 pub use action::*;
 pub use activity::*;
 pub use activity_alias::*;
+pub use apex_system_service::*;
 pub use application::*;
 pub use attribute_list::*;
+pub use attribution::*;
+pub use builder::*;
 pub use category::*;
 pub use compatible_screens::*;
 pub use data::*;
+pub use deep_links::*;
+pub use device_compatibility::*;
+pub use diff::*;
+pub use document::*;
 use error::{Error, Result};
+pub use extension_sdk::*;
+pub use extra::*;
 pub use grant_uri_permission::*;
 pub use instrumentation::*;
 pub use intent_filter::*;
+pub use intent_resolution::*;
 pub use layout::*;
 pub use manifest::*;
+pub use merge::*;
 pub use meta_data::*;
+pub use package_visibility::*;
 pub use path_permission::*;
+pub use pattern_matcher::*;
 pub use permission::*;
 pub use permission_group::*;
 pub use permission_tree::*;
+pub use placeholders::*;
+pub use platform_permissions::*;
 pub use profileable::*;
+pub use property::*;
 pub use provider::*;
 pub use queries::*;
 pub use receiver::*;
 pub use resources::*;
+pub use sdk_library::*;
+pub use security::*;
 pub use service::*;
+pub use static_library::*;
 pub use supports_gl_texture::*;
 pub use supports_screens::*;
+pub use tools::*;
 pub use ui_options::*;
 pub use uses_configuration::*;
 pub use uses_feature::*;
 pub use uses_library::*;
 pub use uses_native_library::UsesNativeLibrary;
+pub use uses_package::*;
 pub use uses_permission::*;
 pub use uses_permission_sdk_23::*;
 pub use uses_sdk::*;
+pub use uses_sdk_library::*;
+pub use uses_static_library::*;
+pub use validate::*;
+pub use var_or::*;
 pub use var_or_bool::*;
 
 /// Deserialize an instance of type [`AndroidManifest`](crate::AndroidManifest) from a
 /// string of XML text.
+///
+/// On failure the [`DeserializeError`](error::DeserializeError) points at the XML syntax
+/// error or at the attribute with an invalid value.
 pub fn from_str(s: &str) -> Result<AndroidManifest> {
-    yaserde::de::from_str(s).map_err(Error::FailedToDeserialize)
+    yaserde::de::from_str(s)
+        .map_err(|message| Error::FailedToDeserialize(document::locate_error(s, message)))
+}
+
+fn from_str_unlocated(s: &str) -> std::result::Result<AndroidManifest, String> {
+    yaserde::de::from_str(s)
 }
 
 /// Deserialize an instance of type [`AndroidManifest`](crate::AndroidManifest) from an IO
 /// stream of XML text.
-pub fn from_reader<R: std::io::Read>(reader: R) -> Result<AndroidManifest> {
-    yaserde::de::from_reader(reader).map_err(Error::FailedToDeserialize)
+///
+/// UTF-8 documents are located on failure like with [`from_str`]. Documents in other
+/// encodings, like `ISO-8859-1` or UTF-16, are decoded as their XML declaration says.
+pub fn from_reader<R: std::io::Read>(mut reader: R) -> Result<AndroidManifest> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|e| Error::FailedToDeserialize(error::DeserializeError::new(e.to_string())))?;
+    let utf8 =
+        declared_encoding(&bytes).is_none_or(|encoding| encoding.eq_ignore_ascii_case("utf-8"));
+    match String::from_utf8(bytes) {
+        Ok(text) if utf8 => from_str(&text),
+        Ok(text) => from_bytes(text.as_bytes()),
+        Err(e) => from_bytes(e.as_bytes()),
+    }
+}
+
+/// Deserializes a document in any encoding xml-rs supports, without locating errors.
+fn from_bytes(bytes: &[u8]) -> Result<AndroidManifest> {
+    yaserde::de::from_reader(bytes)
+        .map_err(|message| Error::FailedToDeserialize(error::DeserializeError::new(message)))
+}
+
+/// Returns the `encoding` of the XML declaration, if the document starts with one.
+fn declared_encoding(bytes: &[u8]) -> Option<&str> {
+    let declaration = bytes.strip_prefix(b"<?xml")?;
+    let end = declaration.windows(2).position(|window| window == b"?>")?;
+    let declaration = std::str::from_utf8(&declaration[..end]).ok()?;
+    let (_, value) = declaration.split_once("encoding")?;
+    let value = value.trim_start().strip_prefix('=')?.trim_start();
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    value[1..].split(quote).next()
+}
+
+/// Deserialize an instance of type [`AndroidManifest`](crate::AndroidManifest) from the
+/// bytes of a compiled binary XML (AXML) file, as stored in APK archives.
+///
+/// References to app resources can't be resolved back to their names without
+/// `resources.arsc`, so they are kept as resource IDs, like `@drawable/0x7f080001`.
+pub fn from_binary_xml(bytes: &[u8]) -> Result<AndroidManifest> {
+    let root = binary_xml::decode(bytes).map_err(Error::FailedToDecodeBinaryXml)?;
+    from_str(&root.to_xml_string())
+}
+
+/// Deserialize an instance of type [`AndroidManifest`](crate::AndroidManifest) from the
+/// compiled `AndroidManifest.xml` at the root of an APK archive.
+pub fn from_apk<P: AsRef<Path>>(path: P) -> Result<AndroidManifest> {
+    let bytes = read_archive_entry(path.as_ref(), "AndroidManifest.xml")?;
+    from_binary_xml(&bytes)
+}
+
+/// Deserialize an instance of type [`AndroidManifest`](crate::AndroidManifest) from the
+/// base module of an Android App Bundle, where it's stored as aapt2's protobuf XML in
+/// `base/manifest/AndroidManifest.xml`.
+pub fn from_aab<P: AsRef<Path>>(path: P) -> Result<AndroidManifest> {
+    let bytes = read_archive_entry(path.as_ref(), "base/manifest/AndroidManifest.xml")?;
+    from_proto_xml(&bytes)
+}
+
+/// Deserialize an instance of type [`AndroidManifest`](crate::AndroidManifest) from the
+/// bytes of aapt2's protobuf XML (`XmlNode` message), as stored in Android App Bundles.
+pub fn from_proto_xml(bytes: &[u8]) -> Result<AndroidManifest> {
+    let root = proto_xml::decode(bytes).map_err(Error::FailedToDecodeProtoXml)?;
+    from_str(&root.to_xml_string())
+}
+
+fn read_archive_entry(path: &Path, name: &str) -> Result<Vec<u8>> {
+    let error = |e: &dyn std::fmt::Display| {
+        Error::FailedToReadArchive(format!("{}: {}", path.display(), e))
+    };
+    let file = std::fs::File::open(path).map_err(|e| error(&e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| error(&e))?;
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| error(&format!("{}: {}", name, e)))?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).map_err(|e| error(&e))?;
+    Ok(bytes)
 }
 
 /// Serialize the given [`AndroidManifest`](crate::AndroidManifest) structure as a String
 /// of XML text.
 pub fn to_string(manifest: &AndroidManifest) -> Result<String> {
-    yaserde::ser::to_string(manifest).map_err(Error::FailedToSerialize)
+    let text = yaserde::ser::to_string(manifest).map_err(Error::FailedToSerialize)?;
+    Ok(tools::remove_unused_tools_namespace(text))
+}
+
+/// Serialize the given [`AndroidManifest`](crate::AndroidManifest) structure as compiled
+/// binary XML (AXML), the way `aapt2` packages it into APK archives. `tools:` markers
+/// are dropped.
+///
+/// Resource references must carry resource IDs, like `@drawable/0x7f080001`, use
+/// [`to_binary_xml_with_resources`] to resolve references by name.
+pub fn to_binary_xml(manifest: &AndroidManifest) -> Result<Vec<u8>> {
+    to_binary_xml_with_resources(manifest, &std::collections::HashMap::new())
+}
+
+/// Serialize the given [`AndroidManifest`](crate::AndroidManifest) structure as compiled
+/// binary XML (AXML), resolving resource references through `resources`.
+///
+/// Keys of `resources` are references without the leading `@`, like
+/// `mipmap/ic_launcher` or `android:style/Theme.Black`, and values are resource IDs.
+pub fn to_binary_xml_with_resources(
+    manifest: &AndroidManifest,
+    resources: &std::collections::HashMap<String, u32>,
+) -> Result<Vec<u8>> {
+    let mut root = xml_tree::XmlElement::parse(&to_string(manifest)?)
+        .map_err(Error::FailedToEncodeBinaryXml)?;
+    root.remove_namespace(TOOLS_NAMESPACE);
+    binary_xml::encode(&root, resources).map_err(Error::FailedToEncodeBinaryXml)
+}
+
+/// Serialize the given [`AndroidManifest`](crate::AndroidManifest) structure as aapt2's
+/// protobuf XML (`XmlNode` message), the way it's stored in Android App Bundles.
+/// `tools:` markers are dropped.
+///
+/// Resource references are written by name, with their resource ID only if it's part of
+/// the reference, like `@drawable/0x7f080001`.
+pub fn to_proto_xml(manifest: &AndroidManifest) -> Result<Vec<u8>> {
+    let mut root = xml_tree::XmlElement::parse(&to_string(manifest)?)
+        .map_err(Error::FailedToEncodeProtoXml)?;
+    root.remove_namespace(TOOLS_NAMESPACE);
+    proto_xml::encode(&root).map_err(Error::FailedToEncodeProtoXml)
 }
 
 /// Serialize the given [`AndroidManifest`](crate::AndroidManifest) structure as a
@@ -101,7 +280,9 @@ pub fn to_string_pretty(manifest: &AndroidManifest) -> Result<String> {
         write_document_declaration: true,
         indent_string: None,
     };
-    yaserde::ser::to_string_with_config(manifest, &config).map_err(Error::FailedToSerialize)
+    let text =
+        yaserde::ser::to_string_with_config(manifest, &config).map_err(Error::FailedToSerialize)?;
+    Ok(tools::remove_unused_tools_namespace(text))
 }
 
 #[cfg(test)]
@@ -171,7 +352,7 @@ mod tests {
     </manifest>"#;
         let expected_manifest = AndroidManifest {
             package: "org.domokit.gcm".to_string(),
-            version_code: Some(4),
+            version_code: Some(VarOr::Value(4)),
             version_name: Some("0.0.4".to_string()),
             application: Application {
                 label: Some(StringResourceOrString::string("gcm")),
@@ -185,7 +366,7 @@ mod tests {
                         ConfigChanges::ScreenSize,
                     ]),
                     hardware_accelerated: Some(true.into()),
-                    launch_mode: Some(LaunchMode::SingleTask),
+                    launch_mode: Some(VarOr::Value(LaunchMode::SingleTask)),
                     name: "org.domokit.sky.shell.SkyActivity".to_string(),
                     theme: Some(StyleResource::new(
                         "Theme.Black.NoTitleBar",
@@ -194,9 +375,11 @@ mod tests {
                     intent_filter: vec![IntentFilter {
                         action: vec![Action {
                             name: Some("android.intent.action.MAIN".to_string()),
+                            ..Default::default()
                         }],
                         category: vec![Category {
                             name: Some("android.intent.category.LAUNCHER".to_string()),
+                            ..Default::default()
                         }],
                         ..Default::default()
                     }],
@@ -215,6 +398,7 @@ mod tests {
                         intent_filter: vec![IntentFilter {
                             action: vec![Action {
                                 name: Some("com.google.android.c2dm.intent.RECEIVE".to_string()),
+                                ..Default::default()
                             }],
                             ..Default::default()
                         }],
@@ -226,6 +410,7 @@ mod tests {
                         intent_filter: vec![IntentFilter {
                             action: vec![Action {
                                 name: Some("com.google.android.gms.iid.InstanceID".to_string()),
+                                ..Default::default()
                             }],
                             ..Default::default()
                         }],
@@ -244,9 +429,11 @@ mod tests {
                     intent_filter: vec![IntentFilter {
                         action: vec![Action {
                             name: Some("com.google.android.c2dm.intent.RECEIVE".to_string()),
+                            ..Default::default()
                         }],
                         category: vec![Category {
                             name: Some("org.domokit.sky.shell".to_string()),
+                            ..Default::default()
                         }],
                         ..Default::default()
                     }],
@@ -255,13 +442,13 @@ mod tests {
                 ..Default::default()
             },
             uses_sdk: Some(UsesSdk {
-                min_sdk_version: Some(14),
-                target_sdk_version: Some(21),
+                min_sdk_version: Some(VarOr::Value(14)),
+                target_sdk_version: Some(VarOr::Value(21)),
                 ..Default::default()
             }),
             permission: vec![Permission {
                 name: Some("org.domokit.gcm.permission.C2D_MESSAGE".to_string()),
-                protection_level: Some(ProtectionLevel::Signature),
+                protection_level: Some(VarOr::Value(BaseProtectionLevel::Signature.into())),
                 ..Default::default()
             }],
             uses_permission: vec![
@@ -291,4 +478,183 @@ mod tests {
             toml::from_str(&serialized_toml_manifest).unwrap();
         assert_eq!(deserialized_xml_manifest, deserialized_toml_manifest);
     }
+
+    #[test]
+    fn test_library_elements_round_trip() {
+        let given_xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+  <uses-sdk android:minSdkVersion="30">
+    <extension-sdk android:sdkVersion="30" android:minExtensionVersion="12" />
+  </uses-sdk>
+  <uses-permission android:name="android.permission.BLUETOOTH_SCAN" android:usesPermissionFlags="neverForLocation" />
+  <attribution android:tag="sharePhotos" android:label="@string/0x7f100001">
+    <inherit-from android:tag="photos" />
+  </attribution>
+  <application>
+    <activity android:name=".MainActivity">
+      <property android:name="android.window.PROPERTY_COMPAT_ALLOW_RESIZEABLE" android:value="true" />
+    </activity>
+    <uses-sdk-library android:name="com.example.ads" android:versionMajor="2" android:certDigest="0A:1B" />
+    <uses-static-library android:name="com.example.lib" android:version="3" android:certDigest="2C:3D">
+      <additional-certificate android:certDigest="4E:5F" />
+    </uses-static-library>
+    <uses-package android:packageType="staticLibrary" android:name="com.example.pkg" android:version="1" android:certDigest="6A:7B" />
+    <static-library android:name="com.example.shared" android:version="5" />
+    <apex-system-service android:name="com.android.server.ExampleService" android:initOrder="1" android:minSdkVersion="33" />
+    <property android:name="android.app.PROPERTY_SPECIAL_USE_FGS_SUBTYPE" android:value="sync" />
+  </application>
+</manifest>"#;
+        let manifest = from_str(given_xml).unwrap();
+        let uses_sdk = manifest.uses_sdk.as_ref().unwrap();
+        assert_eq!(
+            uses_sdk.extension_sdk[0].min_extension_version,
+            Some(VarOr::Value(12))
+        );
+        assert_eq!(
+            manifest.uses_permission[0].uses_permission_flags.vec(),
+            &vec![UsesPermissionFlags::NeverForLocation]
+        );
+        assert_eq!(
+            manifest.attribution[0].inherit_from[0].tag.as_deref(),
+            Some("photos")
+        );
+        let application = &manifest.application;
+        assert_eq!(application.activity[0].property.len(), 1);
+        assert_eq!(application.property[0].value.as_deref(), Some("sync"));
+        assert_eq!(
+            application.uses_sdk_library[0].version_major,
+            Some(VarOr::Value(2))
+        );
+        assert_eq!(
+            application.uses_static_library[0]
+                .additional_certificate
+                .len(),
+            1
+        );
+        assert_eq!(
+            application.uses_package[0].package_type.as_deref(),
+            Some("staticLibrary")
+        );
+        assert!(application.static_library.is_some());
+        assert_eq!(
+            application.apex_system_service[0].init_order,
+            Some(VarOr::Value(1))
+        );
+        for element in [&manifest.extra_elements, &application.extra_elements] {
+            assert!(element.is_empty());
+        }
+        assert_eq!(from_str(&to_string(&manifest).unwrap()).unwrap(), manifest);
+        assert_eq!(
+            from_binary_xml(&to_binary_xml(&manifest).unwrap()).unwrap(),
+            manifest
+        );
+        let invalid_flag = given_xml.replace(
+            r#"android:usesPermissionFlags="neverForLocation""#,
+            r#"android:usesPermissionFlags="neverForLocaton""#,
+        );
+        match from_str(&invalid_flag) {
+            Err(Error::FailedToDeserialize(error)) => assert_eq!(
+                error.path.as_deref(),
+                Some("manifest/uses-permission/@android:usesPermissionFlags")
+            ),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_foreground_service_type_values() {
+        let xml = |value: &str| {
+            format!(
+                r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application>
+        <service android:name=".SyncService" android:foregroundServiceType="{}" />
+    </application>
+</manifest>"#,
+                value
+            )
+        };
+        let error = match from_str(&xml("camera|locaton")) {
+            Err(Error::FailedToDeserialize(error)) => error,
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert_eq!(
+            error.path.as_deref(),
+            Some("manifest/application/service/@android:foregroundServiceType")
+        );
+        assert_eq!(error.value.as_deref(), Some("camera|locaton"));
+
+        let manifest = from_str(&xml("camera|${fgs}")).unwrap();
+        let types = &manifest.application.service[0].foreground_service_type;
+        assert_eq!(types.vec(), &vec![ForegroundServiceType::Camera]);
+        assert_eq!(types.vars(), ["${fgs}"]);
+        let manifest = from_str(&xml("${fgs}")).unwrap();
+        let written = to_string(&manifest).unwrap();
+        assert!(written.contains(r#"android:foregroundServiceType="${fgs}""#));
+        assert_eq!(from_str(&written).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_from_reader_encodings() {
+        let mut latin1 = br#"<?xml version="1.0" encoding="ISO-8859-1"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application android:label="Caf"#
+            .to_vec();
+        latin1.extend_from_slice(b"\xe9\" />\n</manifest>");
+        let manifest = from_reader(latin1.as_slice()).unwrap();
+        assert_eq!(manifest.application.label.unwrap().to_string(), "Caf\u{e9}");
+
+        let utf8 = r#"<?xml version='1.0' encoding='utf-8'?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application android:debuggable="maybe" />
+</manifest>"#;
+        let error = match from_reader(utf8.as_bytes()) {
+            Err(Error::FailedToDeserialize(error)) => error,
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert_eq!(error.line, Some(3));
+        assert_eq!(declared_encoding(utf8.as_bytes()), Some("utf-8"));
+    }
+
+    #[test]
+    fn test_from_apk_and_aab() {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+
+        let manifest = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-sdk android:minSdkVersion="24" android:targetSdkVersion="34" />
+    <application android:label="Example">
+        <activity android:name=".MainActivity" android:exported="true" />
+    </application>
+</manifest>"#)
+        .unwrap();
+        let archive = |name: &str, bytes: &[u8]| {
+            let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+            let options =
+                SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+            writer.start_file("classes.dex", options).unwrap();
+            writer.start_file(name, options).unwrap();
+            writer.write_all(bytes).unwrap();
+            writer.finish().unwrap().into_inner()
+        };
+        let dir = std::env::temp_dir().join(format!("android-manifest-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let apk = dir.join("app.apk");
+        let binary_xml = to_binary_xml(&manifest).unwrap();
+        std::fs::write(&apk, archive("AndroidManifest.xml", &binary_xml)).unwrap();
+        assert_eq!(from_apk(&apk).unwrap(), manifest);
+        let aab = dir.join("app.aab");
+        let proto_xml = to_proto_xml(&manifest).unwrap();
+        std::fs::write(
+            &aab,
+            archive("base/manifest/AndroidManifest.xml", &proto_xml),
+        )
+        .unwrap();
+        assert_eq!(from_aab(&aab).unwrap(), manifest);
+        assert!(matches!(from_aab(&apk), Err(Error::FailedToReadArchive(_))));
+        assert!(matches!(
+            from_apk(dir.join("missing.apk")),
+            Err(Error::FailedToReadArchive(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -1,5 +1,7 @@
 use super::application::Application;
+use super::attribution::Attribution;
 use super::compatible_screens::CompatibleScreens;
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::instrumentation::Instrumentation;
 use super::permission::Permission;
 use super::permission_group::PermissionGroup;
@@ -8,11 +10,13 @@ use super::queries::Queries;
 use super::resources::{Resource, StringResource};
 use super::supports_gl_texture::SupportsGlTexture;
 use super::supports_screens::SupportsScreens;
+use super::tools::ToolsAttributes;
 use super::uses_configuration::UsesConfiguration;
 use super::uses_feature::UsesFeature;
 use super::uses_permission::UsesPermission;
 use super::uses_permission_sdk_23::UsesPermissionSdk23;
 use super::uses_sdk::UsesSdk;
+use super::var_or::VarOr;
 use serde::{Deserialize, Serialize};
 
 /// The root element of the `AndroidManifest.xml` file.
@@ -26,6 +30,8 @@ use serde::{Deserialize, Serialize};
 ///           package="string"
 ///           android:sharedUserId="string"
 ///           android:sharedUserLabel="string resource"
+///           android:sharedUserMaxSdkVersion="integer"
+///           android:targetSandboxVersion="integer"
 ///           android:versionCode="integer"
 ///           android:versionName="string"
 ///           android:installLocation=["auto" | "internalOnly" | "preferExternal"] >
@@ -37,6 +43,7 @@ use serde::{Deserialize, Serialize};
 /// * [`<application>`]
 ///
 /// ## Can contain
+/// * [`<attribution>`]
 /// * [`<compatible-screens>`]
 /// * [`<instrumentation>`]
 /// * [`<permission>`]
@@ -55,6 +62,7 @@ use serde::{Deserialize, Serialize};
 /// API Level 1 for all attributes, unless noted otherwise in the attribute description.
 ///
 /// [`<application>`]: crate::Application
+/// [`<attribution>`]: crate::Attribution
 /// [`<compatible-screens>`]: crate::CompatibleScreens
 /// [`<instrumentation>`]: crate::Instrumentation
 /// [`<permission>`]: crate::Permission
@@ -71,7 +79,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Default, Clone)]
 #[yaserde(
     rename = "manifest",
-    namespace = "android: http://schemas.android.com/apk/res/android"
+    namespace = "android: http://schemas.android.com/apk/res/android",
+    namespace = "tools: http://schemas.android.com/tools"
 )]
 pub struct AndroidManifest {
     /// A full Java-language-style package name for the Android app. The name may contain
@@ -184,6 +193,15 @@ pub struct AndroidManifest {
     /// [`sharedUserId`]: crate::AndroidManifest#structfield.shared_user_id
     #[yaserde(attribute, prefix = "android", rename = "sharedUserLabel")]
     pub shared_user_label: Option<Resource<StringResource>>,
+    /// The highest API level at which the app is installed with its [`sharedUserId`].
+    /// Starting from the next API level, new installs of the app no longer join the
+    /// shared user, letting apps migrate off shared user IDs.
+    ///
+    /// This attribute was introduced in API Level 33.
+    ///
+    /// [`sharedUserId`]: crate::AndroidManifest#structfield.shared_user_id
+    #[yaserde(attribute, prefix = "android", rename = "sharedUserMaxSdkVersion")]
+    pub shared_user_max_sdk_version: Option<VarOr<u32>>,
     /// An internal version number. This number is used only to determine whether one
     /// version is more recent than another, with higher numbers indicating more
     /// recent versions. This is not the version number shown to users; that number is
@@ -196,7 +214,7 @@ pub struct AndroidManifest {
     /// "y" separately in the lower and upper 16 bits. Or you could simply increase the
     /// number by one each time a new version is released.
     #[yaserde(attribute, prefix = "android", rename = "versionCode")]
-    pub version_code: Option<u32>,
+    pub version_code: Option<VarOr<u32>>,
     /// The version number shown to users. This attribute can be set as a raw string or as
     /// a reference to a string resource. The string has no other purpose than to be
     /// displayed to users. The `versionCode` attribute holds the significant version
@@ -224,7 +242,7 @@ pub struct AndroidManifest {
     ///
     /// [`App Install Location`]: https://developer.android.com/guide/topics/data/install-location
     #[yaserde(attribute, prefix = "android", rename = "installLocation")]
-    pub install_location: Option<InstallLocation>,
+    pub install_location: Option<VarOr<InstallLocation>>,
     /// Required `<application>` tag.
     #[serde(default, skip_serializing_if = "Application::is_default")]
     pub application: Application,
@@ -273,6 +291,41 @@ pub struct AndroidManifest {
     #[yaserde(rename = "uses-permission-sdk-23")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub uses_permission_sdk_23: Vec<UsesPermissionSdk23>,
+    /// List of `<attribution>` tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attribution: Vec<Attribution>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<AndroidManifest>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
+
+impl AndroidManifest {
+    /// Returns the fully qualified form of a class name, which may be relative to the
+    /// manifest package, like `.MainActivity` or `MainActivity`.
+    pub fn qualified_class_name(&self, name: &str) -> String {
+        qualify_class_name(&self.package, name)
+    }
+}
+
+pub(crate) fn qualify_class_name(package: &str, name: &str) -> String {
+    if package.is_empty() {
+        name.to_string()
+    } else if name.starts_with('.') {
+        format!("{}{}", package, name)
+    } else if !name.contains('.') && !name.contains('$') {
+        format!("{}.{}", package, name)
+    } else {
+        name.to_string()
+    }
 }
 
 /// The default install location for the app.
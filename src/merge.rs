@@ -0,0 +1,661 @@
+//! Merging of library and overlay manifests into the main manifest of an app, following
+//! the rules of the Android Gradle plugin's manifest merger.
+
+use crate::error::{Error, Result};
+use crate::manifest::qualify_class_name;
+use crate::xml_tree::{XmlElement, XmlNode};
+use crate::{AndroidManifest, TOOLS_NAMESPACE};
+use std::fmt;
+
+/// Merges the manifests making up an app the way the Android Gradle plugin does.
+///
+/// Manifests are merged by priority: build variant overlays first, in the order they
+/// were added, then the main manifest and finally libraries, in dependency order. Each
+/// element is identified by its tag and key attribute, usually `android:name`, so that
+/// an element declared by several manifests ends up only once in the merged manifest,
+/// with the attributes of all of them. The same attribute declared with different values
+/// is a conflict that fails the merge.
+///
+/// Conflicts are resolved with the [`ToolsAttributes`](crate::ToolsAttributes) markers of
+/// the higher priority manifest, which are removed from the merged manifest.
+///
+/// ## Example
+/// ```rust,no_run
+/// # fn main() -> android_manifest::error::Result<()> {
+/// # let read = |path: &str| android_manifest::from_reader(std::fs::File::open(path).unwrap());
+/// let merged = android_manifest::ManifestMerger::new(read("src/main/AndroidManifest.xml")?)
+///     .overlay(read("src/debug/AndroidManifest.xml")?)
+///     .library(read("libs/billing/AndroidManifest.xml")?)
+///     .merge()?;
+/// for record in &merged.log {
+///     println!("{}", record);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ManifestMerger {
+    main: AndroidManifest,
+    overlays: Vec<AndroidManifest>,
+    libraries: Vec<AndroidManifest>,
+}
+
+/// Result of a successful [`ManifestMerger::merge`].
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct MergedManifest {
+    pub manifest: AndroidManifest,
+    /// What each manifest contributed, in merge order.
+    pub log: Vec<MergeRecord>,
+}
+
+/// One step of the merge, telling where an element or attribute came from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MergeRecord {
+    pub source: MergeSource,
+    /// Path of the element below `<manifest>`, like `application/activity#com.example.A`.
+    pub element: String,
+    pub action: MergeAction,
+}
+
+/// A manifest taking part in a merge.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MergeSource {
+    Main,
+    /// Build variant overlay, by index in the order overlays were added.
+    Overlay(usize),
+    /// Library manifest, by package name.
+    Library(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MergeAction {
+    /// The element was added to the merged manifest.
+    Added,
+    /// The element was already present, these attributes were added to it.
+    AttributesMerged(Vec<String>),
+    /// The permission was added because the library targets an SDK version that
+    /// implicitly granted it.
+    ImpliedPermission { reason: String },
+    /// The element was dropped because of the `tools:node` marker of a higher priority
+    /// element.
+    Removed,
+}
+
+impl ManifestMerger {
+    pub fn new(main: AndroidManifest) -> Self {
+        Self {
+            main,
+            ..Default::default()
+        }
+    }
+
+    /// Adds a build type or product flavor manifest. Overlays have priority over the main
+    /// manifest and over overlays added after them.
+    pub fn overlay(mut self, manifest: AndroidManifest) -> Self {
+        self.overlays.push(manifest);
+        self
+    }
+
+    /// Adds a library manifest. Libraries have lower priority than the main manifest and
+    /// than libraries added before them.
+    pub fn library(mut self, manifest: AndroidManifest) -> Self {
+        self.libraries.push(manifest);
+        self
+    }
+
+    /// Merges all manifests. Fails with every conflict found if any.
+    pub fn merge(&self) -> Result<MergedManifest> {
+        let mut sources = Vec::with_capacity(self.overlays.len() + 1);
+        for (index, overlay) in self.overlays.iter().enumerate() {
+            sources.push((MergeSource::Overlay(index), overlay));
+        }
+        sources.push((MergeSource::Main, &self.main));
+
+        let mut merge = Merge::default();
+        let mut root = XmlElement::new("manifest");
+        for (source, manifest) in sources {
+            let tree = tree(manifest, &self.main.package)?;
+            merge.element(&mut root, &tree, "manifest", &source);
+        }
+        for library in &self.libraries {
+            let source = MergeSource::Library(library.package.clone());
+            let tree = tree(library, &library.package)?;
+            merge.library(&mut root, &tree, &source);
+        }
+        if !merge.errors.is_empty() {
+            return Err(Error::FailedToMerge(merge.errors.join("\n")));
+        }
+        remove_marked(&mut root);
+        root.remove_namespace(TOOLS_NAMESPACE);
+        Ok(MergedManifest {
+            manifest: crate::from_str(&root.to_xml_string())?,
+            log: merge.log,
+        })
+    }
+}
+
+/// Converts a manifest to an XML tree with class names made fully qualified, relative to
+/// `package` if the manifest doesn't declare one.
+pub(crate) fn tree(manifest: &AndroidManifest, package: &str) -> Result<XmlElement> {
+    let mut root =
+        XmlElement::parse(&crate::to_string(manifest)?).map_err(Error::FailedToSerialize)?;
+    if manifest.package.is_empty() {
+        qualify_class_names(&mut root, package);
+    } else {
+        qualify_class_names(&mut root, &manifest.package);
+    }
+    Ok(root)
+}
+
+/// Expands class names relative to the package, like `.MainActivity`.
+fn qualify_class_names(element: &mut XmlElement, package: &str) {
+    let attributes: &[&str] = match element.name.as_str() {
+        "application" => &["name", "backupAgent"],
+        "activity" => &["name", "parentActivityName"],
+        "activity-alias" => &["name", "targetActivity"],
+        "service" | "receiver" | "provider" | "instrumentation" => &["name"],
+        _ => &[],
+    };
+    for attribute in &mut element.attributes {
+        if attribute.prefix.as_deref() == Some("android")
+            && attributes.contains(&attribute.name.as_str())
+        {
+            attribute.value = qualify_class_name(package, &attribute.value);
+        }
+    }
+    for child in &mut element.children {
+        if let XmlNode::Element(child) = child {
+            qualify_class_names(child, package);
+        }
+    }
+}
+
+/// Returns the value identifying an element among its siblings of the same tag. Elements
+/// without a key are identified by their whole content.
+pub(crate) fn key(element: &XmlElement) -> Option<String> {
+    let android = |name| element.attribute(Some("android"), name).map(str::to_string);
+    match element.name.as_str() {
+        "application" | "uses-sdk" | "supports-screens" | "compatible-screens" | "queries"
+        | "sdk-library" | "static-library" => Some(String::new()),
+        "intent-filter"
+        | "intent"
+        | "data"
+        | "uses-configuration"
+        | "grant-uri-permission"
+        | "path-permission" => None,
+        "uses-feature" => android("name").or_else(|| android("glEsVersion")),
+        "attribution" | "inherit-from" => android("tag"),
+        "extension-sdk" => android("sdkVersion"),
+        "additional-certificate" => android("certDigest"),
+        "provider" => android("name").or_else(|| android("authorities")),
+        "screen" => Some(format!(
+            "{}:{}",
+            android("screenSize").unwrap_or_default(),
+            android("screenDensity").unwrap_or_default()
+        )),
+        _ => android("name"),
+    }
+}
+
+pub(crate) fn describe(element: &XmlElement) -> String {
+    match key(element) {
+        Some(key) if !key.is_empty() => format!("{}#{}", element.name, key),
+        _ => element.name.clone(),
+    }
+}
+
+fn tools_node(element: &XmlElement) -> Option<&str> {
+    element.attribute(Some("tools"), "node")
+}
+
+/// Returns the comma separated values of a `tools:` attribute.
+fn tools_list(element: &XmlElement, name: &str) -> Vec<String> {
+    element
+        .attribute(Some("tools"), name)
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Removes elements marked with `tools:node="remove"` or `"removeAll"` and the
+/// attributes listed in `tools:remove`.
+fn remove_marked(element: &mut XmlElement) {
+    let removed = tools_list(element, "remove");
+    element.attributes.retain(|attribute| {
+        let qualified_name = match &attribute.prefix {
+            Some(prefix) => format!("{}:{}", prefix, attribute.name),
+            None => attribute.name.clone(),
+        };
+        !removed.contains(&qualified_name)
+    });
+    element.children.retain(|child| match child {
+        XmlNode::Element(child) => !matches!(tools_node(child), Some("remove" | "removeAll")),
+        XmlNode::Text(_) => true,
+    });
+    for child in &mut element.children {
+        if let XmlNode::Element(child) = child {
+            remove_marked(child);
+        }
+    }
+}
+
+/// Whether two elements are the same, `tools:` markers and attribute order aside.
+pub(crate) fn is_same_element(a: &XmlElement, b: &XmlElement) -> bool {
+    fn normalize(element: &mut XmlElement) {
+        element.namespaces.clear();
+        element
+            .attributes
+            .retain(|attribute| attribute.prefix.as_deref() != Some("tools"));
+        element
+            .attributes
+            .sort_by(|a, b| (&a.prefix, &a.name).cmp(&(&b.prefix, &b.name)));
+        for child in &mut element.children {
+            if let XmlNode::Element(child) = child {
+                normalize(child);
+            }
+        }
+    }
+    let (mut a, mut b) = (a.clone(), b.clone());
+    normalize(&mut a);
+    normalize(&mut b);
+    a == b
+}
+
+fn sdk_version(root: &XmlElement, name: &str) -> Option<u32> {
+    root.elements()
+        .find(|element| element.name == "uses-sdk")
+        .and_then(|uses_sdk| uses_sdk.attribute(Some("android"), name))
+        .and_then(|value| value.parse().ok())
+}
+
+/// Minimum and target SDK versions, with the platform defaults.
+fn sdk_versions(root: &XmlElement) -> (u32, u32) {
+    let min_sdk = sdk_version(root, "minSdkVersion").unwrap_or(1);
+    let target_sdk = sdk_version(root, "targetSdkVersion").unwrap_or(min_sdk);
+    (min_sdk, target_sdk)
+}
+
+fn has_permission(root: &XmlElement, permission: &str) -> bool {
+    root.elements().any(|element| {
+        element.name == "uses-permission"
+            && element.attribute(Some("android"), "name") == Some(permission)
+    })
+}
+
+/// Attributes declared with different values that aren't a conflict: the merged element
+/// requires the feature or library if any manifest does.
+fn is_or_merged(element: &XmlElement, prefix: Option<&str>, name: &str) -> bool {
+    matches!(element.name.as_str(), "uses-feature" | "uses-library")
+        && prefix == Some("android")
+        && name == "required"
+}
+
+#[derive(Default)]
+struct Merge {
+    log: Vec<MergeRecord>,
+    errors: Vec<String>,
+}
+
+impl Merge {
+    fn record(&mut self, source: &MergeSource, element: &str, action: MergeAction) {
+        self.log.push(MergeRecord {
+            source: source.clone(),
+            element: element.to_string(),
+            action,
+        });
+    }
+
+    /// Merges the attributes and children of `from` into `into`, which has priority.
+    fn element(
+        &mut self,
+        into: &mut XmlElement,
+        from: &XmlElement,
+        path: &str,
+        source: &MergeSource,
+    ) {
+        let node = tools_node(into).map(str::to_string);
+        match node.as_deref() {
+            Some("remove" | "replace") => {
+                self.record(source, path, MergeAction::Removed);
+                return;
+            }
+            Some("strict") if !is_same_element(into, from) => {
+                self.errors.push(format!(
+                    "Element {} is declared with tools:node=\"strict\" but differs in {}",
+                    path, source
+                ));
+                return;
+            }
+            _ => {}
+        }
+        for namespace in &from.namespaces {
+            if !into
+                .namespaces
+                .iter()
+                .any(|(prefix, _)| *prefix == namespace.0)
+            {
+                into.namespaces.push(namespace.clone());
+            }
+        }
+        let replaced = tools_list(into, "replace");
+        let removed = tools_list(into, "remove");
+        let mut added = Vec::new();
+        for attribute in &from.attributes {
+            let prefix = attribute.prefix.as_deref();
+            let qualified_name = match prefix {
+                Some(prefix) => format!("{}:{}", prefix, attribute.name),
+                None => attribute.name.clone(),
+            };
+            if removed.contains(&qualified_name) {
+                continue;
+            }
+            match into.attribute(prefix, &attribute.name) {
+                // Markers only apply to the manifest declaring them.
+                _ if prefix == Some("tools") => {}
+                None => {
+                    into.attributes.push(attribute.clone());
+                    added.push(qualified_name);
+                }
+                Some(value) if value == attribute.value => {}
+                Some(_) if replaced.contains(&qualified_name) => {}
+                Some(_) if is_or_merged(into, prefix, &attribute.name) => {
+                    if attribute.value == "true" {
+                        into.set_attribute(prefix, &attribute.name, "true");
+                    }
+                }
+                Some(value) => self.errors.push(format!(
+                    "Attribute {}@{} value=({}) is also present in {} with value=({}), \
+                     add tools:replace=\"{}\" to override it",
+                    path, qualified_name, value, source, attribute.value, qualified_name
+                )),
+            }
+        }
+        if !added.is_empty() {
+            self.record(source, path, MergeAction::AttributesMerged(added));
+        }
+        if node.as_deref() != Some("merge-only-attributes") {
+            self.children(into, from, path, source, false);
+        }
+    }
+
+    fn children(
+        &mut self,
+        into: &mut XmlElement,
+        from: &XmlElement,
+        path: &str,
+        source: &MergeSource,
+        library: bool,
+    ) {
+        for child in from.elements() {
+            // The SDK versions of libraries are only checked against the app's ones.
+            if library && child.name == "uses-sdk" {
+                continue;
+            }
+            let child_path = match path {
+                "manifest" => describe(child),
+                path => format!("{}/{}", path, describe(child)),
+            };
+            let removed_all = into.elements().any(|element| {
+                element.name == child.name && tools_node(element) == Some("removeAll")
+            });
+            if removed_all {
+                self.record(source, &child_path, MergeAction::Removed);
+                continue;
+            }
+            let key = key(child);
+            let existing = into.children.iter_mut().find_map(|node| match node {
+                XmlNode::Element(element) if element.name == child.name => {
+                    let matches = match &key {
+                        Some(key) => self::key(element).as_ref() == Some(key),
+                        None => element == child,
+                    };
+                    matches.then_some(element)
+                }
+                _ => None,
+            });
+            match existing {
+                Some(existing) => self.element(existing, child, &child_path, source),
+                None => {
+                    into.children.push(XmlNode::Element(child.clone()));
+                    self.record(source, &child_path, MergeAction::Added);
+                }
+            }
+        }
+    }
+
+    fn library(&mut self, root: &mut XmlElement, library: &XmlElement, source: &MergeSource) {
+        let (app_min_sdk, app_target_sdk) = sdk_versions(root);
+        let (library_min_sdk, library_target_sdk) = sdk_versions(library);
+        let overridden = root
+            .elements()
+            .find(|element| element.name == "uses-sdk")
+            .map(|uses_sdk| tools_list(uses_sdk, "overrideLibrary"))
+            .unwrap_or_default();
+        let package = library.attribute(None, "package").unwrap_or_default();
+        if library_min_sdk > app_min_sdk && !overridden.iter().any(|p| p == package) {
+            self.errors.push(format!(
+                "uses-sdk:minSdkVersion {} cannot be smaller than version {} declared in {}, \
+                 add tools:overrideLibrary=\"{}\" to force its usage",
+                app_min_sdk, library_min_sdk, source, package
+            ));
+        }
+        // Root attributes like `package` and `versionCode` belong to the app.
+        self.children(root, library, "manifest", source, true);
+
+        let mut implied = Vec::new();
+        if library_target_sdk < 4 && app_target_sdk >= 4 {
+            implied.push(("android.permission.WRITE_EXTERNAL_STORAGE", 4));
+            implied.push(("android.permission.READ_PHONE_STATE", 4));
+        }
+        if library_target_sdk < 16 && app_target_sdk >= 16 {
+            let pairs = [
+                (
+                    "android.permission.WRITE_EXTERNAL_STORAGE",
+                    "android.permission.READ_EXTERNAL_STORAGE",
+                ),
+                (
+                    "android.permission.READ_CONTACTS",
+                    "android.permission.READ_CALL_LOG",
+                ),
+                (
+                    "android.permission.WRITE_CONTACTS",
+                    "android.permission.WRITE_CALL_LOG",
+                ),
+            ];
+            for (requested, permission) in pairs {
+                if has_permission(library, requested)
+                    || implied.iter().any(|(implied, _)| *implied == requested)
+                {
+                    implied.push((permission, 16));
+                }
+            }
+        }
+        for (permission, sdk) in implied {
+            if has_permission(root, permission) {
+                continue;
+            }
+            let mut element = XmlElement::new("uses-permission");
+            element.set_attribute(Some("android"), "name", permission);
+            root.children.push(XmlNode::Element(element));
+            let reason = format!(
+                "{} has targetSdkVersion {}, lower than {}",
+                source, library_target_sdk, sdk
+            );
+            self.record(
+                source,
+                &format!("uses-permission#{}", permission),
+                MergeAction::ImpliedPermission { reason },
+            );
+        }
+    }
+}
+
+impl fmt::Display for MergeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Main => write!(f, "main manifest"),
+            Self::Overlay(index) => write!(f, "overlay manifest #{}", index + 1),
+            Self::Library(package) => write!(f, "library {}", package),
+        }
+    }
+}
+
+impl fmt::Display for MergeRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.action {
+            MergeAction::Added => write!(f, "ADDED {} from {}", self.element, self.source),
+            MergeAction::AttributesMerged(attributes) => write!(
+                f,
+                "MERGED {}@{} from {}",
+                self.element,
+                attributes.join(","),
+                self.source
+            ),
+            MergeAction::ImpliedPermission { reason } => {
+                write!(f, "IMPLIED {} because {}", self.element, reason)
+            }
+            MergeAction::Removed => write!(f, "REMOVED {} from {}", self.element, self.source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AttributeList;
+
+    fn manifest(xml: &str) -> AndroidManifest {
+        crate::from_str(xml).unwrap()
+    }
+
+    #[test]
+    fn test_merge_library_manifest() {
+        let main = manifest(
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-sdk android:minSdkVersion="21" android:targetSdkVersion="33" />
+    <uses-permission android:name="android.permission.INTERNET" />
+    <application android:label="App">
+        <activity android:name=".MainActivity" />
+    </application>
+</manifest>"#,
+        );
+        let library = manifest(
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.library">
+    <uses-sdk android:minSdkVersion="14" android:targetSdkVersion="3" />
+    <uses-permission android:name="android.permission.INTERNET" />
+    <application android:allowBackup="false">
+        <activity android:name=".LibraryActivity" android:exported="false" />
+    </application>
+</manifest>"#,
+        );
+        let merged = ManifestMerger::new(main.clone())
+            .library(library.clone())
+            .merge()
+            .unwrap();
+        let application = &merged.manifest.application;
+        assert_eq!(application.allow_backup, Some(false.into()));
+        let activities: Vec<_> = application
+            .activity
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect();
+        assert_eq!(
+            activities,
+            ["com.example.MainActivity", "com.library.LibraryActivity"]
+        );
+        let permissions: Vec<_> = merged
+            .manifest
+            .uses_permission
+            .iter()
+            .filter_map(|p| p.name.as_deref())
+            .collect();
+        assert_eq!(
+            permissions,
+            [
+                "android.permission.INTERNET",
+                "android.permission.WRITE_EXTERNAL_STORAGE",
+                "android.permission.READ_PHONE_STATE",
+                "android.permission.READ_EXTERNAL_STORAGE",
+            ]
+        );
+        let library_source = MergeSource::Library("com.library".to_string());
+        assert!(merged.log.contains(&MergeRecord {
+            source: library_source.clone(),
+            element: "application/activity#com.library.LibraryActivity".to_string(),
+            action: MergeAction::Added,
+        }));
+        assert!(merged.log.contains(&MergeRecord {
+            source: library_source,
+            element: "application".to_string(),
+            action: MergeAction::AttributesMerged(vec!["android:allowBackup".to_string()]),
+        }));
+
+        let mut conflicting = library.clone();
+        conflicting.application.label = Some(crate::StringResourceOrString::string("Library"));
+        conflicting.uses_sdk.as_mut().unwrap().min_sdk_version = Some(crate::VarOr::Value(24));
+        let error = ManifestMerger::new(main)
+            .library(conflicting)
+            .merge()
+            .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("application@android:label value=(App)"));
+        assert!(message.contains("minSdkVersion 21 cannot be smaller than version 24"));
+    }
+
+    #[test]
+    fn test_merge_tools_markers() {
+        let main = manifest(
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" xmlns:tools="http://schemas.android.com/tools" package="com.example">
+    <uses-sdk android:minSdkVersion="21" tools:overrideLibrary="com.library" />
+    <uses-permission android:name="android.permission.CAMERA" tools:node="remove" />
+    <application android:label="App" tools:replace="android:label">
+        <activity android:name="com.library.LibraryActivity" tools:node="remove" />
+    </application>
+</manifest>"#,
+        );
+        assert_eq!(
+            main.application.tools.replace,
+            AttributeList::from_vec(vec!["android:label".to_string()])
+        );
+        assert_eq!(
+            crate::from_str(&crate::to_string(&main).unwrap()).unwrap(),
+            main
+        );
+
+        let library = manifest(
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.library">
+    <uses-sdk android:minSdkVersion="24" android:targetSdkVersion="33" />
+    <uses-permission android:name="android.permission.CAMERA" />
+    <application android:label="Library" android:allowBackup="false">
+        <activity android:name=".LibraryActivity" />
+    </application>
+</manifest>"#,
+        );
+        assert!(crate::to_string(&main).unwrap().contains("xmlns:tools="));
+        assert!(!crate::to_string(&library).unwrap().contains("xmlns:tools="));
+        assert!(!crate::to_string_pretty(&library)
+            .unwrap()
+            .contains("xmlns:tools="));
+        let merged = ManifestMerger::new(main).library(library).merge().unwrap();
+        let application = &merged.manifest.application;
+        assert_eq!(
+            application.label,
+            Some(crate::StringResourceOrString::string("App"))
+        );
+        assert_eq!(application.allow_backup, Some(false.into()));
+        assert!(application.tools.is_empty());
+        assert!(application.activity.is_empty());
+        assert!(merged.manifest.uses_permission.is_empty());
+        assert!(merged.log.contains(&MergeRecord {
+            source: MergeSource::Library("com.library".to_string()),
+            element: "application/activity#com.library.LibraryActivity".to_string(),
+            action: MergeAction::Removed,
+        }));
+    }
+}
@@ -1,4 +1,6 @@
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::resources::*;
+use super::tools::ToolsAttributes;
 use serde::{Deserialize, Serialize};
 
 /// A name-value pair for an item of additional, arbitrary data that can be
@@ -79,4 +81,16 @@ pub struct MetaData {
     /// following table: https://developer.android.com/guide/topics/manifest/meta-data-element#val
     #[yaserde(attribute, prefix = "android")]
     pub value: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<MetaData>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
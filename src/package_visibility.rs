@@ -0,0 +1,391 @@
+//! Package visibility filtering of Android 11 (API level 30) and higher: whether an app
+//! can see another installed package, given the `<queries>` of its manifest.
+
+use crate::intent_resolution::{matches_mime_type, Uri};
+use crate::validate::declared_target_sdk_version;
+use crate::{AndroidManifest, Intent, IntentFilter, IntentRequest, VarOrBool};
+use serde::{Deserialize, Serialize};
+
+const QUERY_ALL_PACKAGES: &str = "android.permission.QUERY_ALL_PACKAGES";
+
+/// Why a package is visible to an app.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum VisibilityReason {
+    /// The app targets API level 29 or lower, where packages aren't filtered.
+    LegacyTargetSdk,
+    /// The app requests the `android.permission.QUERY_ALL_PACKAGES` permission.
+    QueryAllPackages,
+    /// The package is the app itself.
+    SamePackage,
+    /// The package is declared by a `<package>` query.
+    Package,
+    /// The package has an exported component with an intent filter matching an
+    /// `<intent>` query.
+    Intent,
+    /// The package has an exported provider with an authority declared by a
+    /// `<provider>` query.
+    Provider,
+}
+
+/// Whether a package is visible to an app.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum Visibility {
+    Visible(VisibilityReason),
+    /// Android filters the package out.
+    Filtered,
+    /// No query makes the package visible, and the manifest doesn't set a target SDK
+    /// version, or sets it to a placeholder, so it isn't known whether the app is subject
+    /// to filtering. Source manifests usually get it from the build.
+    UnknownTargetSdk,
+}
+
+impl Visibility {
+    pub fn is_visible(&self) -> bool {
+        matches!(self, Self::Visible(_))
+    }
+
+    /// Returns why the package is visible, or `None` if it isn't known to be.
+    pub fn reason(&self) -> Option<VisibilityReason> {
+        match self {
+            Self::Visible(reason) => Some(*reason),
+            Self::Filtered | Self::UnknownTargetSdk => None,
+        }
+    }
+}
+
+impl AndroidManifest {
+    /// Returns whether the package named `package` is visible to the app, and why. A
+    /// package that is filtered out may still be one of the packages
+    /// [`visible automatically`], like system packages, which aren't known offline.
+    ///
+    /// Only the app, its target SDK version, its permissions and its `<package>` queries
+    /// are taken into account. Use [`app_visibility()`](Self::app_visibility) to also
+    /// match the `<intent>` and `<provider>` queries against the manifest of the package.
+    ///
+    /// ```
+    /// # use android_manifest::*;
+    /// let manifest = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    ///     <uses-sdk android:minSdkVersion="24" android:targetSdkVersion="34" />
+    ///     <queries>
+    ///         <package android:name="com.example.store" />
+    ///         <intent>
+    ///             <action android:name="android.intent.action.SEND" />
+    ///             <data android:mimeType="image/*" />
+    ///         </intent>
+    ///     </queries>
+    ///     <application />
+    /// </manifest>"#).unwrap();
+    /// assert_eq!(
+    ///     manifest.package_visibility("com.example.store"),
+    ///     Visibility::Visible(VisibilityReason::Package)
+    /// );
+    /// assert_eq!(manifest.package_visibility("com.example.music"), Visibility::Filtered);
+    /// let intent = IntentRequest::new("android.intent.action.SEND").mime_type("image/png");
+    /// assert_eq!(
+    ///     manifest.intent_visibility(&intent).reason(),
+    ///     Some(VisibilityReason::Intent)
+    /// );
+    /// ```
+    ///
+    /// [`visible automatically`]: https://developer.android.com/training/package-visibility/automatic
+    pub fn package_visibility(&self, package: &str) -> Visibility {
+        if let Some(reason) = self.unfiltered_visibility() {
+            return Visibility::Visible(reason);
+        }
+        if package == self.package {
+            return Visibility::Visible(VisibilityReason::SamePackage);
+        }
+        let queried = self
+            .queries
+            .iter()
+            .flat_map(|queries| &queries.package)
+            .any(|query| query.name == package);
+        match queried {
+            true => Visibility::Visible(VisibilityReason::Package),
+            false => self.filtered_visibility(),
+        }
+    }
+
+    /// Returns whether the apps handling `intent` are visible to the app, and why. They
+    /// are filtered out when Android may filter some of them out.
+    ///
+    /// An `<intent>` query covers `intent` when every intent filter matching `intent`
+    /// also matches the query: same action, no more categories, same data scheme, a `*`
+    /// or the same host, and a MIME type matching the one of `intent`.
+    pub fn intent_visibility(&self, intent: &IntentRequest) -> Visibility {
+        if let Some(reason) = self.unfiltered_visibility() {
+            return Visibility::Visible(reason);
+        }
+        let queried = self
+            .queries
+            .iter()
+            .flat_map(|queries| &queries.intent)
+            .any(|query| covers(&query.to_request(), intent));
+        match queried {
+            true => Visibility::Visible(VisibilityReason::Intent),
+            false => self.filtered_visibility(),
+        }
+    }
+
+    /// Returns whether the app of the `other` manifest is visible to the app, and why.
+    ///
+    /// On top of the rules of [`package_visibility()`](Self::package_visibility), the
+    /// `<intent>` queries are matched against the intent filters of the activities,
+    /// activity aliases, services and receivers of `other`, and the `<provider>`
+    /// queries against the authorities of its providers. Components with
+    /// `android:exported="false"` are ignored.
+    pub fn app_visibility(&self, other: &AndroidManifest) -> Visibility {
+        let visibility = self.package_visibility(&other.package);
+        let queries = match &self.queries {
+            Some(queries) if !visibility.is_visible() => queries,
+            _ => return visibility,
+        };
+        let application = &other.application;
+        let intent_filters = application
+            .activity
+            .iter()
+            .map(|activity| (&activity.exported, &activity.intent_filter))
+            .chain(
+                application
+                    .activity_alias
+                    .iter()
+                    .map(|alias| (&alias.exported, &alias.intent_filter)),
+            )
+            .chain(
+                application
+                    .service
+                    .iter()
+                    .map(|service| (&service.exported, &service.intent_filter)),
+            )
+            .chain(
+                application
+                    .receiver
+                    .iter()
+                    .map(|receiver| (&receiver.exported, &receiver.intent_filter)),
+            )
+            .filter(|(exported, _)| !is_unexported(exported))
+            .flat_map(|(_, intent_filters)| intent_filters)
+            .collect::<Vec<&IntentFilter>>();
+        let visible_by_intent = queries.intent.iter().any(|query| {
+            let request = query.to_request();
+            intent_filters
+                .iter()
+                .any(|intent_filter| intent_filter.matches_wildcards(&request))
+        });
+        if visible_by_intent {
+            return Visibility::Visible(VisibilityReason::Intent);
+        }
+        let visible_by_provider = application
+            .provider
+            .iter()
+            .filter(|provider| !is_unexported(&provider.exported))
+            .flat_map(|provider| provider.authorities.vec())
+            .any(|authority| {
+                queries
+                    .provider
+                    .iter()
+                    .any(|query| query.authorities.vec().contains(authority))
+            });
+        match visible_by_provider {
+            true => Visibility::Visible(VisibilityReason::Provider),
+            false => visibility,
+        }
+    }
+
+    /// Visibility of all packages, when the app isn't subject to filtering.
+    fn unfiltered_visibility(&self) -> Option<VisibilityReason> {
+        if declared_target_sdk_version(self).is_some_and(|version| version < 30) {
+            return Some(VisibilityReason::LegacyTargetSdk);
+        }
+        self.uses_permission
+            .iter()
+            .any(|permission| permission.name.as_deref() == Some(QUERY_ALL_PACKAGES))
+            .then_some(VisibilityReason::QueryAllPackages)
+    }
+
+    /// Visibility of the packages that no query makes visible.
+    fn filtered_visibility(&self) -> Visibility {
+        match declared_target_sdk_version(self) {
+            Some(_) => Visibility::Filtered,
+            None => Visibility::UnknownTargetSdk,
+        }
+    }
+}
+
+impl Intent {
+    /// Returns the intent that the `<intent>` query stands for, built like Android does:
+    /// the data URI is made of the scheme and host with a `/*` path, a MIME type without
+    /// subtype gets a `/*` one, and a MIME type without scheme gets a `content://*/*`
+    /// URI.
+    pub fn to_request(&self) -> IntentRequest {
+        let scheme = self.data.iter().find_map(|data| data.scheme.as_deref());
+        let host = self.data.iter().find_map(|data| data.host.as_deref());
+        let mime_type = self
+            .data
+            .iter()
+            .find_map(|data| data.mime_type.as_deref())
+            .map(|mime_type| match mime_type.contains('/') {
+                true => mime_type.to_string(),
+                false => format!("{}/*", mime_type),
+            });
+        let data = match (scheme, host) {
+            (Some(scheme), Some(host)) => Some(format!("{}://{}/*", scheme, host)),
+            (Some(scheme), None) => Some(format!("{}:/*", scheme)),
+            (None, _) => mime_type.as_ref().map(|_| "content://*/*".to_string()),
+        };
+        IntentRequest {
+            action: self.action.first().and_then(|action| action.name.clone()),
+            categories: self
+                .category
+                .iter()
+                .filter_map(|category| category.name.clone())
+                .collect(),
+            data,
+            mime_type,
+        }
+    }
+}
+
+/// Whether every intent filter matching `intent` also matches `query`.
+fn covers(query: &IntentRequest, intent: &IntentRequest) -> bool {
+    if query.action.is_some() && query.action != intent.action {
+        return false;
+    }
+    if !query
+        .categories
+        .iter()
+        .all(|category| intent.categories.contains(category))
+    {
+        return false;
+    }
+    let matches_data = match (query.data.as_deref().map(Uri::parse), &intent.data) {
+        (None, data) => data.is_none(),
+        (Some(query), Some(data)) => {
+            let data = Uri::parse(data);
+            query.scheme == data.scheme
+                && (query.host == "*" || query.host.eq_ignore_ascii_case(data.host))
+        }
+        // Filters without scheme only accept `content:` and `file:` URIs.
+        (Some(query), None) => ["content", "file"].contains(&query.scheme),
+    };
+    matches_data
+        && match (&query.mime_type, &intent.mime_type) {
+            (None, mime_type) => mime_type.is_none(),
+            (Some(query), Some(mime_type)) => matches_mime_type(&[query.as_str()], mime_type),
+            (Some(_), None) => false,
+        }
+}
+
+fn is_unexported(exported: &Option<VarOrBool>) -> bool {
+    exported == &Some(VarOrBool::Bool(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VarOr;
+
+    #[test]
+    fn test_app_visibility() {
+        let manifest = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-sdk android:minSdkVersion="24" android:targetSdkVersion="34" />
+    <queries>
+        <package android:name="com.example.store" />
+        <intent>
+            <action android:name="android.intent.action.VIEW" />
+            <category android:name="android.intent.category.BROWSABLE" />
+            <data android:scheme="https" android:host="*" />
+        </intent>
+        <intent>
+            <action android:name="android.intent.action.SEND" />
+            <data android:mimeType="image" />
+        </intent>
+        <provider android:authorities="com.example.settings;com.example.files" />
+    </queries>
+    <application />
+</manifest>"#)
+        .unwrap();
+        let queries = manifest.queries.as_ref().unwrap();
+        assert_eq!(queries.intent.len(), 2);
+        assert_eq!(
+            queries.intent[1].to_request(),
+            IntentRequest::new("android.intent.action.SEND")
+                .data("content://*/*")
+                .mime_type("image/*")
+        );
+        let browser = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="org.browser">
+    <application>
+        <activity android:name=".BrowserActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.DEFAULT" />
+                <category android:name="android.intent.category.BROWSABLE" />
+                <data android:scheme="https" android:host="*" android:pathPrefix="/" />
+            </intent-filter>
+        </activity>
+    </application>
+</manifest>"#)
+        .unwrap();
+        assert_eq!(
+            manifest.app_visibility(&browser),
+            Visibility::Visible(VisibilityReason::Intent)
+        );
+        let files = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="org.files">
+    <application>
+        <provider android:name=".FilesProvider" android:authorities="com.example.files" android:exported="true" />
+        <activity android:name=".ViewerActivity" android:exported="false">
+            <intent-filter>
+                <action android:name="android.intent.action.SEND" />
+                <data android:mimeType="image/png" />
+            </intent-filter>
+        </activity>
+    </application>
+</manifest>"#)
+        .unwrap();
+        assert_eq!(
+            manifest.app_visibility(&files),
+            Visibility::Visible(VisibilityReason::Provider)
+        );
+        let intent = IntentRequest::new("android.intent.action.VIEW")
+            .category("android.intent.category.BROWSABLE")
+            .data("https://example.com/");
+        assert_eq!(
+            manifest.intent_visibility(&intent),
+            Visibility::Visible(VisibilityReason::Intent)
+        );
+        let intent = IntentRequest::new("android.intent.action.VIEW").data("https://example.com/");
+        assert_eq!(manifest.intent_visibility(&intent), Visibility::Filtered);
+        assert_eq!(
+            manifest.package_visibility("com.example"),
+            Visibility::Visible(VisibilityReason::SamePackage)
+        );
+        let mut legacy = manifest.clone();
+        legacy.uses_sdk.as_mut().unwrap().target_sdk_version = Some(VarOr::Value(29));
+        assert_eq!(
+            legacy.package_visibility("org.files"),
+            Visibility::Visible(VisibilityReason::LegacyTargetSdk)
+        );
+        let mut unknown = manifest.clone();
+        unknown.uses_sdk = None;
+        assert_eq!(
+            unknown.package_visibility("org.files"),
+            Visibility::UnknownTargetSdk
+        );
+        assert_eq!(
+            unknown.package_visibility("com.example.store"),
+            Visibility::Visible(VisibilityReason::Package)
+        );
+        unknown.uses_sdk = legacy.uses_sdk.clone();
+        unknown.uses_sdk.as_mut().unwrap().target_sdk_version = Some(VarOr::var("${targetSdk}"));
+        assert_eq!(
+            unknown.app_visibility(&browser),
+            Visibility::Visible(VisibilityReason::Intent)
+        );
+        assert_eq!(
+            unknown.intent_visibility(&intent),
+            Visibility::UnknownTargetSdk
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::pattern_matcher::path_matchers;
+use crate::{ExtraAttributes, ExtraElements, PatternMatcher, ToolsAttributes};
 use serde::{Deserialize, Serialize};
 
 /// Defines the path and required permissions for a specific subset of data
@@ -11,6 +13,8 @@ use serde::{Deserialize, Serialize};
 /// <path-permission android:path="string"
 ///                  android:pathPrefix="string"
 ///                  android:pathPattern="string"
+///                  android:pathAdvancedPattern="string"
+///                  android:pathSuffix="string"
 ///                  android:permission="string"
 ///                  android:readPermission="string"
 ///                  android:writePermission="string" />
@@ -61,6 +65,21 @@ pub struct PathPermission {
     /// [`PatternMatcher`]: https://developer.android.com/reference/android/os/PatternMatcher
     #[yaserde(attribute, prefix = "android", rename = "pathPattern")]
     pub path_pattern: Option<String>,
+    /// A path pattern with character sets and quantifiers, matched as
+    /// [`PATTERN_ADVANCED_GLOB`].
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`PATTERN_ADVANCED_GLOB`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_ADVANCED_GLOB
+    #[yaserde(attribute, prefix = "android", rename = "pathAdvancedPattern")]
+    pub path_advanced_pattern: Option<String>,
+    /// The final part of a path, matched as [`PATTERN_SUFFIX`].
+    ///
+    /// This attribute was introduced in API Level 31.
+    ///
+    /// [`PATTERN_SUFFIX`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_SUFFIX
+    #[yaserde(attribute, prefix = "android", rename = "pathSuffix")]
+    pub path_suffix: Option<String>,
     /// The name of a permission that clients must have in order to read or write the
     /// content provider's data. This attribute is a convenient way of setting a
     /// single permission for both reading and writing. However, the `readPermission`
@@ -74,4 +93,36 @@ pub struct PathPermission {
     /// controlled by the content provider.
     #[yaserde(attribute, prefix = "android", rename = "writePermission")]
     pub write_permission: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<PathPermission>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
+
+impl PathPermission {
+    /// Returns the matchers of the path attributes of this element.
+    pub fn path_matchers(&self) -> Vec<PatternMatcher> {
+        path_matchers(
+            &self.path,
+            &self.path_prefix,
+            &self.path_pattern,
+            &self.path_advanced_pattern,
+            &self.path_suffix,
+        )
+    }
+
+    /// Returns whether the URI path `path` matches one of the path attributes.
+    pub fn matches_path(&self, path: &str) -> bool {
+        self.path_matchers()
+            .iter()
+            .any(|matcher| matcher.matches(path))
+    }
 }
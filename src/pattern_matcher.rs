@@ -0,0 +1,433 @@
+//! Port of Android's [`PatternMatcher`], used to match the paths of intent filter data,
+//! URI permission grants and path permissions.
+//!
+//! [`PatternMatcher`]: https://developer.android.com/reference/android/os/PatternMatcher
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// How a [`PatternMatcher`] pattern is interpreted.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum PatternType {
+    /// [`PATTERN_LITERAL`]: the text must be equal to the pattern. Used for `path`.
+    ///
+    /// [`PATTERN_LITERAL`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_LITERAL
+    Literal,
+    /// [`PATTERN_PREFIX`]: the text must start with the pattern. Used for `pathPrefix`.
+    ///
+    /// [`PATTERN_PREFIX`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_PREFIX
+    Prefix,
+    /// [`PATTERN_SIMPLE_GLOB`]: `.` matches any character, `*` matches zero or more of
+    /// the preceding character and `\` escapes the next one. Used for `pathPattern`.
+    ///
+    /// [`PATTERN_SIMPLE_GLOB`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_SIMPLE_GLOB
+    SimpleGlob,
+    /// [`PATTERN_ADVANCED_GLOB`]: like [`SimpleGlob`](PatternType::SimpleGlob), with
+    /// character sets like `[a-z0-9_]` or `[^/]`, and the `+` and `{min,max}`
+    /// quantifiers. Used for `pathAdvancedPattern`.
+    ///
+    /// [`PATTERN_ADVANCED_GLOB`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_ADVANCED_GLOB
+    AdvancedGlob,
+    /// [`PATTERN_SUFFIX`]: the text must end with the pattern. Used for `pathSuffix`.
+    ///
+    /// [`PATTERN_SUFFIX`]: https://developer.android.com/reference/android/os/PatternMatcher#PATTERN_SUFFIX
+    Suffix,
+}
+
+/// A path pattern of one of the [`PatternType`]s, matching texts the way Android does.
+///
+/// Like Android, globs match greedily without backtracking: `.*` consumes everything up
+/// to the first occurrence of the character after it, so `.*a/b` doesn't match `a/a/b`.
+///
+/// Patterns are matched as Android sees them, after the XML compiler has processed the
+/// backslash escapes of the manifest, so `"\\*"` in a manifest is the pattern `\*`.
+///
+/// ```
+/// # use android_manifest::*;
+/// let matcher = PatternMatcher::advanced_glob("/items/[0-9]+/edit").unwrap();
+/// assert!(matcher.matches("/items/42/edit"));
+/// assert!(!matcher.matches("/items/new/edit"));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PatternMatcher {
+    pattern: String,
+    pattern_type: PatternType,
+    tokens: Vec<Token>,
+}
+
+/// A token of an advanced glob, repeated between `min` and `max` times.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Token {
+    kind: TokenKind,
+    min: usize,
+    max: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum TokenKind {
+    Any,
+    Literal(char),
+    Set {
+        inverse: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl PatternMatcher {
+    /// Returns a matcher of `pattern`, failing if it's an invalid advanced glob.
+    pub fn new(pattern: impl Into<String>, pattern_type: PatternType) -> Result<Self> {
+        let pattern = pattern.into();
+        let tokens = match pattern_type {
+            PatternType::AdvancedGlob => parse_advanced_glob(&pattern)
+                .map_err(|message| Error::InvalidPattern(format!("`{}`: {}", pattern, message)))?,
+            _ => Vec::new(),
+        };
+        Ok(Self {
+            pattern,
+            pattern_type,
+            tokens,
+        })
+    }
+
+    pub fn literal(pattern: impl Into<String>) -> Self {
+        Self::infallible(pattern.into(), PatternType::Literal)
+    }
+
+    pub fn prefix(pattern: impl Into<String>) -> Self {
+        Self::infallible(pattern.into(), PatternType::Prefix)
+    }
+
+    pub fn simple_glob(pattern: impl Into<String>) -> Self {
+        Self::infallible(pattern.into(), PatternType::SimpleGlob)
+    }
+
+    pub fn advanced_glob(pattern: impl Into<String>) -> Result<Self> {
+        Self::new(pattern, PatternType::AdvancedGlob)
+    }
+
+    pub fn suffix(pattern: impl Into<String>) -> Self {
+        Self::infallible(pattern.into(), PatternType::Suffix)
+    }
+
+    fn infallible(pattern: String, pattern_type: PatternType) -> Self {
+        Self {
+            pattern,
+            pattern_type,
+            tokens: Vec::new(),
+        }
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn pattern_type(&self) -> PatternType {
+        self.pattern_type
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        match self.pattern_type {
+            PatternType::Literal => self.pattern == text,
+            PatternType::Prefix => text.starts_with(&self.pattern),
+            PatternType::SimpleGlob => matches_simple_glob(&self.pattern, text),
+            PatternType::AdvancedGlob => matches_advanced_glob(&self.tokens, text),
+            PatternType::Suffix => text.ends_with(&self.pattern),
+        }
+    }
+}
+
+/// Returns the matchers of the path attributes of an element, skipping invalid advanced
+/// globs, which Android rejects when installing the app.
+pub(crate) fn path_matchers(
+    path: &Option<String>,
+    path_prefix: &Option<String>,
+    path_pattern: &Option<String>,
+    path_advanced_pattern: &Option<String>,
+    path_suffix: &Option<String>,
+) -> Vec<PatternMatcher> {
+    let mut matchers = Vec::new();
+    matchers.extend(path.as_deref().map(PatternMatcher::literal));
+    matchers.extend(path_prefix.as_deref().map(PatternMatcher::prefix));
+    matchers.extend(path_pattern.as_deref().map(PatternMatcher::simple_glob));
+    matchers.extend(
+        path_advanced_pattern
+            .as_deref()
+            .and_then(|pattern| PatternMatcher::advanced_glob(pattern).ok()),
+    );
+    matchers.extend(path_suffix.as_deref().map(PatternMatcher::suffix));
+    matchers
+}
+
+fn matches_simple_glob(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    if pattern.is_empty() {
+        return text.is_empty();
+    }
+    let at = |index: usize| pattern.get(index).copied().unwrap_or('\0');
+    let (mut ip, mut it) = (0, 0);
+    let mut next = pattern[0];
+    while ip < pattern.len() && it < text.len() {
+        let mut c = next;
+        ip += 1;
+        next = at(ip);
+        let escaped = c == '\\';
+        if escaped {
+            c = next;
+            ip += 1;
+            next = at(ip);
+        }
+        if next == '*' {
+            if !escaped && c == '.' {
+                if ip >= pattern.len() - 1 {
+                    return true;
+                }
+                ip += 1;
+                next = pattern[ip];
+                if next == '\\' {
+                    ip += 1;
+                    next = at(ip);
+                }
+                while it < text.len() && text[it] != next {
+                    it += 1;
+                }
+                if it == text.len() {
+                    return false;
+                }
+                ip += 1;
+                next = at(ip);
+                it += 1;
+            } else {
+                while it < text.len() && text[it] == c {
+                    it += 1;
+                }
+                ip += 1;
+                next = at(ip);
+            }
+        } else {
+            if c != '.' && text[it] != c {
+                return false;
+            }
+            it += 1;
+        }
+    }
+    if ip >= pattern.len() && it >= text.len() {
+        return true;
+    }
+    ip + 2 == pattern.len() && pattern[ip] == '.' && pattern[ip + 1] == '*'
+}
+
+fn parse_advanced_glob(pattern: &str) -> std::result::Result<Vec<Token>, &'static str> {
+    let chars = pattern.chars().collect::<Vec<_>>();
+    let mut tokens: Vec<Token> = Vec::new();
+    // Whether the last token already has a quantifier.
+    let mut quantified = false;
+    let mut ip = 0;
+    while ip < chars.len() {
+        let kind = match chars[ip] {
+            '[' => {
+                let inverse = chars.get(ip + 1) == Some(&'^');
+                ip += if inverse { 2 } else { 1 };
+                let mut ranges = Vec::new();
+                loop {
+                    let start = match chars.get(ip) {
+                        None => return Err("set was not terminated"),
+                        Some(']') => break,
+                        Some('\\') => {
+                            ip += 1;
+                            *chars.get(ip).ok_or("escape found at end of pattern")?
+                        }
+                        Some(c) => *c,
+                    };
+                    ip += 1;
+                    let end = match (chars.get(ip), chars.get(ip + 1)) {
+                        (Some('-'), Some(end)) if *end != ']' => {
+                            ip += 1;
+                            let end = match end {
+                                '\\' => {
+                                    ip += 1;
+                                    *chars.get(ip).ok_or("escape found at end of pattern")?
+                                }
+                                end => *end,
+                            };
+                            ip += 1;
+                            end
+                        }
+                        _ => start,
+                    };
+                    ranges.push((start, end));
+                }
+                if ranges.is_empty() {
+                    return Err("you must define characters in set");
+                }
+                ip += 1;
+                TokenKind::Set { inverse, ranges }
+            }
+            '*' | '+' | '{' => {
+                let last = match tokens.last_mut() {
+                    Some(last) if !quantified => last,
+                    _ => return Err("modifier must follow a token"),
+                };
+                let (min, max) = match chars[ip] {
+                    '*' => (0, usize::MAX),
+                    '+' => (1, usize::MAX),
+                    _ => {
+                        let end = chars[ip..]
+                            .iter()
+                            .position(|c| *c == '}')
+                            .ok_or("range not ended with '}'")?;
+                        let range = chars[ip + 1..ip + end].iter().collect::<String>();
+                        ip += end;
+                        parse_range(&range)?
+                    }
+                };
+                last.min = min;
+                last.max = max;
+                quantified = true;
+                ip += 1;
+                continue;
+            }
+            // Like Android, a closing brace outside of a range is dropped.
+            '}' => {
+                ip += 1;
+                continue;
+            }
+            '.' => {
+                ip += 1;
+                TokenKind::Any
+            }
+            '\\' => {
+                let c = *chars.get(ip + 1).ok_or("escape found at end of pattern")?;
+                ip += 2;
+                TokenKind::Literal(c)
+            }
+            c => {
+                ip += 1;
+                TokenKind::Literal(c)
+            }
+        };
+        tokens.push(Token {
+            kind,
+            min: 1,
+            max: 1,
+        });
+        quantified = false;
+    }
+    Ok(tokens)
+}
+
+/// Parses the inside of a `{min,max}`, `{min,}` or `{count}` quantifier.
+fn parse_range(range: &str) -> std::result::Result<(usize, usize), &'static str> {
+    let number = |number: &str| {
+        number
+            .parse::<usize>()
+            .map_err(|_| "range number format incorrect")
+    };
+    let (min, max) = match range.split_once(',') {
+        Some((min, "")) => (number(min)?, usize::MAX),
+        Some((min, max)) => (number(min)?, number(max)?),
+        None => (number(range)?, number(range)?),
+    };
+    match min > max {
+        true => Err("range quantifier minimum is greater than maximum"),
+        false => Ok((min, max)),
+    }
+}
+
+/// Matches each token as many times as possible, without backtracking.
+fn matches_advanced_glob(tokens: &[Token], text: &str) -> bool {
+    let text = text.chars().collect::<Vec<_>>();
+    let mut it = 0;
+    for token in tokens {
+        let mut matched = 0;
+        while matched < token.max
+            && text
+                .get(it + matched)
+                .is_some_and(|c| token.kind.matches(*c))
+        {
+            matched += 1;
+        }
+        if matched < token.min {
+            return false;
+        }
+        it += matched;
+    }
+    it >= text.len()
+}
+
+impl TokenKind {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Literal(literal) => c == *literal,
+            Self::Set { inverse, ranges } => {
+                ranges
+                    .iter()
+                    .any(|(start, end)| (*start..=*end).contains(&c))
+                    != *inverse
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matcher() {
+        let simple = PatternMatcher::simple_glob;
+        assert!(simple("/items/.*/edit").matches("/items/42/edit"));
+        assert!(simple("/a.*").matches("/a"));
+        assert!(simple("/fo*").matches("/foooo"));
+        assert!(simple("/a\\*b").matches("/a*b"));
+        assert!(!simple("/a\\*b").matches("/aab"));
+        // `.*` doesn't backtrack, as on Android.
+        assert!(!simple(".*a/b").matches("a/a/b"));
+
+        let advanced = |pattern| PatternMatcher::advanced_glob(pattern).unwrap();
+        assert!(advanced("/user/[a-z0-9_]+").matches("/user/jane_doe"));
+        assert!(!advanced("/user/[a-z0-9_]+").matches("/user/"));
+        assert!(advanced("/[^/]+/view").matches("/42/view"));
+        assert!(!advanced("/[^/]+/view").matches("/a/b/view"));
+        assert!(advanced("/v[0-9]{1,2}/.*").matches("/v12/items"));
+        assert!(!advanced("/v[0-9]{1,2}/.*").matches("/v123/items"));
+        assert!(advanced("/id/[0-9]{3,}").matches("/id/12345"));
+        assert!(advanced("/price/\\$[.0-9]*").matches("/price/$4.99"));
+        assert!(advanced("/[a\\-z]").matches("/-"));
+        assert!(!advanced("/[a\\-z]").matches("/b"));
+
+        for invalid in ["/[a-z", "*a", "/a**", "/[]", "/a{2,1}", "/a{x}", "/a\\"] {
+            assert!(
+                PatternMatcher::advanced_glob(invalid).is_err(),
+                "{}",
+                invalid
+            );
+        }
+
+        assert!(PatternMatcher::suffix(".pdf").matches("/docs/report.pdf"));
+        assert!(PatternMatcher::prefix("/docs/").matches("/docs/report.pdf"));
+        assert!(!PatternMatcher::literal("/docs").matches("/docs/"));
+
+        let manifest = crate::from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application>
+        <provider android:name=".FileProvider" android:authorities="com.example.files" android:exported="false">
+            <grant-uri-permission android:pathAdvancedPattern="/shared/[0-9]+" />
+        </provider>
+        <provider android:name=".DocumentProvider" android:authorities="com.example.documents" android:exported="false">
+            <grant-uri-permission android:pathSuffix=".pdf" />
+        </provider>
+    </application>
+</manifest>"#).unwrap();
+        let grant = |index: usize| {
+            manifest.application.provider[index]
+                .grant_uri_permission
+                .clone()
+                .unwrap()
+        };
+        assert!(grant(0).matches_path("/shared/42"));
+        assert!(!grant(0).matches_path("/private/42"));
+        assert!(grant(1).matches_path("/docs/report.pdf"));
+    }
+}
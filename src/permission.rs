@@ -1,7 +1,14 @@
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::resources::{
     MipmapOrDrawableResource, Resource, StringResource, StringResourceOrString,
 };
-use serde::{Deserialize, Serialize};
+use super::tools::ToolsAttributes;
+use super::var_or::VarOr;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
+use yaserde::{YaDeserialize, YaSerialize};
 
 /// Declares a security permission.
 ///
@@ -13,6 +20,7 @@ use serde::{Deserialize, Serialize};
 /// ```xml
 /// <permission android:description="string resource"
 ///             android:icon="drawable resource"
+///             android:knownCerts="string array resource | string"
 ///             android:label="string resource"
 ///             android:name="string"
 ///             android:permissionGroup="string"
@@ -45,6 +53,13 @@ pub struct Permission {
     /// A reference to a drawable resource for an icon that represents the permission.
     #[yaserde(attribute, prefix = "android")]
     pub icon: Option<MipmapOrDrawableResource>,
+    /// The SHA-256 digests of the signing certificates of the apps granted the
+    /// permission when its protection level has the `knownSigner` flag, or a reference
+    /// to a string array resource listing them.
+    ///
+    /// This attribute was introduced in API Level 31.
+    #[yaserde(attribute, prefix = "android", rename = "knownCerts")]
+    pub known_certs: Option<String>,
     /// A name for the permission, one that can be displayed to users. As a convenience,
     /// the label can be directly set as a raw string while you're developing the
     /// application. However, when the application is ready to be published, it should
@@ -86,24 +101,189 @@ pub struct Permission {
     /// the protection level `"signature|privileged"` is a combination of the
     /// `"signature"` base permission type and the `"privileged"` flag.
     #[yaserde(attribute, prefix = "android", rename = "protectionLevel")]
-    pub protection_level: Option<ProtectionLevel>,
+    pub protection_level: Option<VarOr<ProtectionLevel>>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Permission>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
-/// The following table shows all base permission types. For a list of flags,
-/// see [`protectionLevel`].
+/// Protection level of a permission: a base permission type and zero or more flags,
+/// written like `signature|privileged`. See [`protectionLevel`].
+///
+/// The base type is written first, and omitted when it's `normal` and there are flags.
 ///
 /// [`protectionLevel`]: https://developer.android.com/reference/android/R.attr#protectionLevel
-#[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct ProtectionLevel {
+    pub base: BaseProtectionLevel,
+    pub flags: Vec<ProtectionFlag>,
+}
+
+impl ProtectionLevel {
+    pub fn new(base: BaseProtectionLevel) -> Self {
+        Self {
+            base,
+            flags: Vec::new(),
+        }
+    }
+
+    /// Adds a flag.
+    pub fn flag(mut self, flag: ProtectionFlag) -> Self {
+        self.flags.push(flag);
+        self
+    }
+
+    pub fn has_flag(&self, flag: &ProtectionFlag) -> bool {
+        self.flags.contains(flag)
+    }
+
+    /// Whether the permission is a runtime permission, that the user grants while the
+    /// app runs: a `dangerous` one.
+    pub fn is_runtime(&self) -> bool {
+        self.base == BaseProtectionLevel::Dangerous
+    }
+
+    /// Whether the permission is only granted to apps signed with the same certificate
+    /// as the app declaring it, possibly among others.
+    pub fn is_signature(&self) -> bool {
+        matches!(
+            self.base,
+            BaseProtectionLevel::Signature | BaseProtectionLevel::SignatureOrSystem
+        )
+    }
+
+    /// Whether the permission is granted to privileged apps of the system image, with
+    /// the `privileged` flag or the `signatureOrSystem` base type.
+    pub fn is_privileged(&self) -> bool {
+        self.base == BaseProtectionLevel::SignatureOrSystem
+            || self.has_flag(&ProtectionFlag::Privileged)
+    }
+
+    /// Whether an app op controls the permission, which the user can then grant in
+    /// special app access settings.
+    pub fn is_appop(&self) -> bool {
+        self.has_flag(&ProtectionFlag::Appop)
+    }
+
+    /// Whether the permission can be granted to apps with `adb shell pm grant`.
+    pub fn is_development(&self) -> bool {
+        self.has_flag(&ProtectionFlag::Development)
+    }
+}
+
+impl From<BaseProtectionLevel> for ProtectionLevel {
+    fn from(base: BaseProtectionLevel) -> Self {
+        Self::new(base)
+    }
+}
+
+impl fmt::Display for ProtectionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut values = Vec::new();
+        if self.base != BaseProtectionLevel::Normal || self.flags.is_empty() {
+            values.push(serde_plain::to_string(&self.base).map_err(|_| fmt::Error)?);
+        }
+        for flag in &self.flags {
+            values.push(serde_plain::to_string(flag).map_err(|_| fmt::Error)?);
+        }
+        f.write_str(&values.join("|"))
+    }
+}
+
+impl FromStr for ProtectionLevel {
+    type Err = String;
+
+    /// Parses a `|` separated protection level, `normal` unless a base type is given.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut base = None;
+        let mut flags = Vec::new();
+        for value in value.split('|').map(str::trim) {
+            if let Ok(value) = serde_plain::from_str(value) {
+                if base.replace(value).is_some() {
+                    return Err("protection level has more than one base type".to_string());
+                }
+            } else {
+                let flag = serde_plain::from_str(value)
+                    .map_err(|_| format!("value `{}` is not a valid protection level", value))?;
+                flags.push(flag);
+            }
+        }
+        Ok(Self {
+            base: base.unwrap_or_default(),
+            flags,
+        })
+    }
+}
+
+impl Serialize for ProtectionLevel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for ProtectionLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl YaSerialize for ProtectionLevel {
+    fn serialize<W: Write>(&self, writer: &mut yaserde::ser::Serializer<W>) -> Result<(), String> {
+        let _ret = writer.write(xml::writer::XmlEvent::characters(&self.to_string()));
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<xml::attribute::OwnedAttribute>,
+        namespace: xml::namespace::Namespace,
+    ) -> Result<
+        (
+            Vec<xml::attribute::OwnedAttribute>,
+            xml::namespace::Namespace,
+        ),
+        String,
+    > {
+        Ok((attributes, namespace))
+    }
+}
+
+impl YaDeserialize for ProtectionLevel {
+    fn deserialize<R: Read>(reader: &mut yaserde::de::Deserializer<R>) -> Result<Self, String> {
+        loop {
+            match reader.next_event()? {
+                xml::reader::XmlEvent::StartElement { .. } => {}
+                xml::reader::XmlEvent::Characters(ref v) => return v.parse(),
+                _ => break,
+            }
+        }
+        Err("Unable to parse attribute".to_string())
+    }
+}
+
+/// The following table shows all base permission types. For a list of flags,
+/// see [`ProtectionFlag`].
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 #[derive(Default)]
-pub enum ProtectionLevel {
+pub enum BaseProtectionLevel {
     /// The default value. A lower-risk permission that gives requesting applications
     /// access to isolated application-level features, with minimal risk to other
     /// applications, the system, or the user. The system automatically grants this
     /// type of permission to a requesting application at installation, without asking
     /// for the user's explicit approval (though the user always has the option to
     /// review these permissions before installing).
-    #[yaserde(rename = "normal")]
     #[default]
     Normal,
     /// A higher-risk permission that would give a requesting application access to
@@ -114,13 +294,11 @@ pub enum ProtectionLevel {
     /// and require confirmation before proceeding, or some other approach may
     /// be taken to avoid the user automatically allowing the use of such
     /// facilities.
-    #[yaserde(rename = "dangerous")]
     Dangerous,
     /// A permission that the system grants only if the requesting application is signed
     /// with the same certificate as the application that declared the permission. If
     /// the certificates match, the system automatically grants the permission without
     /// notifying the user or asking for the user's explicit approval.
-    #[yaserde(rename = "signature")]
     Signature,
     /// Old synonym for `"signature|privileged"`. Deprecated in API level 23. A permission
     /// that the system grants only to applications that are in a dedicated folder on
@@ -132,6 +310,110 @@ pub enum ProtectionLevel {
     /// situations where multiple vendors have applications
     /// built into a system image and need to share specific features
     /// explicitly because they are being built together.
-    #[yaserde(rename = "signatureOrSystem")]
     SignatureOrSystem,
+    /// A permission only granted to apps with one of the flags of the protection level,
+    /// like `role`. Added in API level 29.
+    Internal,
+}
+
+/// Flag of a protection level, granting the permission to more apps than its base type.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum ProtectionFlag {
+    /// Granted to privileged apps of the system image. Also accepted as `system`, its
+    /// deprecated synonym, and written back as `privileged`.
+    #[serde(alias = "system")]
+    Privileged,
+    /// Can be granted with `adb shell pm grant`.
+    Development,
+    /// Controlled by an app op, that the user grants in special app access settings.
+    Appop,
+    /// Automatically granted to apps targeting API level 22 or lower.
+    Pre23,
+    /// Granted to the package installer.
+    Installer,
+    /// Granted to the package verifier.
+    Verifier,
+    /// Granted to any preinstalled app.
+    Preinstalled,
+    /// Granted to the setup wizard.
+    Setup,
+    /// Granted to instant apps.
+    Instant,
+    /// Only granted to apps that support runtime permissions.
+    Runtime,
+    /// Granted to allowlisted apps of the OEM partition.
+    Oem,
+    /// Granted to privileged apps of the vendor partition.
+    VendorPrivileged,
+    /// Granted to the system text classifier.
+    TextClassifier,
+    /// Granted to the device configurator.
+    Configurator,
+    /// Granted to the app approving incident reports.
+    IncidentReportApprover,
+    /// Granted to the system app predictor.
+    AppPredictor,
+    /// Granted to APEX modules.
+    Module,
+    /// Granted to the companion device manager.
+    Companion,
+    /// Granted to the retail demo app.
+    RetailDemo,
+    /// Granted to the system recents app.
+    Recents,
+    /// Granted to the holders of a role that requires the permission.
+    Role,
+    /// Granted to apps signed with one of the certificates of `android:knownCerts`.
+    KnownSigner,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protection_level_round_trip() {
+        let xml = r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <permission android:name="com.example.permission.ROLE" android:protectionLevel="internal|role" />
+    <permission android:name="com.example.permission.DEBUG" android:protectionLevel="signature|appop|development" />
+    <permission android:name="com.example.permission.CAMERA" android:protectionLevel="dangerous" />
+    <permission android:name="com.example.permission.INSTANT" android:protectionLevel="instant" />
+    <permission android:name="com.example.permission.SYSTEM" android:protectionLevel="signature|system" />
+    <application />
+</manifest>"#;
+        let manifest = crate::from_str(xml).unwrap();
+        let levels: Vec<&ProtectionLevel> = manifest
+            .permission
+            .iter()
+            .filter_map(|permission| permission.protection_level.as_ref()?.value())
+            .collect();
+        assert_eq!(
+            levels[0],
+            &ProtectionLevel::new(BaseProtectionLevel::Internal).flag(ProtectionFlag::Role)
+        );
+        assert!(levels[1].is_signature() && levels[1].is_appop() && levels[1].is_development());
+        assert!(levels[2].is_runtime() && !levels[2].is_privileged());
+        assert_eq!(levels[3].base, BaseProtectionLevel::Normal);
+        assert_eq!(
+            "signatureOrSystem"
+                .parse::<ProtectionLevel>()
+                .map(|level| level.is_privileged()),
+            Ok(true)
+        );
+        assert_eq!(
+            levels[4],
+            &ProtectionLevel::new(BaseProtectionLevel::Signature).flag(ProtectionFlag::Privileged)
+        );
+        assert_eq!(
+            "signature|system".parse::<ProtectionLevel>(),
+            "signature|privileged".parse::<ProtectionLevel>()
+        );
+        assert!("signature|dangerous".parse::<ProtectionLevel>().is_err());
+        let written = crate::to_string(&manifest).unwrap();
+        assert!(written.contains(r#"android:protectionLevel="signature|appop|development""#));
+        assert!(written.contains(r#"android:protectionLevel="instant""#));
+        assert!(written.contains(r#"android:protectionLevel="signature|privileged""#));
+        assert_eq!(crate::from_str(&written).unwrap(), manifest);
+    }
 }
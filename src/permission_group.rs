@@ -1,6 +1,8 @@
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::resources::{
     MipmapOrDrawableResource, Resource, StringResource, StringResourceOrString,
 };
+use super::tools::ToolsAttributes;
 use serde::{Deserialize, Serialize};
 
 /// Declares a name for a logical grouping of related permissions.
@@ -56,4 +58,16 @@ pub struct PermissionGroup {
     /// [`<permissionGroup>`]: crate::Permission#structfield.permission_group
     #[yaserde(attribute, prefix = "android")]
     pub name: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<PermissionGroup>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
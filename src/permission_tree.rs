@@ -1,4 +1,6 @@
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::resources::{MipmapOrDrawableResource, StringResourceOrString};
+use super::tools::ToolsAttributes;
 use serde::{Deserialize, Serialize};
 
 /// Declares the base name for a tree of permissions.
@@ -55,4 +57,16 @@ pub struct PermissionTree {
     /// in its path — for example, `com.example.base` is OK, but `com.example` is not.
     #[yaserde(attribute, prefix = "android")]
     pub name: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<PermissionTree>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
@@ -0,0 +1,279 @@
+//! Substitution of `${name}` placeholders, the way the Android Gradle plugin injects
+//! `manifestPlaceholders` into manifests.
+
+use crate::error::{Error, Result};
+use crate::xml_tree::{XmlElement, XmlNode};
+use crate::AndroidManifest;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A `${name}` placeholder left in the manifest because no value was given for it.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub struct UnresolvedPlaceholder {
+    /// Name of the placeholder, like `applicationId`.
+    pub name: String,
+    /// Path of the attribute holding the placeholder, like
+    /// `manifest/application/provider/@android:authorities`.
+    pub path: String,
+}
+
+/// Replaces the `${name}` placeholders in all attribute values of the manifest with the
+/// values of `values`, and returns the placeholders that have no value.
+///
+/// Attributes are parsed again once their placeholders are replaced, so a
+/// [`VarOrBool::Var`](crate::VarOrBool::Var) resolved to `true` becomes
+/// [`VarOrBool::Bool`](crate::VarOrBool::Bool), and a [`VarOr::Var`](crate::VarOr::Var)
+/// becomes a [`VarOr::Value`](crate::VarOr::Value). Fails if a resolved value isn't valid
+/// for its attribute, leaving the manifest unchanged.
+///
+/// Unlike the Android Gradle plugin, no placeholder is predefined, `applicationId` must
+/// be part of `values` too.
+///
+/// ```
+/// # use android_manifest::*;
+/// # use std::collections::HashMap;
+/// let mut manifest = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+///     <application android:debuggable="${debuggable}" />
+/// </manifest>"#).unwrap();
+/// let values = HashMap::from([("debuggable".to_string(), "false".to_string())]);
+/// let unresolved = resolve_placeholders(&mut manifest, &values).unwrap();
+/// assert!(unresolved.is_empty());
+/// assert_eq!(manifest.application.debuggable, Some(VarOrBool::Bool(false)));
+/// ```
+pub fn resolve_placeholders(
+    manifest: &mut AndroidManifest,
+    values: &HashMap<String, String>,
+) -> Result<Vec<UnresolvedPlaceholder>> {
+    let mut root =
+        XmlElement::parse(&crate::to_string(manifest)?).map_err(Error::FailedToSerialize)?;
+    let mut unresolved = Vec::new();
+    resolve(&mut root, "manifest", values, &mut unresolved);
+    *manifest = crate::from_str(&root.to_xml_string())?;
+    Ok(unresolved)
+}
+
+fn resolve(
+    element: &mut XmlElement,
+    path: &str,
+    values: &HashMap<String, String>,
+    unresolved: &mut Vec<UnresolvedPlaceholder>,
+) {
+    for attribute in &mut element.attributes {
+        let name = match &attribute.prefix {
+            Some(prefix) => format!("{}:{}", prefix, attribute.name),
+            None => attribute.name.clone(),
+        };
+        let mut missing = Vec::new();
+        attribute.value = substitute(&attribute.value, values, &mut missing);
+        unresolved.extend(
+            missing
+                .into_iter()
+                .map(|placeholder| UnresolvedPlaceholder {
+                    name: placeholder,
+                    path: format!("{}/@{}", path, name),
+                }),
+        );
+    }
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for child in element.elements() {
+        *counts.entry(child.name.clone()).or_default() += 1;
+    }
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    for child in &mut element.children {
+        if let XmlNode::Element(child) = child {
+            let index = indices.entry(child.name.clone()).or_default();
+            *index += 1;
+            let child_path = match counts[&child.name] {
+                1 => format!("{}/{}", path, child.name),
+                _ => format!("{}/{}[{}]", path, child.name, index),
+            };
+            resolve(child, &child_path, values, unresolved);
+        }
+    }
+}
+
+/// Replaces the placeholders of `value`, collecting the names of those without a value
+/// into `missing`.
+fn substitute(value: &str, values: &HashMap<String, String>, missing: &mut Vec<String>) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let name = &rest[start + 2..end];
+        result.push_str(&rest[..start]);
+        match values.get(name) {
+            Some(value) => result.push_str(value),
+            None => {
+                result.push_str(&rest[start..=end]);
+                missing.push(name.to_string());
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InstallLocation, LaunchMode, ScreenOrientation, VarOr, VarOrBool};
+
+    #[test]
+    fn test_resolve_placeholders() {
+        let given_xml = r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="${applicationId}" android:versionCode="${versionCode}">
+    <application android:label="${appName} (${flavor})" android:debuggable="${debuggable}">
+        <activity android:name=".MainActivity" android:launchMode="${launchMode}" />
+        <provider android:name=".FileProvider" android:authorities="${applicationId}.files;${applicationId}.cache" android:exported="false" />
+        <meta-data android:name="com.example.API_KEY" android:value="${apiKey}" />
+    </application>
+</manifest>"#;
+        let mut manifest = crate::from_str(given_xml).unwrap();
+        assert_eq!(
+            manifest.application.debuggable,
+            Some(VarOrBool::var("${debuggable}"))
+        );
+        assert_eq!(manifest.version_code, Some(VarOr::var("${versionCode}")));
+        let values = HashMap::from([
+            ("applicationId".to_string(), "com.example.app".to_string()),
+            ("appName".to_string(), "Example".to_string()),
+            ("flavor".to_string(), "free".to_string()),
+            ("debuggable".to_string(), "true".to_string()),
+            ("versionCode".to_string(), "42".to_string()),
+            ("launchMode".to_string(), "singleTask".to_string()),
+        ]);
+        let unresolved = resolve_placeholders(&mut manifest, &values).unwrap();
+        assert_eq!(
+            unresolved,
+            vec![UnresolvedPlaceholder {
+                name: "apiKey".to_string(),
+                path: "manifest/application/meta-data/@android:value".to_string(),
+            }]
+        );
+        assert_eq!(manifest.package, "com.example.app");
+        assert_eq!(manifest.application.debuggable, Some(VarOrBool::Bool(true)));
+        assert_eq!(manifest.version_code, Some(VarOr::Value(42)));
+        assert_eq!(
+            manifest.application.activity[0].launch_mode,
+            Some(VarOr::Value(LaunchMode::SingleTask))
+        );
+        assert_eq!(
+            manifest.application.provider[0].authorities.vec(),
+            &vec![
+                "com.example.app.files".to_string(),
+                "com.example.app.cache".to_string()
+            ]
+        );
+        assert_eq!(
+            manifest.application.meta_data[0].value.as_deref(),
+            Some("${apiKey}")
+        );
+    }
+
+    #[test]
+    fn test_enum_placeholders_round_trip() {
+        let given_xml = r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example" android:installLocation="${installLocation}">
+    <uses-configuration android:reqTouchScreen="${touchScreen}" />
+    <supports-gl-texture android:name="${texture}" />
+    <permission android:name="com.example.permission.SYNC" android:protectionLevel="${protectionLevel}" />
+    <uses-permission android:name="android.permission.BLUETOOTH_SCAN" android:usesPermissionFlags="${permissionFlags}" />
+    <application android:appCategory="${category}" android:gwpAsanMode="${gwpAsan}" android:memtagMode="${memtag}" android:uiOptions="${uiOptions}">
+        <activity android:name=".MainActivity" android:configChanges="orientation|${configChanges}" android:windowSoftInputMode="${softInputMode}" android:screenOrientation="${orientation}" android:documentLaunchMode="${documentLaunchMode}" android:lockTaskMode="${lockTask}" android:persistableMode="${persistable}" android:colorMode="${colorMode}" android:maxAspectRatio="${maxAspectRatio}" android:minAspectRatio="1.5" />
+    </application>
+</manifest>"#;
+        let mut manifest = crate::from_str(given_xml).unwrap();
+        let activity = &manifest.application.activity[0];
+        assert_eq!(
+            manifest.install_location,
+            Some(VarOr::var("${installLocation}"))
+        );
+        assert_eq!(
+            activity.screen_orientation,
+            Some(VarOr::var("${orientation}"))
+        );
+        assert_eq!(
+            activity.document_launch_mode,
+            Some(VarOr::var("${documentLaunchMode}"))
+        );
+        assert_eq!(
+            activity.max_aspect_ratio,
+            Some(VarOr::var("${maxAspectRatio}"))
+        );
+        assert_eq!(activity.min_aspect_ratio, Some(VarOr::Value(1.5)));
+        assert_eq!(
+            activity.config_changes.vec(),
+            &vec![crate::ConfigChanges::Orientation]
+        );
+        assert_eq!(activity.config_changes.vars(), ["${configChanges}"]);
+        assert_eq!(activity.window_soft_input_mode.vars(), ["${softInputMode}"]);
+        assert_eq!(
+            manifest.permission[0].protection_level,
+            Some(VarOr::var("${protectionLevel}"))
+        );
+        assert_eq!(
+            manifest.uses_permission[0].uses_permission_flags.vars(),
+            ["${permissionFlags}"]
+        );
+        assert_eq!(
+            manifest
+                .uses_configuration
+                .as_ref()
+                .unwrap()
+                .req_touch_screen,
+            Some(VarOr::var("${touchScreen}"))
+        );
+        let serialized = crate::to_string(&manifest).unwrap();
+        for placeholder in [
+            r#"android:installLocation="${installLocation}""#,
+            r#"android:screenOrientation="${orientation}""#,
+            r#"android:documentLaunchMode="${documentLaunchMode}""#,
+            r#"android:gwpAsanMode="${gwpAsan}""#,
+            r#"android:name="${texture}""#,
+            r#"android:configChanges="orientation|${configChanges}""#,
+            r#"android:windowSoftInputMode="${softInputMode}""#,
+            r#"android:protectionLevel="${protectionLevel}""#,
+            r#"android:usesPermissionFlags="${permissionFlags}""#,
+        ] {
+            assert!(serialized.contains(placeholder), "{}", placeholder);
+        }
+        assert_eq!(crate::from_str(&serialized).unwrap(), manifest);
+
+        let values = HashMap::from([
+            ("installLocation".to_string(), "preferExternal".to_string()),
+            ("orientation".to_string(), "sensorLandscape".to_string()),
+            ("maxAspectRatio".to_string(), "2.4".to_string()),
+            ("configChanges".to_string(), "locale".to_string()),
+            ("protectionLevel".to_string(), "signature".to_string()),
+        ]);
+        resolve_placeholders(&mut manifest, &values).unwrap();
+        let activity = &manifest.application.activity[0];
+        assert_eq!(
+            manifest.install_location,
+            Some(VarOr::Value(InstallLocation::PreferExternal))
+        );
+        assert_eq!(
+            activity.screen_orientation,
+            Some(VarOr::Value(ScreenOrientation::SensorLandscape))
+        );
+        assert_eq!(activity.max_aspect_ratio, Some(VarOr::Value(2.4)));
+        assert_eq!(
+            activity.config_changes.vec(),
+            &vec![
+                crate::ConfigChanges::Orientation,
+                crate::ConfigChanges::Locale
+            ]
+        );
+        assert!(activity.config_changes.vars().is_empty());
+        assert_eq!(
+            manifest.permission[0].protection_level,
+            Some(VarOr::Value(crate::BaseProtectionLevel::Signature.into()))
+        );
+        assert_eq!(
+            activity.document_launch_mode,
+            Some(VarOr::var("${documentLaunchMode}"))
+        );
+    }
+}
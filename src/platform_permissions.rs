@@ -0,0 +1,1963 @@
+//! Catalog of the permissions that the Android platform declares and apps request,
+//! with their protection level, the API levels that added and deprecated them, and the
+//! restrictions on runtime permissions, as of Android 16 (API level 36).
+
+use crate::{
+    BaseProtectionLevel, ProtectionFlag, ProtectionLevel, UsesPermission, UsesPermissionSdk23,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use BaseProtectionLevel::{Dangerous, Normal, Signature};
+
+const PREFIX: &str = "android.permission.";
+
+const ACTIVITY_RECOGNITION: &str = "android.permission-group.ACTIVITY_RECOGNITION";
+const CALENDAR: &str = "android.permission-group.CALENDAR";
+const CALL_LOG: &str = "android.permission-group.CALL_LOG";
+const CAMERA: &str = "android.permission-group.CAMERA";
+const CONTACTS: &str = "android.permission-group.CONTACTS";
+const LOCATION: &str = "android.permission-group.LOCATION";
+const MICROPHONE: &str = "android.permission-group.MICROPHONE";
+const NEARBY_DEVICES: &str = "android.permission-group.NEARBY_DEVICES";
+const NOTIFICATIONS: &str = "android.permission-group.NOTIFICATIONS";
+const PHONE: &str = "android.permission-group.PHONE";
+const READ_MEDIA_AURAL: &str = "android.permission-group.READ_MEDIA_AURAL";
+const READ_MEDIA_VISUAL: &str = "android.permission-group.READ_MEDIA_VISUAL";
+const SENSORS: &str = "android.permission-group.SENSORS";
+const SMS: &str = "android.permission-group.SMS";
+const STORAGE: &str = "android.permission-group.STORAGE";
+
+/// How Android restricts a runtime permission until the installer allowlists it for the
+/// app, like Google Play does for the apps that meet its policies.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum Restriction {
+    /// The app can't be granted the permission.
+    Hard,
+    /// The app can be granted the permission, with a reduced effect, like storage access
+    /// limited to its own files.
+    Soft,
+}
+
+/// A permission declared by the Android platform.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PlatformPermission {
+    pub permission: AndroidPermission,
+    /// Name, like `android.permission.CAMERA`.
+    pub name: &'static str,
+    pub base_protection_level: BaseProtectionLevel,
+    pub protection_flags: &'static [ProtectionFlag],
+    /// API level that added the permission.
+    pub added_in: u32,
+    /// API level that deprecated or removed the permission.
+    pub deprecated_in: Option<u32>,
+    /// Group of a runtime permission, like `android.permission-group.LOCATION`, that
+    /// the permission settings show it in.
+    pub group: Option<&'static str>,
+    pub restriction: Option<Restriction>,
+}
+
+impl PlatformPermission {
+    pub fn protection_level(&self) -> ProtectionLevel {
+        ProtectionLevel {
+            base: self.base_protection_level,
+            flags: self.protection_flags.to_vec(),
+        }
+    }
+
+    /// Whether the user grants the permission while the app runs.
+    pub fn is_runtime(&self) -> bool {
+        self.base_protection_level == Dangerous
+    }
+
+    /// Whether the permission is deprecated or removed as of API level `api_level`.
+    pub fn is_deprecated_in(&self, api_level: u32) -> bool {
+        self.deprecated_in
+            .is_some_and(|deprecated_in| deprecated_in <= api_level)
+    }
+
+    const fn deprecated_in(mut self, api_level: u32) -> Self {
+        self.deprecated_in = Some(api_level);
+        self
+    }
+
+    const fn group(mut self, group: &'static str) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    const fn restriction(mut self, restriction: Restriction) -> Self {
+        self.restriction = Some(restriction);
+        self
+    }
+}
+
+/// A permission of the [catalog](platform_permissions) of platform permissions, written
+/// as its name, like `android.permission.CAMERA`.
+///
+/// ```
+/// # use android_manifest::*;
+/// let permission: AndroidPermission = "android.permission.ACCESS_FINE_LOCATION".parse().unwrap();
+/// assert_eq!(permission, AndroidPermission::AccessFineLocation);
+/// assert!(permission.info().is_runtime());
+/// assert_eq!(permission.info().group, Some("android.permission-group.LOCATION"));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum AndroidPermission {
+    AcceptHandover,
+    AccessAdservicesAdId,
+    AccessAdservicesAttribution,
+    AccessAdservicesCustomAudience,
+    AccessAdservicesTopics,
+    AccessBackgroundLocation,
+    AccessCheckinProperties,
+    AccessCoarseLocation,
+    AccessFineLocation,
+    AccessLocationExtraCommands,
+    AccessMediaLocation,
+    AccessNetworkState,
+    AccessNotificationPolicy,
+    AccessWifiState,
+    AccountManager,
+    ActivityRecognition,
+    AnswerPhoneCalls,
+    AuthenticateAccounts,
+    BatteryStats,
+    BindAccessibilityService,
+    BindAppwidget,
+    BindAutofillService,
+    BindCallRedirectionService,
+    BindCarrierMessagingClientService,
+    BindCarrierMessagingService,
+    BindCarrierServices,
+    BindChooserTargetService,
+    BindCompanionDeviceService,
+    BindConditionProviderService,
+    BindCredentialProviderService,
+    BindDeviceAdmin,
+    BindDreamService,
+    BindIncallService,
+    BindInputMethod,
+    BindJobService,
+    BindMidiDeviceService,
+    BindNfcService,
+    BindNotificationListenerService,
+    BindPrintService,
+    BindQuickAccessWalletService,
+    BindQuickSettingsTile,
+    BindRemoteviews,
+    BindScreeningService,
+    BindTelecomConnectionService,
+    BindTextService,
+    BindTvInput,
+    BindVisualVoicemailService,
+    BindVoiceInteraction,
+    BindVpnService,
+    BindWallpaper,
+    Bluetooth,
+    BluetoothAdmin,
+    BluetoothAdvertise,
+    BluetoothConnect,
+    BluetoothPrivileged,
+    BluetoothScan,
+    BodySensors,
+    BodySensorsBackground,
+    BroadcastPackageRemoved,
+    BroadcastSms,
+    BroadcastSticky,
+    BroadcastWapPush,
+    CallCompanionApp,
+    CallPhone,
+    CallPrivileged,
+    Camera,
+    CaptureAudioOutput,
+    ChangeConfiguration,
+    ChangeNetworkState,
+    ChangeWifiMulticastState,
+    ChangeWifiState,
+    ClearAppCache,
+    CredentialManagerSetAllowedProviders,
+    CredentialManagerSetOrigin,
+    DeletePackages,
+    DeliverCompanionMessages,
+    DetectScreenCapture,
+    DetectScreenRecording,
+    DisableKeyguard,
+    Dump,
+    EnforceUpdateOwnership,
+    ExpandStatusBar,
+    FactoryTest,
+    ForegroundService,
+    ForegroundServiceCamera,
+    ForegroundServiceConnectedDevice,
+    ForegroundServiceDataSync,
+    ForegroundServiceHealth,
+    ForegroundServiceLocation,
+    ForegroundServiceMediaPlayback,
+    ForegroundServiceMediaProcessing,
+    ForegroundServiceMediaProjection,
+    ForegroundServiceMicrophone,
+    ForegroundServicePhoneCall,
+    ForegroundServiceRemoteMessaging,
+    ForegroundServiceSpecialUse,
+    ForegroundServiceSystemExempted,
+    GetAccounts,
+    GetPackageSize,
+    GetTasks,
+    GlobalSearch,
+    HideOverlayWindows,
+    HighSamplingRateSensors,
+    InstallLocationProvider,
+    InstallPackages,
+    InteractAcrossProfiles,
+    Internet,
+    KillBackgroundProcesses,
+    LoaderUsageStats,
+    LocationHardware,
+    ManageAccounts,
+    ManageDocuments,
+    ManageExternalStorage,
+    ManageMedia,
+    ManageOngoingCalls,
+    ManageOwnCalls,
+    MasterClear,
+    MediaContentControl,
+    ModifyAudioSettings,
+    ModifyPhoneState,
+    NearbyWifiDevices,
+    Nfc,
+    NfcPreferredPaymentInfo,
+    NfcTransactionEvent,
+    PackageUsageStats,
+    PersistentActivity,
+    PostNotifications,
+    ProcessOutgoingCalls,
+    QueryAllPackages,
+    ReadBasicPhoneState,
+    ReadCalendar,
+    ReadCallLog,
+    ReadContacts,
+    ReadExternalStorage,
+    ReadLogs,
+    ReadMediaAudio,
+    ReadMediaImages,
+    ReadMediaVideo,
+    ReadMediaVisualUserSelected,
+    ReadPhoneNumbers,
+    ReadPhoneState,
+    ReadSms,
+    ReadSyncSettings,
+    ReadSyncStats,
+    ReadVoicemail,
+    Reboot,
+    ReceiveBootCompleted,
+    ReceiveMms,
+    ReceiveSms,
+    ReceiveWapPush,
+    RecordAudio,
+    ReorderTasks,
+    RequestCompanionProfileWatch,
+    RequestCompanionRunInBackground,
+    RequestCompanionUseDataInBackground,
+    RequestDeletePackages,
+    RequestIgnoreBatteryOptimizations,
+    RequestInstallPackages,
+    RequestObserveCompanionDevicePresence,
+    RequestPasswordComplexity,
+    RestartPackages,
+    RunUserInitiatedJobs,
+    ScheduleExactAlarm,
+    SendRespondViaMessage,
+    SendSms,
+    SetTime,
+    SetTimeZone,
+    SetWallpaper,
+    SetWallpaperHints,
+    SmsFinancialTransactions,
+    StartViewAppFeatures,
+    StartViewPermissionUsage,
+    StatusBar,
+    SystemAlertWindow,
+    TransmitIr,
+    UpdateDeviceStats,
+    UpdatePackagesWithoutUserAction,
+    UseBiometric,
+    UseCredentials,
+    UseExactAlarm,
+    UseFingerprint,
+    UseFullScreenIntent,
+    UseIccAuthWithDeviceIdentifier,
+    UseSip,
+    UwbRanging,
+    Vibrate,
+    WakeLock,
+    WriteApnSettings,
+    WriteCalendar,
+    WriteCallLog,
+    WriteContacts,
+    WriteExternalStorage,
+    WriteSecureSettings,
+    WriteSettings,
+    WriteSyncSettings,
+    WriteVoicemail,
+}
+
+impl AndroidPermission {
+    /// Returns the permission named `name`, if the catalog has it.
+    pub fn from_name(name: &str) -> Option<Self> {
+        platform_permission(name).map(|info| info.permission)
+    }
+
+    pub fn info(self) -> &'static PlatformPermission {
+        &PLATFORM_PERMISSIONS[self as usize]
+    }
+
+    /// Name, like `android.permission.CAMERA`.
+    pub fn as_str(self) -> &'static str {
+        self.info().name
+    }
+}
+
+impl fmt::Display for AndroidPermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AndroidPermission {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::from_name(name).ok_or_else(|| format!("unknown platform permission `{}`", name))
+    }
+}
+
+impl Serialize for AndroidPermission {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for AndroidPermission {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl UsesPermission {
+    /// Returns what the platform declares about the requested permission, or `None` if
+    /// it isn't a platform permission of the catalog.
+    ///
+    /// ```
+    /// # use android_manifest::*;
+    /// let uses_permission = UsesPermission {
+    ///     name: Some("android.permission.READ_SMS".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let info = uses_permission.platform_info().unwrap();
+    /// assert_eq!(info.restriction, Some(Restriction::Hard));
+    /// ```
+    pub fn platform_info(&self) -> Option<&'static PlatformPermission> {
+        self.name.as_deref().and_then(platform_permission)
+    }
+}
+
+impl UsesPermissionSdk23 {
+    /// Returns what the platform declares about the requested permission, or `None` if
+    /// it isn't a platform permission of the catalog.
+    pub fn platform_info(&self) -> Option<&'static PlatformPermission> {
+        self.name.as_deref().and_then(platform_permission)
+    }
+}
+
+/// Returns the catalog of platform permissions, sorted by name.
+///
+/// The catalog covers the permissions that apps commonly request: the runtime ones,
+/// the normal ones of the SDK and of the Privacy Sandbox, and the signature ones that
+/// guard APIs of the SDK. It isn't exhaustive, a name missing from it can still be a
+/// platform permission, like the ones reserved to system apps.
+pub fn platform_permissions() -> &'static [PlatformPermission] {
+    PLATFORM_PERMISSIONS
+}
+
+/// Returns the platform permission named `name`, if the catalog has it.
+pub fn platform_permission(name: &str) -> Option<&'static PlatformPermission> {
+    PLATFORM_PERMISSIONS
+        .binary_search_by(|permission| permission.name.cmp(name))
+        .ok()
+        .map(|index| &PLATFORM_PERMISSIONS[index])
+}
+
+/// Returns the platform permission that `name` is likely a typo of: an unknown
+/// `android.permission.` name at most two edits away from a known one.
+pub(crate) fn misspelled_platform_permission(name: &str) -> Option<&'static PlatformPermission> {
+    let suffix = name.strip_prefix(PREFIX)?;
+    if platform_permission(name).is_some() {
+        return None;
+    }
+    PLATFORM_PERMISSIONS
+        .iter()
+        .map(|permission| {
+            let known = &permission.name[PREFIX.len()..];
+            (edit_distance(suffix, known), permission)
+        })
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, permission)| permission)
+}
+
+/// Levenshtein distance, counting the insertions, deletions and substitutions of
+/// characters that turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a != *b);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
+
+const fn permission(
+    permission: AndroidPermission,
+    name: &'static str,
+    base_protection_level: BaseProtectionLevel,
+    protection_flags: &'static [ProtectionFlag],
+    added_in: u32,
+) -> PlatformPermission {
+    PlatformPermission {
+        permission,
+        name,
+        base_protection_level,
+        protection_flags,
+        added_in,
+        deprecated_in: None,
+        group: None,
+        restriction: None,
+    }
+}
+
+/// Sorted by name, in the order of the variants of [`AndroidPermission`].
+const PLATFORM_PERMISSIONS: &[PlatformPermission] = &[
+    permission(
+        AndroidPermission::AcceptHandover,
+        "android.permission.ACCEPT_HANDOVER",
+        Dangerous,
+        &[],
+        28,
+    )
+    .group(PHONE),
+    permission(
+        AndroidPermission::AccessAdservicesAdId,
+        "android.permission.ACCESS_ADSERVICES_AD_ID",
+        Normal,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::AccessAdservicesAttribution,
+        "android.permission.ACCESS_ADSERVICES_ATTRIBUTION",
+        Normal,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::AccessAdservicesCustomAudience,
+        "android.permission.ACCESS_ADSERVICES_CUSTOM_AUDIENCE",
+        Normal,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::AccessAdservicesTopics,
+        "android.permission.ACCESS_ADSERVICES_TOPICS",
+        Normal,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::AccessBackgroundLocation,
+        "android.permission.ACCESS_BACKGROUND_LOCATION",
+        Dangerous,
+        &[ProtectionFlag::Instant],
+        29,
+    )
+    .group(LOCATION)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::AccessCheckinProperties,
+        "android.permission.ACCESS_CHECKIN_PROPERTIES",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::AccessCoarseLocation,
+        "android.permission.ACCESS_COARSE_LOCATION",
+        Dangerous,
+        &[ProtectionFlag::Instant],
+        1,
+    )
+    .group(LOCATION),
+    permission(
+        AndroidPermission::AccessFineLocation,
+        "android.permission.ACCESS_FINE_LOCATION",
+        Dangerous,
+        &[ProtectionFlag::Instant],
+        1,
+    )
+    .group(LOCATION),
+    permission(
+        AndroidPermission::AccessLocationExtraCommands,
+        "android.permission.ACCESS_LOCATION_EXTRA_COMMANDS",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::AccessMediaLocation,
+        "android.permission.ACCESS_MEDIA_LOCATION",
+        Dangerous,
+        &[],
+        29,
+    )
+    .group(READ_MEDIA_VISUAL),
+    permission(
+        AndroidPermission::AccessNetworkState,
+        "android.permission.ACCESS_NETWORK_STATE",
+        Normal,
+        &[ProtectionFlag::Instant],
+        1,
+    ),
+    permission(
+        AndroidPermission::AccessNotificationPolicy,
+        "android.permission.ACCESS_NOTIFICATION_POLICY",
+        Normal,
+        &[],
+        23,
+    ),
+    permission(
+        AndroidPermission::AccessWifiState,
+        "android.permission.ACCESS_WIFI_STATE",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::AccountManager,
+        "android.permission.ACCOUNT_MANAGER",
+        Signature,
+        &[],
+        5,
+    ),
+    permission(
+        AndroidPermission::ActivityRecognition,
+        "android.permission.ACTIVITY_RECOGNITION",
+        Dangerous,
+        &[ProtectionFlag::Instant],
+        29,
+    )
+    .group(ACTIVITY_RECOGNITION),
+    permission(
+        AndroidPermission::AnswerPhoneCalls,
+        "android.permission.ANSWER_PHONE_CALLS",
+        Dangerous,
+        &[ProtectionFlag::Runtime],
+        26,
+    )
+    .group(PHONE),
+    permission(
+        AndroidPermission::AuthenticateAccounts,
+        "android.permission.AUTHENTICATE_ACCOUNTS",
+        Dangerous,
+        &[],
+        5,
+    )
+    .deprecated_in(23),
+    permission(
+        AndroidPermission::BatteryStats,
+        "android.permission.BATTERY_STATS",
+        Signature,
+        &[ProtectionFlag::Privileged, ProtectionFlag::Development],
+        1,
+    ),
+    permission(
+        AndroidPermission::BindAccessibilityService,
+        "android.permission.BIND_ACCESSIBILITY_SERVICE",
+        Signature,
+        &[],
+        16,
+    ),
+    permission(
+        AndroidPermission::BindAppwidget,
+        "android.permission.BIND_APPWIDGET",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        3,
+    ),
+    permission(
+        AndroidPermission::BindAutofillService,
+        "android.permission.BIND_AUTOFILL_SERVICE",
+        Signature,
+        &[],
+        26,
+    ),
+    permission(
+        AndroidPermission::BindCallRedirectionService,
+        "android.permission.BIND_CALL_REDIRECTION_SERVICE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        29,
+    ),
+    permission(
+        AndroidPermission::BindCarrierMessagingClientService,
+        "android.permission.BIND_CARRIER_MESSAGING_CLIENT_SERVICE",
+        Signature,
+        &[],
+        29,
+    ),
+    permission(
+        AndroidPermission::BindCarrierMessagingService,
+        "android.permission.BIND_CARRIER_MESSAGING_SERVICE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        22,
+    )
+    .deprecated_in(23),
+    permission(
+        AndroidPermission::BindCarrierServices,
+        "android.permission.BIND_CARRIER_SERVICES",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        23,
+    ),
+    permission(
+        AndroidPermission::BindChooserTargetService,
+        "android.permission.BIND_CHOOSER_TARGET_SERVICE",
+        Signature,
+        &[],
+        23,
+    )
+    .deprecated_in(30),
+    permission(
+        AndroidPermission::BindCompanionDeviceService,
+        "android.permission.BIND_COMPANION_DEVICE_SERVICE",
+        Signature,
+        &[],
+        31,
+    ),
+    permission(
+        AndroidPermission::BindConditionProviderService,
+        "android.permission.BIND_CONDITION_PROVIDER_SERVICE",
+        Signature,
+        &[],
+        24,
+    ),
+    permission(
+        AndroidPermission::BindCredentialProviderService,
+        "android.permission.BIND_CREDENTIAL_PROVIDER_SERVICE",
+        Signature,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::BindDeviceAdmin,
+        "android.permission.BIND_DEVICE_ADMIN",
+        Signature,
+        &[],
+        8,
+    ),
+    permission(
+        AndroidPermission::BindDreamService,
+        "android.permission.BIND_DREAM_SERVICE",
+        Signature,
+        &[],
+        21,
+    ),
+    permission(
+        AndroidPermission::BindIncallService,
+        "android.permission.BIND_INCALL_SERVICE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        23,
+    ),
+    permission(
+        AndroidPermission::BindInputMethod,
+        "android.permission.BIND_INPUT_METHOD",
+        Signature,
+        &[],
+        3,
+    ),
+    permission(
+        AndroidPermission::BindJobService,
+        "android.permission.BIND_JOB_SERVICE",
+        Signature,
+        &[],
+        21,
+    ),
+    permission(
+        AndroidPermission::BindMidiDeviceService,
+        "android.permission.BIND_MIDI_DEVICE_SERVICE",
+        Signature,
+        &[],
+        23,
+    ),
+    permission(
+        AndroidPermission::BindNfcService,
+        "android.permission.BIND_NFC_SERVICE",
+        Signature,
+        &[],
+        19,
+    ),
+    permission(
+        AndroidPermission::BindNotificationListenerService,
+        "android.permission.BIND_NOTIFICATION_LISTENER_SERVICE",
+        Signature,
+        &[],
+        18,
+    ),
+    permission(
+        AndroidPermission::BindPrintService,
+        "android.permission.BIND_PRINT_SERVICE",
+        Signature,
+        &[],
+        19,
+    ),
+    permission(
+        AndroidPermission::BindQuickAccessWalletService,
+        "android.permission.BIND_QUICK_ACCESS_WALLET_SERVICE",
+        Signature,
+        &[],
+        30,
+    ),
+    permission(
+        AndroidPermission::BindQuickSettingsTile,
+        "android.permission.BIND_QUICK_SETTINGS_TILE",
+        Signature,
+        &[],
+        24,
+    ),
+    permission(
+        AndroidPermission::BindRemoteviews,
+        "android.permission.BIND_REMOTEVIEWS",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        11,
+    ),
+    permission(
+        AndroidPermission::BindScreeningService,
+        "android.permission.BIND_SCREENING_SERVICE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        24,
+    ),
+    permission(
+        AndroidPermission::BindTelecomConnectionService,
+        "android.permission.BIND_TELECOM_CONNECTION_SERVICE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        23,
+    ),
+    permission(
+        AndroidPermission::BindTextService,
+        "android.permission.BIND_TEXT_SERVICE",
+        Signature,
+        &[],
+        14,
+    ),
+    permission(
+        AndroidPermission::BindTvInput,
+        "android.permission.BIND_TV_INPUT",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        21,
+    ),
+    permission(
+        AndroidPermission::BindVisualVoicemailService,
+        "android.permission.BIND_VISUAL_VOICEMAIL_SERVICE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        26,
+    ),
+    permission(
+        AndroidPermission::BindVoiceInteraction,
+        "android.permission.BIND_VOICE_INTERACTION",
+        Signature,
+        &[],
+        21,
+    ),
+    permission(
+        AndroidPermission::BindVpnService,
+        "android.permission.BIND_VPN_SERVICE",
+        Signature,
+        &[],
+        14,
+    ),
+    permission(
+        AndroidPermission::BindWallpaper,
+        "android.permission.BIND_WALLPAPER",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        8,
+    ),
+    permission(
+        AndroidPermission::Bluetooth,
+        "android.permission.BLUETOOTH",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::BluetoothAdmin,
+        "android.permission.BLUETOOTH_ADMIN",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::BluetoothAdvertise,
+        "android.permission.BLUETOOTH_ADVERTISE",
+        Dangerous,
+        &[],
+        31,
+    )
+    .group(NEARBY_DEVICES),
+    permission(
+        AndroidPermission::BluetoothConnect,
+        "android.permission.BLUETOOTH_CONNECT",
+        Dangerous,
+        &[],
+        31,
+    )
+    .group(NEARBY_DEVICES),
+    permission(
+        AndroidPermission::BluetoothPrivileged,
+        "android.permission.BLUETOOTH_PRIVILEGED",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        19,
+    ),
+    permission(
+        AndroidPermission::BluetoothScan,
+        "android.permission.BLUETOOTH_SCAN",
+        Dangerous,
+        &[],
+        31,
+    )
+    .group(NEARBY_DEVICES),
+    permission(
+        AndroidPermission::BodySensors,
+        "android.permission.BODY_SENSORS",
+        Dangerous,
+        &[],
+        20,
+    )
+    .group(SENSORS),
+    permission(
+        AndroidPermission::BodySensorsBackground,
+        "android.permission.BODY_SENSORS_BACKGROUND",
+        Dangerous,
+        &[],
+        33,
+    )
+    .group(SENSORS)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::BroadcastPackageRemoved,
+        "android.permission.BROADCAST_PACKAGE_REMOVED",
+        Signature,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::BroadcastSms,
+        "android.permission.BROADCAST_SMS",
+        Signature,
+        &[],
+        2,
+    ),
+    permission(
+        AndroidPermission::BroadcastSticky,
+        "android.permission.BROADCAST_STICKY",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::BroadcastWapPush,
+        "android.permission.BROADCAST_WAP_PUSH",
+        Signature,
+        &[],
+        2,
+    ),
+    permission(
+        AndroidPermission::CallCompanionApp,
+        "android.permission.CALL_COMPANION_APP",
+        Normal,
+        &[],
+        29,
+    ),
+    permission(
+        AndroidPermission::CallPhone,
+        "android.permission.CALL_PHONE",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(PHONE),
+    permission(
+        AndroidPermission::CallPrivileged,
+        "android.permission.CALL_PRIVILEGED",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::Camera,
+        "android.permission.CAMERA",
+        Dangerous,
+        &[ProtectionFlag::Instant],
+        1,
+    )
+    .group(CAMERA),
+    permission(
+        AndroidPermission::CaptureAudioOutput,
+        "android.permission.CAPTURE_AUDIO_OUTPUT",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        19,
+    ),
+    permission(
+        AndroidPermission::ChangeConfiguration,
+        "android.permission.CHANGE_CONFIGURATION",
+        Signature,
+        &[ProtectionFlag::Privileged, ProtectionFlag::Development],
+        1,
+    ),
+    permission(
+        AndroidPermission::ChangeNetworkState,
+        "android.permission.CHANGE_NETWORK_STATE",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::ChangeWifiMulticastState,
+        "android.permission.CHANGE_WIFI_MULTICAST_STATE",
+        Normal,
+        &[],
+        4,
+    ),
+    permission(
+        AndroidPermission::ChangeWifiState,
+        "android.permission.CHANGE_WIFI_STATE",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::ClearAppCache,
+        "android.permission.CLEAR_APP_CACHE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::CredentialManagerSetAllowedProviders,
+        "android.permission.CREDENTIAL_MANAGER_SET_ALLOWED_PROVIDERS",
+        Normal,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::CredentialManagerSetOrigin,
+        "android.permission.CREDENTIAL_MANAGER_SET_ORIGIN",
+        Normal,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::DeletePackages,
+        "android.permission.DELETE_PACKAGES",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::DeliverCompanionMessages,
+        "android.permission.DELIVER_COMPANION_MESSAGES",
+        Normal,
+        &[],
+        33,
+    ),
+    permission(
+        AndroidPermission::DetectScreenCapture,
+        "android.permission.DETECT_SCREEN_CAPTURE",
+        Normal,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::DetectScreenRecording,
+        "android.permission.DETECT_SCREEN_RECORDING",
+        Normal,
+        &[],
+        35,
+    ),
+    permission(
+        AndroidPermission::DisableKeyguard,
+        "android.permission.DISABLE_KEYGUARD",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::Dump,
+        "android.permission.DUMP",
+        Signature,
+        &[ProtectionFlag::Privileged, ProtectionFlag::Development],
+        1,
+    ),
+    permission(
+        AndroidPermission::EnforceUpdateOwnership,
+        "android.permission.ENFORCE_UPDATE_OWNERSHIP",
+        Normal,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::ExpandStatusBar,
+        "android.permission.EXPAND_STATUS_BAR",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::FactoryTest,
+        "android.permission.FACTORY_TEST",
+        Signature,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::ForegroundService,
+        "android.permission.FOREGROUND_SERVICE",
+        Normal,
+        &[ProtectionFlag::Instant],
+        28,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceCamera,
+        "android.permission.FOREGROUND_SERVICE_CAMERA",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceConnectedDevice,
+        "android.permission.FOREGROUND_SERVICE_CONNECTED_DEVICE",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceDataSync,
+        "android.permission.FOREGROUND_SERVICE_DATA_SYNC",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceHealth,
+        "android.permission.FOREGROUND_SERVICE_HEALTH",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceLocation,
+        "android.permission.FOREGROUND_SERVICE_LOCATION",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceMediaPlayback,
+        "android.permission.FOREGROUND_SERVICE_MEDIA_PLAYBACK",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceMediaProcessing,
+        "android.permission.FOREGROUND_SERVICE_MEDIA_PROCESSING",
+        Normal,
+        &[ProtectionFlag::Instant],
+        35,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceMediaProjection,
+        "android.permission.FOREGROUND_SERVICE_MEDIA_PROJECTION",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceMicrophone,
+        "android.permission.FOREGROUND_SERVICE_MICROPHONE",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServicePhoneCall,
+        "android.permission.FOREGROUND_SERVICE_PHONE_CALL",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceRemoteMessaging,
+        "android.permission.FOREGROUND_SERVICE_REMOTE_MESSAGING",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceSpecialUse,
+        "android.permission.FOREGROUND_SERVICE_SPECIAL_USE",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::ForegroundServiceSystemExempted,
+        "android.permission.FOREGROUND_SERVICE_SYSTEM_EXEMPTED",
+        Normal,
+        &[ProtectionFlag::Instant],
+        34,
+    ),
+    permission(
+        AndroidPermission::GetAccounts,
+        "android.permission.GET_ACCOUNTS",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(CONTACTS),
+    permission(
+        AndroidPermission::GetPackageSize,
+        "android.permission.GET_PACKAGE_SIZE",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::GetTasks,
+        "android.permission.GET_TASKS",
+        Normal,
+        &[],
+        1,
+    )
+    .deprecated_in(21),
+    permission(
+        AndroidPermission::GlobalSearch,
+        "android.permission.GLOBAL_SEARCH",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        4,
+    ),
+    permission(
+        AndroidPermission::HideOverlayWindows,
+        "android.permission.HIDE_OVERLAY_WINDOWS",
+        Normal,
+        &[],
+        31,
+    ),
+    permission(
+        AndroidPermission::HighSamplingRateSensors,
+        "android.permission.HIGH_SAMPLING_RATE_SENSORS",
+        Normal,
+        &[],
+        31,
+    ),
+    permission(
+        AndroidPermission::InstallLocationProvider,
+        "android.permission.INSTALL_LOCATION_PROVIDER",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        4,
+    ),
+    permission(
+        AndroidPermission::InstallPackages,
+        "android.permission.INSTALL_PACKAGES",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::InteractAcrossProfiles,
+        "android.permission.INTERACT_ACROSS_PROFILES",
+        Signature,
+        &[ProtectionFlag::Appop],
+        30,
+    ),
+    permission(
+        AndroidPermission::Internet,
+        "android.permission.INTERNET",
+        Normal,
+        &[ProtectionFlag::Instant],
+        1,
+    ),
+    permission(
+        AndroidPermission::KillBackgroundProcesses,
+        "android.permission.KILL_BACKGROUND_PROCESSES",
+        Normal,
+        &[],
+        8,
+    ),
+    permission(
+        AndroidPermission::LoaderUsageStats,
+        "android.permission.LOADER_USAGE_STATS",
+        Signature,
+        &[ProtectionFlag::Privileged, ProtectionFlag::Appop],
+        30,
+    ),
+    permission(
+        AndroidPermission::LocationHardware,
+        "android.permission.LOCATION_HARDWARE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        18,
+    ),
+    permission(
+        AndroidPermission::ManageAccounts,
+        "android.permission.MANAGE_ACCOUNTS",
+        Dangerous,
+        &[],
+        5,
+    )
+    .deprecated_in(23),
+    permission(
+        AndroidPermission::ManageDocuments,
+        "android.permission.MANAGE_DOCUMENTS",
+        Signature,
+        &[],
+        19,
+    ),
+    permission(
+        AndroidPermission::ManageExternalStorage,
+        "android.permission.MANAGE_EXTERNAL_STORAGE",
+        Signature,
+        &[ProtectionFlag::Appop, ProtectionFlag::Preinstalled],
+        30,
+    ),
+    permission(
+        AndroidPermission::ManageMedia,
+        "android.permission.MANAGE_MEDIA",
+        Signature,
+        &[ProtectionFlag::Appop, ProtectionFlag::Preinstalled],
+        31,
+    ),
+    permission(
+        AndroidPermission::ManageOngoingCalls,
+        "android.permission.MANAGE_ONGOING_CALLS",
+        Signature,
+        &[ProtectionFlag::Appop],
+        33,
+    ),
+    permission(
+        AndroidPermission::ManageOwnCalls,
+        "android.permission.MANAGE_OWN_CALLS",
+        Normal,
+        &[],
+        26,
+    ),
+    permission(
+        AndroidPermission::MasterClear,
+        "android.permission.MASTER_CLEAR",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::MediaContentControl,
+        "android.permission.MEDIA_CONTENT_CONTROL",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        19,
+    ),
+    permission(
+        AndroidPermission::ModifyAudioSettings,
+        "android.permission.MODIFY_AUDIO_SETTINGS",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::ModifyPhoneState,
+        "android.permission.MODIFY_PHONE_STATE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::NearbyWifiDevices,
+        "android.permission.NEARBY_WIFI_DEVICES",
+        Dangerous,
+        &[],
+        33,
+    )
+    .group(NEARBY_DEVICES),
+    permission(
+        AndroidPermission::Nfc,
+        "android.permission.NFC",
+        Normal,
+        &[],
+        9,
+    ),
+    permission(
+        AndroidPermission::NfcPreferredPaymentInfo,
+        "android.permission.NFC_PREFERRED_PAYMENT_INFO",
+        Normal,
+        &[],
+        30,
+    ),
+    permission(
+        AndroidPermission::NfcTransactionEvent,
+        "android.permission.NFC_TRANSACTION_EVENT",
+        Normal,
+        &[],
+        28,
+    ),
+    permission(
+        AndroidPermission::PackageUsageStats,
+        "android.permission.PACKAGE_USAGE_STATS",
+        Signature,
+        &[
+            ProtectionFlag::Privileged,
+            ProtectionFlag::Development,
+            ProtectionFlag::Appop,
+            ProtectionFlag::RetailDemo,
+        ],
+        23,
+    ),
+    permission(
+        AndroidPermission::PersistentActivity,
+        "android.permission.PERSISTENT_ACTIVITY",
+        Normal,
+        &[],
+        1,
+    )
+    .deprecated_in(9),
+    permission(
+        AndroidPermission::PostNotifications,
+        "android.permission.POST_NOTIFICATIONS",
+        Dangerous,
+        &[],
+        33,
+    )
+    .group(NOTIFICATIONS),
+    permission(
+        AndroidPermission::ProcessOutgoingCalls,
+        "android.permission.PROCESS_OUTGOING_CALLS",
+        Dangerous,
+        &[],
+        1,
+    )
+    .deprecated_in(29)
+    .group(CALL_LOG)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::QueryAllPackages,
+        "android.permission.QUERY_ALL_PACKAGES",
+        Normal,
+        &[],
+        30,
+    ),
+    permission(
+        AndroidPermission::ReadBasicPhoneState,
+        "android.permission.READ_BASIC_PHONE_STATE",
+        Normal,
+        &[],
+        33,
+    ),
+    permission(
+        AndroidPermission::ReadCalendar,
+        "android.permission.READ_CALENDAR",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(CALENDAR),
+    permission(
+        AndroidPermission::ReadCallLog,
+        "android.permission.READ_CALL_LOG",
+        Dangerous,
+        &[],
+        16,
+    )
+    .group(CALL_LOG)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::ReadContacts,
+        "android.permission.READ_CONTACTS",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(CONTACTS),
+    permission(
+        AndroidPermission::ReadExternalStorage,
+        "android.permission.READ_EXTERNAL_STORAGE",
+        Dangerous,
+        &[],
+        16,
+    )
+    .group(STORAGE)
+    .restriction(Restriction::Soft),
+    permission(
+        AndroidPermission::ReadLogs,
+        "android.permission.READ_LOGS",
+        Signature,
+        &[ProtectionFlag::Privileged, ProtectionFlag::Development],
+        1,
+    ),
+    permission(
+        AndroidPermission::ReadMediaAudio,
+        "android.permission.READ_MEDIA_AUDIO",
+        Dangerous,
+        &[],
+        33,
+    )
+    .group(READ_MEDIA_AURAL),
+    permission(
+        AndroidPermission::ReadMediaImages,
+        "android.permission.READ_MEDIA_IMAGES",
+        Dangerous,
+        &[],
+        33,
+    )
+    .group(READ_MEDIA_VISUAL),
+    permission(
+        AndroidPermission::ReadMediaVideo,
+        "android.permission.READ_MEDIA_VIDEO",
+        Dangerous,
+        &[],
+        33,
+    )
+    .group(READ_MEDIA_VISUAL),
+    permission(
+        AndroidPermission::ReadMediaVisualUserSelected,
+        "android.permission.READ_MEDIA_VISUAL_USER_SELECTED",
+        Dangerous,
+        &[],
+        34,
+    )
+    .group(READ_MEDIA_VISUAL),
+    permission(
+        AndroidPermission::ReadPhoneNumbers,
+        "android.permission.READ_PHONE_NUMBERS",
+        Dangerous,
+        &[ProtectionFlag::Instant],
+        26,
+    )
+    .group(PHONE),
+    permission(
+        AndroidPermission::ReadPhoneState,
+        "android.permission.READ_PHONE_STATE",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(PHONE),
+    permission(
+        AndroidPermission::ReadSms,
+        "android.permission.READ_SMS",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(SMS)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::ReadSyncSettings,
+        "android.permission.READ_SYNC_SETTINGS",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::ReadSyncStats,
+        "android.permission.READ_SYNC_STATS",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::ReadVoicemail,
+        "android.permission.READ_VOICEMAIL",
+        Signature,
+        &[ProtectionFlag::Privileged, ProtectionFlag::Role],
+        14,
+    ),
+    permission(
+        AndroidPermission::Reboot,
+        "android.permission.REBOOT",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::ReceiveBootCompleted,
+        "android.permission.RECEIVE_BOOT_COMPLETED",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::ReceiveMms,
+        "android.permission.RECEIVE_MMS",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(SMS)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::ReceiveSms,
+        "android.permission.RECEIVE_SMS",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(SMS)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::ReceiveWapPush,
+        "android.permission.RECEIVE_WAP_PUSH",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(SMS)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::RecordAudio,
+        "android.permission.RECORD_AUDIO",
+        Dangerous,
+        &[ProtectionFlag::Instant],
+        1,
+    )
+    .group(MICROPHONE),
+    permission(
+        AndroidPermission::ReorderTasks,
+        "android.permission.REORDER_TASKS",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::RequestCompanionProfileWatch,
+        "android.permission.REQUEST_COMPANION_PROFILE_WATCH",
+        Normal,
+        &[],
+        31,
+    ),
+    permission(
+        AndroidPermission::RequestCompanionRunInBackground,
+        "android.permission.REQUEST_COMPANION_RUN_IN_BACKGROUND",
+        Normal,
+        &[],
+        26,
+    ),
+    permission(
+        AndroidPermission::RequestCompanionUseDataInBackground,
+        "android.permission.REQUEST_COMPANION_USE_DATA_IN_BACKGROUND",
+        Normal,
+        &[],
+        26,
+    ),
+    permission(
+        AndroidPermission::RequestDeletePackages,
+        "android.permission.REQUEST_DELETE_PACKAGES",
+        Normal,
+        &[],
+        28,
+    ),
+    permission(
+        AndroidPermission::RequestIgnoreBatteryOptimizations,
+        "android.permission.REQUEST_IGNORE_BATTERY_OPTIMIZATIONS",
+        Normal,
+        &[],
+        23,
+    ),
+    permission(
+        AndroidPermission::RequestInstallPackages,
+        "android.permission.REQUEST_INSTALL_PACKAGES",
+        Signature,
+        &[ProtectionFlag::Appop],
+        23,
+    ),
+    permission(
+        AndroidPermission::RequestObserveCompanionDevicePresence,
+        "android.permission.REQUEST_OBSERVE_COMPANION_DEVICE_PRESENCE",
+        Normal,
+        &[],
+        31,
+    ),
+    permission(
+        AndroidPermission::RequestPasswordComplexity,
+        "android.permission.REQUEST_PASSWORD_COMPLEXITY",
+        Normal,
+        &[],
+        29,
+    ),
+    permission(
+        AndroidPermission::RestartPackages,
+        "android.permission.RESTART_PACKAGES",
+        Normal,
+        &[],
+        1,
+    )
+    .deprecated_in(8),
+    permission(
+        AndroidPermission::RunUserInitiatedJobs,
+        "android.permission.RUN_USER_INITIATED_JOBS",
+        Normal,
+        &[],
+        34,
+    ),
+    permission(
+        AndroidPermission::ScheduleExactAlarm,
+        "android.permission.SCHEDULE_EXACT_ALARM",
+        Normal,
+        &[ProtectionFlag::Appop],
+        31,
+    ),
+    permission(
+        AndroidPermission::SendRespondViaMessage,
+        "android.permission.SEND_RESPOND_VIA_MESSAGE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        18,
+    ),
+    permission(
+        AndroidPermission::SendSms,
+        "android.permission.SEND_SMS",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(SMS)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::SetTime,
+        "android.permission.SET_TIME",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        8,
+    ),
+    permission(
+        AndroidPermission::SetTimeZone,
+        "android.permission.SET_TIME_ZONE",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::SetWallpaper,
+        "android.permission.SET_WALLPAPER",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::SetWallpaperHints,
+        "android.permission.SET_WALLPAPER_HINTS",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::SmsFinancialTransactions,
+        "android.permission.SMS_FINANCIAL_TRANSACTIONS",
+        Signature,
+        &[ProtectionFlag::Appop],
+        29,
+    ),
+    permission(
+        AndroidPermission::StartViewAppFeatures,
+        "android.permission.START_VIEW_APP_FEATURES",
+        Signature,
+        &[ProtectionFlag::Installer],
+        31,
+    ),
+    permission(
+        AndroidPermission::StartViewPermissionUsage,
+        "android.permission.START_VIEW_PERMISSION_USAGE",
+        Signature,
+        &[ProtectionFlag::Installer],
+        29,
+    ),
+    permission(
+        AndroidPermission::StatusBar,
+        "android.permission.STATUS_BAR",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::SystemAlertWindow,
+        "android.permission.SYSTEM_ALERT_WINDOW",
+        Signature,
+        &[
+            ProtectionFlag::Setup,
+            ProtectionFlag::Appop,
+            ProtectionFlag::Installer,
+            ProtectionFlag::Pre23,
+            ProtectionFlag::Development,
+        ],
+        1,
+    ),
+    permission(
+        AndroidPermission::TransmitIr,
+        "android.permission.TRANSMIT_IR",
+        Normal,
+        &[],
+        19,
+    ),
+    permission(
+        AndroidPermission::UpdateDeviceStats,
+        "android.permission.UPDATE_DEVICE_STATS",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        3,
+    ),
+    permission(
+        AndroidPermission::UpdatePackagesWithoutUserAction,
+        "android.permission.UPDATE_PACKAGES_WITHOUT_USER_ACTION",
+        Normal,
+        &[],
+        31,
+    ),
+    permission(
+        AndroidPermission::UseBiometric,
+        "android.permission.USE_BIOMETRIC",
+        Normal,
+        &[],
+        28,
+    ),
+    permission(
+        AndroidPermission::UseCredentials,
+        "android.permission.USE_CREDENTIALS",
+        Dangerous,
+        &[],
+        5,
+    )
+    .deprecated_in(23),
+    permission(
+        AndroidPermission::UseExactAlarm,
+        "android.permission.USE_EXACT_ALARM",
+        Normal,
+        &[],
+        33,
+    ),
+    permission(
+        AndroidPermission::UseFingerprint,
+        "android.permission.USE_FINGERPRINT",
+        Normal,
+        &[],
+        23,
+    )
+    .deprecated_in(28),
+    permission(
+        AndroidPermission::UseFullScreenIntent,
+        "android.permission.USE_FULL_SCREEN_INTENT",
+        Normal,
+        &[ProtectionFlag::Appop],
+        29,
+    ),
+    permission(
+        AndroidPermission::UseIccAuthWithDeviceIdentifier,
+        "android.permission.USE_ICC_AUTH_WITH_DEVICE_IDENTIFIER",
+        Signature,
+        &[ProtectionFlag::Appop],
+        31,
+    ),
+    permission(
+        AndroidPermission::UseSip,
+        "android.permission.USE_SIP",
+        Dangerous,
+        &[],
+        9,
+    )
+    .group(PHONE),
+    permission(
+        AndroidPermission::UwbRanging,
+        "android.permission.UWB_RANGING",
+        Dangerous,
+        &[],
+        31,
+    )
+    .group(NEARBY_DEVICES),
+    permission(
+        AndroidPermission::Vibrate,
+        "android.permission.VIBRATE",
+        Normal,
+        &[ProtectionFlag::Instant],
+        1,
+    ),
+    permission(
+        AndroidPermission::WakeLock,
+        "android.permission.WAKE_LOCK",
+        Normal,
+        &[ProtectionFlag::Instant],
+        1,
+    ),
+    permission(
+        AndroidPermission::WriteApnSettings,
+        "android.permission.WRITE_APN_SETTINGS",
+        Signature,
+        &[ProtectionFlag::Privileged],
+        1,
+    ),
+    permission(
+        AndroidPermission::WriteCalendar,
+        "android.permission.WRITE_CALENDAR",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(CALENDAR),
+    permission(
+        AndroidPermission::WriteCallLog,
+        "android.permission.WRITE_CALL_LOG",
+        Dangerous,
+        &[],
+        16,
+    )
+    .group(CALL_LOG)
+    .restriction(Restriction::Hard),
+    permission(
+        AndroidPermission::WriteContacts,
+        "android.permission.WRITE_CONTACTS",
+        Dangerous,
+        &[],
+        1,
+    )
+    .group(CONTACTS),
+    permission(
+        AndroidPermission::WriteExternalStorage,
+        "android.permission.WRITE_EXTERNAL_STORAGE",
+        Dangerous,
+        &[],
+        4,
+    )
+    .group(STORAGE)
+    .restriction(Restriction::Soft),
+    permission(
+        AndroidPermission::WriteSecureSettings,
+        "android.permission.WRITE_SECURE_SETTINGS",
+        Signature,
+        &[ProtectionFlag::Privileged, ProtectionFlag::Development],
+        3,
+    ),
+    permission(
+        AndroidPermission::WriteSettings,
+        "android.permission.WRITE_SETTINGS",
+        Signature,
+        &[
+            ProtectionFlag::Preinstalled,
+            ProtectionFlag::Appop,
+            ProtectionFlag::Pre23,
+        ],
+        1,
+    ),
+    permission(
+        AndroidPermission::WriteSyncSettings,
+        "android.permission.WRITE_SYNC_SETTINGS",
+        Normal,
+        &[],
+        1,
+    ),
+    permission(
+        AndroidPermission::WriteVoicemail,
+        "android.permission.WRITE_VOICEMAIL",
+        Signature,
+        &[ProtectionFlag::Privileged, ProtectionFlag::Role],
+        14,
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_permissions() {
+        for (index, permission) in PLATFORM_PERMISSIONS.iter().enumerate() {
+            assert_eq!(permission.permission as usize, index, "{}", permission.name);
+            assert!(permission.name.starts_with(PREFIX));
+            assert!(permission.group.is_none() || permission.is_runtime());
+        }
+        assert!(PLATFORM_PERMISSIONS
+            .windows(2)
+            .all(|pair| pair[0].name < pair[1].name));
+        let permission = AndroidPermission::WriteExternalStorage;
+        assert_eq!(
+            permission.to_string(),
+            "android.permission.WRITE_EXTERNAL_STORAGE"
+        );
+        assert_eq!(permission.info().restriction, Some(Restriction::Soft));
+        assert_eq!(
+            AndroidPermission::SystemAlertWindow
+                .info()
+                .protection_level()
+                .to_string(),
+            "signature|setup|appop|installer|pre23|development"
+        );
+        assert!(AndroidPermission::UseFingerprint
+            .info()
+            .is_deprecated_in(28));
+        assert_eq!(
+            misspelled_platform_permission("android.permission.ACCESS_FINE_LOCATIO")
+                .map(|permission| permission.permission),
+            Some(AndroidPermission::AccessFineLocation)
+        );
+        assert_eq!(
+            misspelled_platform_permission("android.permission.CAMERA"),
+            None
+        );
+        assert_eq!(
+            misspelled_platform_permission("com.example.permission.CAMERAS"),
+            None
+        );
+        // Real permissions missing from the catalog aren't typos of the ones it has.
+        for name in [
+            "android.permission.ACCESS_WIMAX_STATE",
+            "android.permission.BODY_SENSORS_WRIST_TEMPERATURE",
+            "android.permission.CHANGE_COMPONENT_ENABLED_STATE",
+            "android.permission.INTERACT_ACROSS_USERS",
+            "android.permission.MANAGE_USERS",
+            "android.permission.READ_PRIVILEGED_PHONE_STATE",
+            "android.permission.SET_DEBUG_APP",
+            "android.permission.TURN_SCREEN_ON",
+        ] {
+            assert_eq!(platform_permission(name), None, "{}", name);
+            assert_eq!(misspelled_platform_permission(name), None, "{}", name);
+        }
+        assert_eq!(
+            AndroidPermission::from_name("android.permission.ACCESS_ADSERVICES_AD_ID"),
+            Some(AndroidPermission::AccessAdservicesAdId)
+        );
+    }
+}
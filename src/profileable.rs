@@ -1,3 +1,4 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes};
 use serde::{Deserialize, Serialize};
 
 use crate::VarOrBool;
@@ -7,7 +8,7 @@ use crate::VarOrBool;
 /// ## XML Syntax
 /// ```xml
 /// <profileable android:shell=["true" | "false"]
-///              android:enable=["true" | "false"]
+///              android:enabled=["true" | "false"]
 ///  />
 /// ```
 ///
@@ -47,6 +48,18 @@ pub struct Profileable {
     /// attribute was added in API level 30.
     ///
     /// [`android:shell`]: https://developer.android.com/guide/topics/manifest/profileable-element#shell
-    #[yaserde(attribute, prefix = "android")]
+    #[yaserde(attribute, prefix = "android", rename = "enabled")]
     pub enable: VarOrBool,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Profileable>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
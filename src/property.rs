@@ -0,0 +1,66 @@
+use super::extra::{ExtraAttributes, ExtraElements};
+use super::resources::*;
+use super::tools::ToolsAttributes;
+use serde::{Deserialize, Serialize};
+
+/// A name-value pair for a property of the application or of a component, that other
+/// apps and the system can read without starting the app.
+///
+/// Unlike [`<meta-data>`], whose values are only available once the app is loaded,
+/// properties are read by the system, for example to opt in to compatibility behaviors
+/// with `android.window.PROPERTY_COMPAT_ALLOW_RESIZEABLE`, and can be queried by other
+/// apps with [`PackageManager.getProperty()`].
+///
+/// ## XML Syntax
+/// ```xml
+/// <property android:name="string"
+///           android:resource="resource specification"
+///           android:value="string" />
+/// ```
+///
+/// ## Contained in
+/// * [`<activity>`]
+/// * [`<activity-alias>`]
+/// * [`<application>`]
+/// * [`<service>`]
+/// * [`<receiver>`]
+/// * [`<provider>`]
+///
+/// ## Introduced in
+/// API Level 31
+///
+/// [`<meta-data>`]: crate::MetaData
+/// [`PackageManager.getProperty()`]: https://developer.android.com/reference/android/content/pm/PackageManager#getProperty(java.lang.String,%20java.lang.String)
+/// [`<activity>`]: crate::Activity
+/// [`<activity-alias>`]: crate::ActivityAlias
+/// [`<application>`]: crate::Application
+/// [`<service>`]: crate::Service
+/// [`<receiver>`]: crate::Receiver
+/// [`<provider>`]: crate::Provider
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct Property {
+    /// A unique name for the property, using a Java-style naming convention — for
+    /// example, `"com.example.project.PROPERTY_NAME"`.
+    #[yaserde(attribute, prefix = "android")]
+    pub name: Option<String>,
+    /// A reference to a resource. The ID of the resource is the value of the property.
+    #[yaserde(attribute, prefix = "android")]
+    pub resource: Option<AnyResource>,
+    /// The value of the property, a string, an integer, a boolean or a float.
+    #[yaserde(attribute, prefix = "android")]
+    pub value: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Property>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
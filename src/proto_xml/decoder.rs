@@ -0,0 +1,286 @@
+use super::Fields;
+use crate::binary_xml::attributes::{self, AttributeInfo};
+use crate::binary_xml::*;
+use crate::xml_tree::{XmlAttribute, XmlElement, XmlNode, ANDROID_NAMESPACE};
+
+/// Decodes an aapt2 `XmlNode` message into an [`XmlElement`] tree with attribute values
+/// rendered the way they would be written in a source `AndroidManifest.xml`.
+pub(crate) fn decode(data: &[u8]) -> Result<XmlElement, String> {
+    match node(data, &mut Vec::new())? {
+        Some(XmlNode::Element(element)) => Ok(element),
+        _ => Err("proto XML document has no root element".to_string()),
+    }
+}
+
+/// Decodes an `XmlNode`: `element = 1`, `text = 2`.
+fn node(data: &[u8], namespaces: &mut Vec<(String, String)>) -> Result<Option<XmlNode>, String> {
+    let mut node = None;
+    for field in Fields::new(data) {
+        match field? {
+            (1, value) => node = Some(XmlNode::Element(element(value.as_bytes()?, namespaces)?)),
+            (2, value) => node = Some(XmlNode::Text(value.as_string()?)),
+            _ => {}
+        }
+    }
+    Ok(node)
+}
+
+/// Decodes an `XmlElement`: `namespace_declaration = 1`, `namespace_uri = 2`,
+/// `name = 3`, `attribute = 4`, `child = 5`.
+fn element(data: &[u8], namespaces: &mut Vec<(String, String)>) -> Result<XmlElement, String> {
+    let mut element = XmlElement::default();
+    let mut namespace_uri = String::new();
+    let mut attributes = Vec::new();
+    let mut children = Vec::new();
+    // Children and attributes can only be decoded once all namespace declarations of the
+    // element are known.
+    for field in Fields::new(data) {
+        match field? {
+            (1, value) => element.namespaces.push(namespace(value.as_bytes()?)?),
+            (2, value) => namespace_uri = value.as_string()?,
+            (3, value) => element.name = value.as_string()?,
+            (4, value) => attributes.push(value.as_bytes()?),
+            (5, value) => children.push(value.as_bytes()?),
+            _ => {}
+        }
+    }
+    let scope_len = namespaces.len();
+    namespaces.extend(element.namespaces.iter().cloned());
+    if !namespace_uri.is_empty() {
+        if let Some(prefix) = prefix(namespaces, &namespace_uri) {
+            element.name = format!("{}:{}", prefix, element.name);
+        }
+    }
+    for data in attributes {
+        element.attributes.push(attribute(data, namespaces)?);
+    }
+    for data in children {
+        if let Some(child) = node(data, namespaces)? {
+            element.children.push(child);
+        }
+    }
+    namespaces.truncate(scope_len);
+    Ok(element)
+}
+
+/// Decodes an `XmlNamespace`: `prefix = 1`, `uri = 2`.
+fn namespace(data: &[u8]) -> Result<(String, String), String> {
+    let mut prefix = String::new();
+    let mut uri = String::new();
+    for field in Fields::new(data) {
+        match field? {
+            (1, value) => prefix = value.as_string()?,
+            (2, value) => uri = value.as_string()?,
+            _ => {}
+        }
+    }
+    if uri == ANDROID_NAMESPACE {
+        prefix = "android".to_string();
+    }
+    Ok((prefix, uri))
+}
+
+fn prefix(namespaces: &[(String, String)], uri: &str) -> Option<String> {
+    namespaces
+        .iter()
+        .rev()
+        .find(|(_, u)| u == uri)
+        .map(|(prefix, _)| prefix.clone())
+}
+
+/// Decodes an `XmlAttribute`: `namespace_uri = 1`, `name = 2`, `value = 3`,
+/// `resource_id = 5`, `compiled_item = 6`.
+fn attribute(data: &[u8], namespaces: &[(String, String)]) -> Result<XmlAttribute, String> {
+    let mut namespace_uri = String::new();
+    let mut name = String::new();
+    let mut value = String::new();
+    let mut resource_id = None;
+    let mut compiled_item = None;
+    for field in Fields::new(data) {
+        match field? {
+            (1, field) => namespace_uri = field.as_string()?,
+            (2, field) => name = field.as_string()?,
+            (3, field) => value = field.as_string()?,
+            (5, field) => resource_id = Some(field.as_u32()?),
+            (6, field) => compiled_item = Some(field.as_bytes()?),
+            _ => {}
+        }
+    }
+    // Like in binary XML, the resource ID is what identifies `android:` attributes.
+    let info = resource_id.and_then(attributes::by_id);
+    if let Some(info) = info {
+        name = info.name.to_string();
+    }
+    if value.is_empty() {
+        if let Some(item) = compiled_item {
+            value = self::item(item, info)?;
+        }
+    }
+    let prefix = match namespace_uri.as_str() {
+        "" => None,
+        uri => prefix(namespaces, uri),
+    };
+    Ok(XmlAttribute {
+        prefix,
+        name,
+        value,
+    })
+}
+
+/// Decodes an `Item`: `ref = 1`, `str = 2`, `raw_str = 3`, `prim = 7`.
+fn item(data: &[u8], info: Option<&AttributeInfo>) -> Result<String, String> {
+    for field in Fields::new(data) {
+        match field? {
+            (1, value) => return reference(value.as_bytes()?, info),
+            (2 | 3, value) => return string_value(value.as_bytes()?),
+            (7, value) => return primitive(value.as_bytes()?, info),
+            _ => {}
+        }
+    }
+    Ok(String::new())
+}
+
+/// Decodes a `String` or `RawString`: `value = 1`.
+fn string_value(data: &[u8]) -> Result<String, String> {
+    for field in Fields::new(data) {
+        if let (1, value) = field? {
+            return value.as_string();
+        }
+    }
+    Ok(String::new())
+}
+
+/// Decodes a `Reference`: `type = 1` (`REFERENCE = 0`, `ATTRIBUTE = 1`), `id = 2`,
+/// `name = 3`.
+fn reference(data: &[u8], info: Option<&AttributeInfo>) -> Result<String, String> {
+    let mut attribute = false;
+    let mut id = 0;
+    let mut name = String::new();
+    for field in Fields::new(data) {
+        match field? {
+            (1, value) => attribute = value.as_u32()? == 1,
+            (2, value) => id = value.as_u32()?,
+            (3, value) => name = value.as_string()?,
+            _ => {}
+        }
+    }
+    let sigil = if attribute { '?' } else { '@' };
+    if !name.is_empty() {
+        return Ok(format!("{}{}", sigil, name));
+    }
+    let data_type = if attribute {
+        TYPE_ATTRIBUTE
+    } else {
+        TYPE_REFERENCE
+    };
+    Ok(typed_value(data_type, id, info))
+}
+
+/// Decodes a `Primitive`, a oneof of `null_value = 1`, `empty_value = 2`,
+/// `float_value = 3`, `int_decimal_value = 6`, `int_hexadecimal_value = 7`,
+/// `boolean_value = 8`, `color_*_value = 9..=12`, `dimension_value = 13` and
+/// `fraction_value = 14`.
+fn primitive(data: &[u8], info: Option<&AttributeInfo>) -> Result<String, String> {
+    for field in Fields::new(data) {
+        let (data_type, value) = match field? {
+            (1 | 2, _) => return Ok(String::new()),
+            (3, value) => (TYPE_FLOAT, value),
+            (6, value) => (TYPE_INT_DEC, value),
+            (7, value) => (TYPE_INT_HEX, value),
+            (8, value) => (TYPE_INT_BOOLEAN, value),
+            (9..=12, value) => (TYPE_FIRST_COLOR_INT, value),
+            (13, value) => (TYPE_DIMENSION, value),
+            (14, value) => (TYPE_FRACTION, value),
+            _ => continue,
+        };
+        return Ok(typed_value(data_type, value.as_u32()?, info));
+    }
+    Ok(String::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(number: u32, bytes: &[u8]) -> Vec<u8> {
+        let mut out = vec![(number << 3) as u8 | super::super::WIRE_BYTES];
+        out.extend(leb128(bytes.len() as u32));
+        out.extend(bytes);
+        out
+    }
+
+    fn varint_field(number: u32, value: u8) -> Vec<u8> {
+        vec![(number << 3) as u8, value]
+    }
+
+    fn attribute(name: &str, resource_id: u32, value: &str, item: &[u8]) -> Vec<u8> {
+        let mut out = field(1, ANDROID_NAMESPACE.as_bytes());
+        out.extend(field(2, name.as_bytes()));
+        out.extend(field(3, value.as_bytes()));
+        out.push(5 << 3);
+        out.extend(leb128(resource_id));
+        out.extend(field(6, item));
+        field(4, &out)
+    }
+
+    fn leb128(mut value: u32) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    #[test]
+    fn test_decode_proto_manifest() {
+        let mut namespace = field(1, b"android");
+        namespace.extend(field(2, ANDROID_NAMESPACE.as_bytes()));
+
+        let mut activity = field(3, b"activity");
+        activity.extend(attribute(
+            "name",
+            0x01010003,
+            "com.example.MainActivity",
+            &[],
+        ));
+        let launch_mode = field(7, &varint_field(6, 2));
+        activity.extend(attribute("", 0x0101001d, "", &launch_mode));
+        let theme = field(1, &field(3, b"android:style/Theme.Black"));
+        activity.extend(attribute("theme", 0x01010000, "", &theme));
+        let mut application = field(3, b"application");
+        application.extend(field(5, &field(1, &activity)));
+
+        let mut manifest = field(1, &namespace);
+        manifest.extend(field(3, b"manifest"));
+        let mut package = field(2, b"package");
+        package.extend(field(3, b"com.example"));
+        manifest.extend(field(4, &package));
+        let version_code = field(7, &varint_field(6, 3));
+        manifest.extend(attribute("versionCode", 0x0101021b, "", &version_code));
+        manifest.extend(field(5, &field(1, &application)));
+        let document = field(1, &manifest);
+
+        let root = decode(&document).unwrap();
+        let manifest = crate::from_str(&root.to_xml_string()).unwrap();
+        assert_eq!(manifest.package, "com.example");
+        assert_eq!(manifest.version_code, Some(crate::VarOr::Value(3)));
+        let activity = &manifest.application.activity[0];
+        assert_eq!(activity.name, "com.example.MainActivity");
+        assert_eq!(
+            activity.launch_mode,
+            Some(crate::VarOr::Value(crate::LaunchMode::SingleTask))
+        );
+        assert_eq!(
+            activity.theme,
+            Some(crate::Resource::new_with_package(
+                "Theme.Black",
+                Some("android".to_string())
+            ))
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use super::Message;
+use crate::binary_xml::attributes;
+use crate::binary_xml::*;
+use crate::xml_tree::{XmlAttribute, XmlElement, XmlNode, ANDROID_NAMESPACE};
+
+/// Encodes an [`XmlElement`] tree into an aapt2 `XmlNode` message the way `aapt2`
+/// compiles a source `AndroidManifest.xml` in proto format.
+///
+/// Source values are kept next to compiled items. References are written by name, with
+/// a resource ID only when it can be resolved without a resource table.
+pub(crate) fn encode(root: &XmlElement) -> Result<Vec<u8>, String> {
+    let mut node = Message::default();
+    node.message(1, &element(root, &mut Vec::new())?);
+    Ok(node.into_bytes())
+}
+
+fn element(
+    element: &XmlElement,
+    namespaces: &mut Vec<(String, String)>,
+) -> Result<Message, String> {
+    let scope_len = namespaces.len();
+    namespaces.extend(element.namespaces.iter().cloned());
+    let mut message = Message::default();
+    for (prefix, uri) in &element.namespaces {
+        let mut namespace = Message::default();
+        namespace.string(1, prefix).string(2, uri);
+        message.message(1, &namespace);
+    }
+    match element.name.split_once(':') {
+        Some((prefix, name)) => message
+            .string(2, namespace_uri(namespaces, prefix)?)
+            .string(3, name),
+        None => message.string(3, &element.name),
+    };
+    for attribute in &element.attributes {
+        message.message(4, &self::attribute(attribute, namespaces)?);
+    }
+    for child in &element.children {
+        let mut node = Message::default();
+        match child {
+            XmlNode::Element(child) => node.message(1, &self::element(child, namespaces)?),
+            XmlNode::Text(text) => node.string(2, text),
+        };
+        message.message(5, &node);
+    }
+    namespaces.truncate(scope_len);
+    Ok(message)
+}
+
+fn namespace_uri<'a>(namespaces: &'a [(String, String)], prefix: &str) -> Result<&'a str, String> {
+    namespaces
+        .iter()
+        .rev()
+        .find(|(p, _)| p == prefix)
+        .map(|(_, uri)| uri.as_str())
+        .ok_or_else(|| format!("namespace prefix `{}` is not declared", prefix))
+}
+
+fn attribute(attribute: &XmlAttribute, namespaces: &[(String, String)]) -> Result<Message, String> {
+    let uri = match &attribute.prefix {
+        Some(prefix) => namespace_uri(namespaces, prefix)?,
+        None => "",
+    };
+    let info = match uri {
+        ANDROID_NAMESPACE => attributes::by_name(&attribute.name),
+        _ => None,
+    };
+    let mut message = Message::default();
+    message
+        .string(1, uri)
+        .string(2, &attribute.name)
+        .string(3, &attribute.value);
+    if let Some(info) = info {
+        message.varint(5, info.id.into());
+    }
+    let value = attribute.value.as_str();
+    let item = if let Some(reference) = value.strip_prefix('@') {
+        Some(self::reference(false, reference, value)?)
+    } else if let Some(reference) = value.strip_prefix('?') {
+        Some(self::reference(true, reference, value)?)
+    } else {
+        info.and_then(|info| compile_value(value, info.format))
+            .map(|(data_type, data)| primitive(data_type, data))
+    };
+    if let Some(item) = item {
+        message.message(6, &item);
+    }
+    Ok(message)
+}
+
+/// Writes an `Item` holding a `Reference`: `type = 1`, `id = 2`, `name = 3`.
+fn reference(attribute: bool, reference: &str, value: &str) -> Result<Message, String> {
+    let mut message = Message::default();
+    if attribute {
+        message.varint(1, 1);
+    }
+    match known_resource_id(reference, value)? {
+        Some(id) => message.varint(2, id.into()),
+        None => message.string(3, reference.strip_prefix('+').unwrap_or(reference)),
+    };
+    let mut item = Message::default();
+    item.message(1, &message);
+    Ok(item)
+}
+
+/// Writes an `Item` holding a `Primitive`.
+fn primitive(data_type: u8, data: u32) -> Message {
+    let mut message = Message::default();
+    match data_type {
+        TYPE_FLOAT => message.fixed32(3, data),
+        // int32 fields are sign-extended to 64 bits.
+        TYPE_INT_DEC => message.varint(6, data as i32 as u64),
+        TYPE_INT_HEX => message.varint(7, data.into()),
+        TYPE_INT_BOOLEAN => message.varint(8, (data != 0).into()),
+        TYPE_DIMENSION => message.varint(13, data.into()),
+        TYPE_FRACTION => message.varint(14, data.into()),
+        _ => message.varint(9, data.into()),
+    };
+    let mut item = Message::default();
+    item.message(7, &message);
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_proto_manifest_round_trip() {
+        let given_xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example" android:versionCode="7" android:versionName="1.0">
+    <uses-sdk android:minSdkVersion="21" android:targetSdkVersion="33" />
+    <application android:label="@string/app_name" android:icon="@mipmap/ic_launcher" android:debuggable="false">
+        <activity android:name=".MainActivity" android:launchMode="singleTask" android:configChanges="keyboardHidden|orientation" android:theme="@android:style/Theme.Black.NoTitleBar" android:exported="true" />
+    </application>
+</manifest>"#;
+        let manifest = crate::from_str(given_xml).unwrap();
+        let bytes = crate::to_proto_xml(&manifest).unwrap();
+        assert_eq!(crate::from_proto_xml(&bytes).unwrap(), manifest);
+    }
+}
@@ -0,0 +1,174 @@
+//! aapt2's protobuf XML, the format `AndroidManifest.xml` is stored in inside Android App
+//! Bundles (`base/manifest/AndroidManifest.xml`).
+//!
+//! Only the messages of aapt2's `Resources.proto` needed to represent XML documents are
+//! implemented, directly on top of the protobuf wire format.
+
+mod decoder;
+mod encoder;
+
+pub(crate) use decoder::decode;
+pub(crate) use encoder::encode;
+
+pub(crate) const WIRE_VARINT: u8 = 0;
+pub(crate) const WIRE_FIXED64: u8 = 1;
+pub(crate) const WIRE_BYTES: u8 = 2;
+pub(crate) const WIRE_FIXED32: u8 = 5;
+
+/// A field value as read from the protobuf wire format.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum WireValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+impl<'a> WireValue<'a> {
+    pub fn as_u32(self) -> Result<u32, String> {
+        match self {
+            Self::Varint(value) => Ok(value as u32),
+            Self::Fixed32(value) => Ok(value),
+            _ => Err("expected an integer field".to_string()),
+        }
+    }
+
+    pub fn as_bytes(self) -> Result<&'a [u8], String> {
+        match self {
+            Self::Bytes(bytes) => Ok(bytes),
+            _ => Err("expected a length-delimited field".to_string()),
+        }
+    }
+
+    pub fn as_string(self) -> Result<String, String> {
+        String::from_utf8(self.as_bytes()?.to_vec()).map_err(|e| e.to_string())
+    }
+}
+
+/// Iterates over the `(field number, value)` pairs of an encoded message.
+pub(crate) struct Fields<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Fields<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .data
+                .get(self.offset)
+                .ok_or_else(|| format!("unexpected end of data at offset {}", self.offset))?;
+            self.offset += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(format!("varint too long at offset {}", self.offset))
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .offset
+            .checked_add(length)
+            .and_then(|end| self.data.get(self.offset..end))
+            .ok_or_else(|| format!("unexpected end of data at offset {}", self.offset))?;
+        self.offset += length;
+        Ok(bytes)
+    }
+
+    fn field(&mut self) -> Result<(u32, WireValue<'a>), String> {
+        let key = self.varint()?;
+        let value = match (key & 0x7) as u8 {
+            WIRE_VARINT => WireValue::Varint(self.varint()?),
+            WIRE_FIXED64 => {
+                let bytes = self.take(8)?;
+                WireValue::Fixed64(u64::from_le_bytes(bytes.try_into().unwrap()))
+            }
+            WIRE_BYTES => {
+                let length = self.varint()? as usize;
+                WireValue::Bytes(self.take(length)?)
+            }
+            WIRE_FIXED32 => {
+                let bytes = self.take(4)?;
+                WireValue::Fixed32(u32::from_le_bytes(bytes.try_into().unwrap()))
+            }
+            wire_type => return Err(format!("unsupported wire type {}", wire_type)),
+        };
+        Ok(((key >> 3) as u32, value))
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = Result<(u32, WireValue<'a>), String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+        let field = self.field();
+        if field.is_err() {
+            // Stop at the first malformed field.
+            self.offset = self.data.len();
+        }
+        Some(field)
+    }
+}
+
+/// Builds an encoded message field by field.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Message(Vec<u8>);
+
+impl Message {
+    fn key(&mut self, field: u32, wire_type: u8) {
+        self.raw_varint(u64::from(field) << 3 | u64::from(wire_type));
+    }
+
+    fn raw_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    pub fn varint(&mut self, field: u32, value: u64) -> &mut Self {
+        self.key(field, WIRE_VARINT);
+        self.raw_varint(value);
+        self
+    }
+
+    pub fn fixed32(&mut self, field: u32, value: u32) -> &mut Self {
+        self.key(field, WIRE_FIXED32);
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    pub fn bytes(&mut self, field: u32, value: &[u8]) -> &mut Self {
+        self.key(field, WIRE_BYTES);
+        self.raw_varint(value.len() as u64);
+        self.0.extend(value);
+        self
+    }
+
+    /// Writes a string field, omitting it if empty like proto3 does for defaults.
+    pub fn string(&mut self, field: u32, value: &str) -> &mut Self {
+        if !value.is_empty() {
+            self.bytes(field, value.as_bytes());
+        }
+        self
+    }
+
+    pub fn message(&mut self, field: u32, value: &Message) -> &mut Self {
+        self.bytes(field, &value.0)
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
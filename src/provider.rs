@@ -1,11 +1,14 @@
-use crate::VarOrBool;
+use crate::{VarOr, VarOrBool};
 
 use super::attribute_list::{AttributeList, Semicolon};
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::grant_uri_permission::GrantUriPermission;
 use super::intent_filter::IntentFilter;
 use super::meta_data::MetaData;
 use super::path_permission::PathPermission;
+use super::property::Property;
 use super::resources::{MipmapOrDrawableResource, StringResourceOrString};
+use super::tools::ToolsAttributes;
 use serde::{Deserialize, Serialize};
 
 /// Declares a content provider component.
@@ -68,6 +71,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// ## Can contain
 /// * [`<meta-data>`]
+/// * [`<property>`]
 /// * [`<grant-uri-permission>`]
 /// * [`<intent-filter>`]
 /// * [`<path-permission>`]
@@ -80,6 +84,7 @@ use serde::{Deserialize, Serialize};
 /// [`Content Providers`]: https://developer.android.com/guide/topics/providers/content-providers
 /// [`<application>`]: crate::Application
 /// [`<meta-data>`]: crate::MetaData
+/// [`<property>`]: crate::Property
 /// [`<grant-uri-permission>`]: crate::GrantUriPermission
 /// [`<intent-filter>`]: crate::IntentFilter
 /// [`<path-permission>`]: crate::PathPermission
@@ -207,7 +212,7 @@ pub struct Provider {
     /// they are created in the order required by those dependencies. The value is a
     /// simple integer, with higher numbers being initialized first.
     #[yaserde(attribute, prefix = "android", rename = "initOrder")]
-    pub init_order: Option<u32>,
+    pub init_order: Option<VarOr<u32>>,
     /// A user-readable label for the content provided. If this attribute is not set, the
     /// label set for the application as a whole is used instead (see
     /// the [`<application>`] element's [`label`] attribute).
@@ -326,6 +331,21 @@ pub struct Provider {
     #[yaserde(rename = "meta-data")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub meta_data: Vec<MetaData>,
+    /// List of `<property>` tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub property: Vec<Property>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Provider>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 impl Provider {
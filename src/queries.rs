@@ -1,6 +1,9 @@
 use super::action::Action;
 use super::attribute_list::{AttributeList, Semicolon};
+use super::category::Category;
 use super::data::Data;
+use super::extra::{ExtraAttributes, ExtraElements};
+use super::tools::ToolsAttributes;
 use serde::{Deserialize, Serialize};
 
 /// Specifies the set of other apps that an app intends to interact with.
@@ -36,15 +39,21 @@ use serde::{Deserialize, Serialize};
 /// [`<manifest>`]: crate::AndroidManifest
 /// [`package visibility filtering`]: https://developer.android.com/training/package-visibility
 /// [`visible automatically`]: https://developer.android.com/training/package-visibility/automatic
-#[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone)]
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
 pub struct Queries {
-    /// Specifies a single app that your app intends to access. This other app might
-    /// integrate with your app, or your app might use services that the other app
-    /// provides.
-    pub package: Option<Package>,
-    /// Specifies an [`intent filter signature`]. Your app can discover other apps that
+    /// Specifies single apps that your app intends to access. These other apps might
+    /// integrate with your app, or your app might use services that they provide.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub package: Vec<Package>,
+    /// Specifies [`intent filter signatures`]. Your app can discover other apps that
     /// have matching [`<intent-filter>`] elements.
-    pub intent: Option<Intent>,
+    ///
+    /// [`intent filter signatures`]: https://developer.android.com/training/basics/intents/filters
+    /// [`<intent-filter>`]: crate::IntentFilter
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub intent: Vec<Intent>,
     /// Specifies one or more [`content provider authorities`]. Your app can discover
     /// other apps whose content providers use the specified authorities.
     ///
@@ -57,6 +66,18 @@ pub struct Queries {
     /// [`content provider authorities`]: https://developer.android.com/guide/topics/providers/content-provider-basics#ContentURIs
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub provider: Vec<QueriesProvider>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Queries>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 /// Specifies a single app that your app intends to access. This other app might integrate
@@ -66,6 +87,18 @@ pub struct Package {
     /// `Required`. Specifies the package name of the other app.
     #[yaserde(attribute, prefix = "android")]
     pub name: String,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Package>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 /// Specifies an [`intent filter signature`]. Your app can discover other apps that have
@@ -84,9 +117,28 @@ pub struct Package {
     Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
 )]
 pub struct Intent {
-    pub action: Action,
+    /// List of `<action>` tags. Android rejects `<intent>` tags with more than one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub action: Vec<Action>,
+    /// List of `<category>` tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub category: Vec<Category>,
+    /// List of `<data>` tags. Android rejects `<intent>` tags with more than one
+    /// `scheme`, `host` or `mimeType`, and ignores the paths.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub data: Vec<Data>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Intent>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 /// Declares a content provider component used in [`<queries>`].
@@ -182,6 +234,18 @@ pub struct QueriesProvider {
     /// [`<manifest>`]: crate::AndroidManifest
     #[yaserde(attribute, prefix = "android")]
     pub name: String,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<QueriesProvider>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 impl QueriesProvider {
@@ -1,8 +1,11 @@
 use crate::VarOrBool;
 
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::intent_filter::IntentFilter;
 use super::meta_data::MetaData;
+use super::property::Property;
 use super::resources::{MipmapOrDrawableResource, StringResourceOrString};
+use super::tools::ToolsAttributes;
 use serde::{Deserialize, Serialize};
 
 /// Declares a broadcast receiver (a [`BroadcastReceiver`] subclass) as one of the
@@ -44,6 +47,7 @@ use serde::{Deserialize, Serialize};
 /// ## Can contain
 /// * [`<intent-filter>`]
 /// * [`<meta-data>`]
+/// * [`<property>`]
 ///
 /// ## Introduced in
 /// API Level 1
@@ -53,6 +57,7 @@ use serde::{Deserialize, Serialize};
 /// [`<application>`]: crate::Application
 /// [`<intent-filter>`]: crate::IntentFilter
 /// [`<meta-data>`]: crate::MetaData
+/// [`<property>`]: crate::Property
 #[derive(
     Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
 )]
@@ -193,4 +198,19 @@ pub struct Receiver {
     #[serde(rename = "meta-data", skip_serializing_if = "Vec::is_empty", default)]
     #[yaserde(rename = "meta-data")]
     pub meta_data: Vec<MetaData>,
+    /// List of `<property>` tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub property: Vec<Property>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Receiver>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
@@ -0,0 +1,44 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
+use serde::{Deserialize, Serialize};
+
+/// Declares that the package is an SDK library, a library distributed as its own package
+/// and loaded by the apps that use it, like the SDKs of the [`SDK Runtime`].
+///
+/// ## XML Syntax
+/// ```xml
+/// <sdk-library android:name="string"
+///              android:versionMajor="integer" />
+/// ```
+///
+/// ## Contained in
+/// * [`<application>`]
+///
+/// ## Introduced in
+/// API Level 33
+///
+/// [`SDK Runtime`]: https://developer.android.com/design-for-safety/privacy-sandbox/sdk-runtime
+/// [`<application>`]: crate::Application
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct SdkLibrary {
+    /// The name of the library.
+    #[yaserde(attribute, prefix = "android")]
+    pub name: Option<String>,
+    /// The major version of the library. Versions with the same major version are
+    /// compatible.
+    #[yaserde(attribute, prefix = "android", rename = "versionMajor")]
+    pub version_major: Option<VarOr<u32>>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<SdkLibrary>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
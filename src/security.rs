@@ -0,0 +1,650 @@
+//! Security audit of a manifest, for the settings that expose the app or its data to
+//! other apps, with findings written as JSON or as SARIF for code scanning tools.
+
+use crate::validate::{declared_target_sdk_version, path};
+use crate::{
+    Activity, AndroidManifest, BaseProtectionLevel, IntentFilter, LaunchMode, ManifestDocument,
+    Risk, VarOr, VarOrBool,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt;
+
+const ACTION_MAIN: &str = "android.intent.action.MAIN";
+const CATEGORY_LAUNCHER: &str = "android.intent.category.LAUNCHER";
+
+/// A check run by [`audit`].
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecurityRule {
+    /// An exported activity, activity alias, service, receiver or provider isn't
+    /// protected by a permission, so any app can start, bind to or query it. Launcher
+    /// activities are left out, as they are meant to be started by any app.
+    ExportedWithoutPermission,
+    /// `android:debuggable="true"` lets anyone attach a debugger to the app and run code
+    /// in its process.
+    Debuggable,
+    /// `android:testOnly="true"` marks a test build that shouldn't be distributed.
+    TestOnly,
+    /// Backups are allowed, as they are by default, without `android:fullBackupContent`
+    /// or `android:dataExtractionRules` to leave out sensitive files.
+    BackupWithoutRules,
+    /// Traffic without TLS is allowed, explicitly or by default when targeting API level
+    /// 27 or lower without a network security config. The default is only reported when
+    /// the manifest sets the target SDK version, which source manifests usually leave to
+    /// the build.
+    CleartextTraffic,
+    /// An exported provider sets `android:grantUriPermissions="true"`, letting the apps
+    /// it grants a URI to access any of its data.
+    ExportedProviderGrantsUriPermissions,
+    /// A permission declared by the app has a `normal` or `dangerous` protection level,
+    /// which any app can request.
+    WeakPermissionProtection,
+    /// A `singleTask` or `singleInstance` activity runs in a task whose affinity
+    /// another app can claim to put its own activities on top of it, or an activity can
+    /// move to the task of another app.
+    TaskHijacking,
+}
+
+impl SecurityRule {
+    /// Stable identifier of the rule, like `exported-without-permission`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::ExportedWithoutPermission => "exported-without-permission",
+            Self::Debuggable => "debuggable",
+            Self::TestOnly => "test-only",
+            Self::BackupWithoutRules => "backup-without-rules",
+            Self::CleartextTraffic => "cleartext-traffic",
+            Self::ExportedProviderGrantsUriPermissions => {
+                "exported-provider-grants-uri-permissions"
+            }
+            Self::WeakPermissionProtection => "weak-permission-protection",
+            Self::TaskHijacking => "task-hijacking",
+        }
+    }
+
+    pub fn risk(&self) -> Risk {
+        match self {
+            Self::ExportedWithoutPermission
+            | Self::Debuggable
+            | Self::ExportedProviderGrantsUriPermissions => Risk::High,
+            Self::TestOnly
+            | Self::CleartextTraffic
+            | Self::WeakPermissionProtection
+            | Self::TaskHijacking => Risk::Medium,
+            Self::BackupWithoutRules => Risk::Low,
+        }
+    }
+
+    /// One line description of the rule.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::ExportedWithoutPermission => "Exported component without permission",
+            Self::Debuggable => "Debuggable application",
+            Self::TestOnly => "Test-only application",
+            Self::BackupWithoutRules => "Backup allowed without backup rules",
+            Self::CleartextTraffic => "Cleartext traffic allowed",
+            Self::ExportedProviderGrantsUriPermissions => {
+                "Exported provider granting URI permissions"
+            }
+            Self::WeakPermissionProtection => "Custom permission with a weak protection level",
+            Self::TaskHijacking => "Activity prone to task hijacking",
+        }
+    }
+
+    fn all() -> [Self; 8] {
+        [
+            Self::ExportedWithoutPermission,
+            Self::Debuggable,
+            Self::TestOnly,
+            Self::BackupWithoutRules,
+            Self::CleartextTraffic,
+            Self::ExportedProviderGrantsUriPermissions,
+            Self::WeakPermissionProtection,
+            Self::TaskHijacking,
+        ]
+    }
+}
+
+impl fmt::Display for SecurityRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// A security issue found by [`audit`].
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub struct Finding {
+    pub rule: SecurityRule,
+    pub risk: Risk,
+    pub message: String,
+    /// Path of the offending element, like `manifest/application/activity[2]`. Elements
+    /// are numbered from 1 when there are several of the same kind.
+    pub path: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {} [{}]",
+            self.risk, self.path, self.message, self.rule
+        )
+    }
+}
+
+/// The findings of [`audit`], in manifest order.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Default, Clone)]
+pub struct SecurityReport {
+    pub findings: Vec<Finding>,
+}
+
+impl SecurityReport {
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Highest risk of the findings, or `None` when there are none.
+    pub fn max_risk(&self) -> Option<Risk> {
+        self.findings.iter().map(|finding| finding.risk).max()
+    }
+
+    /// Returns the report as pretty printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report is serializable")
+    }
+
+    /// Returns the report as a [SARIF 2.1.0] log, with each finding located in the
+    /// manifest file at `uri`, like `app/src/main/AndroidManifest.xml`, at the start tag
+    /// of its element in `document`, the audited manifest, and by path.
+    ///
+    /// Each result has a `manifestElement/v1` partial fingerprint made of the rule, the
+    /// element and its `android:name`, so code scanning tools can match it across runs
+    /// after lines or siblings move.
+    ///
+    /// Risks map to SARIF levels: high to `error`, medium to `warning` and low to
+    /// `note`.
+    ///
+    /// [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+    pub fn to_sarif(&self, uri: &str, document: &ManifestDocument) -> String {
+        let rules = SecurityRule::all()
+            .iter()
+            .map(|rule| {
+                json!({
+                    "id": rule.id(),
+                    "shortDescription": { "text": rule.description() },
+                    "defaultConfiguration": { "level": sarif_level(rule.risk()) },
+                })
+            })
+            .collect::<Vec<_>>();
+        let results = self
+            .findings
+            .iter()
+            .map(|finding| {
+                let element = document.find_path(&finding.path);
+                let mut physical_location = json!({ "artifactLocation": { "uri": uri } });
+                if let Some(element) = element {
+                    let (line, column) = document.position(element);
+                    physical_location["region"] = json!({
+                        "startLine": line,
+                        "startColumn": column,
+                    });
+                }
+                let fingerprint =
+                    match element.and_then(|element| document.attribute(element, "android:name")) {
+                        Some(name) => {
+                            let elements = finding
+                                .path
+                                .split('/')
+                                .map(|element| element.split('[').next().unwrap_or(element))
+                                .collect::<Vec<_>>();
+                            format!("{}:{}[{}]", finding.rule.id(), elements.join("/"), name)
+                        }
+                        None => format!("{}:{}", finding.rule.id(), finding.path),
+                    };
+                json!({
+                    "ruleId": finding.rule.id(),
+                    "level": sarif_level(finding.risk),
+                    "message": { "text": finding.message },
+                    "locations": [{
+                        "physicalLocation": physical_location,
+                        "logicalLocations": [{
+                            "fullyQualifiedName": finding.path,
+                            "kind": "element",
+                        }],
+                    }],
+                    "partialFingerprints": { "manifestElement/v1": fingerprint },
+                })
+            })
+            .collect::<Vec<_>>();
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        });
+        serde_json::to_string_pretty(&log).expect("SARIF log is serializable")
+    }
+}
+
+impl fmt::Display for SecurityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for finding in &self.findings {
+            writeln!(f, "{}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+/// Audits a manifest for settings that expose the app or its data to other apps.
+///
+/// ```
+/// # use android_manifest::*;
+/// let document = ManifestDocument::parse(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+///     <uses-sdk android:minSdkVersion="24" android:targetSdkVersion="34" />
+///     <application android:debuggable="true" android:allowBackup="false">
+///         <service android:name=".SyncService" android:exported="true" />
+///     </application>
+/// </manifest>"#).unwrap();
+/// let report = audit(&document.manifest().unwrap());
+/// assert_eq!(report.findings[0].rule, SecurityRule::Debuggable);
+/// assert_eq!(report.findings[1].rule, SecurityRule::ExportedWithoutPermission);
+/// assert_eq!(report.max_risk(), Some(Risk::High));
+/// let sarif = report.to_sarif("app/src/main/AndroidManifest.xml", &document);
+/// ```
+pub fn audit(manifest: &AndroidManifest) -> SecurityReport {
+    let mut auditor = Auditor {
+        manifest,
+        findings: Vec::new(),
+    };
+    auditor.application();
+    auditor.components();
+    auditor.providers();
+    auditor.permissions();
+    SecurityReport {
+        findings: auditor.findings,
+    }
+}
+
+fn sarif_level(risk: Risk) -> &'static str {
+    match risk {
+        Risk::High => "error",
+        Risk::Medium => "warning",
+        Risk::Low => "note",
+    }
+}
+
+fn is_true(value: &Option<VarOrBool>) -> bool {
+    value == &Some(VarOrBool::Bool(true))
+}
+
+fn is_false(value: &Option<VarOrBool>) -> bool {
+    value == &Some(VarOrBool::Bool(false))
+}
+
+fn is_launcher(intent_filters: &[IntentFilter]) -> bool {
+    intent_filters.iter().any(|intent_filter| {
+        let has_action = intent_filter
+            .action
+            .iter()
+            .any(|action| action.name.as_deref() == Some(ACTION_MAIN));
+        let has_category = intent_filter
+            .category
+            .iter()
+            .any(|category| category.name.as_deref() == Some(CATEGORY_LAUNCHER));
+        has_action && has_category
+    })
+}
+
+struct Auditor<'a> {
+    manifest: &'a AndroidManifest,
+    findings: Vec<Finding>,
+}
+
+impl Auditor<'_> {
+    fn report(&mut self, rule: SecurityRule, path: String, message: String) {
+        self.findings.push(Finding {
+            rule,
+            risk: rule.risk(),
+            message,
+            path,
+        });
+    }
+
+    fn application(&mut self) {
+        let application = &self.manifest.application;
+        let path = "manifest/application".to_string();
+        if is_true(&application.debuggable) {
+            let message = "android:debuggable is true".to_string();
+            self.report(SecurityRule::Debuggable, path.clone(), message);
+        }
+        if is_true(&application.test_only) {
+            let message = "android:testOnly is true".to_string();
+            self.report(SecurityRule::TestOnly, path.clone(), message);
+        }
+        if !is_false(&application.allow_backup)
+            && application.full_backup_content.is_none()
+            && application.data_extraction_rules.is_none()
+        {
+            let message = "backups are allowed without android:fullBackupContent or \
+                android:dataExtractionRules"
+                .to_string();
+            self.report(SecurityRule::BackupWithoutRules, path.clone(), message);
+        }
+        if is_true(&application.uses_cleartext_traffic) {
+            let message = "android:usesCleartextTraffic is true".to_string();
+            self.report(SecurityRule::CleartextTraffic, path, message);
+        } else if application.uses_cleartext_traffic.is_none()
+            && application.network_security_config.is_none()
+            && declared_target_sdk_version(self.manifest).is_some_and(|version| version < 28)
+        {
+            let message = "cleartext traffic is allowed by default when targeting API level \
+                27 or lower"
+                .to_string();
+            self.report(SecurityRule::CleartextTraffic, path, message);
+        }
+    }
+
+    fn components(&mut self) {
+        let application = &self.manifest.application;
+        let parent = "manifest/application";
+        let count = application.activity.len();
+        for (index, activity) in application.activity.iter().enumerate() {
+            let path = path(parent, "activity", index, count);
+            if !is_launcher(&activity.intent_filter) {
+                self.component(
+                    &path,
+                    &activity.enabled,
+                    &activity.exported,
+                    &activity.permission,
+                    &activity.intent_filter,
+                );
+            }
+            self.task_affinity(&path, activity);
+        }
+        let count = application.activity_alias.len();
+        for (index, alias) in application.activity_alias.iter().enumerate() {
+            if !is_launcher(&alias.intent_filter) {
+                self.component(
+                    &path(parent, "activity-alias", index, count),
+                    &alias.enabled,
+                    &alias.exported,
+                    &alias.permission,
+                    &alias.intent_filter,
+                );
+            }
+        }
+        let count = application.service.len();
+        for (index, service) in application.service.iter().enumerate() {
+            self.component(
+                &path(parent, "service", index, count),
+                &service.enabled,
+                &service.exported,
+                &service.permission,
+                &service.intent_filter,
+            );
+        }
+        let count = application.receiver.len();
+        for (index, receiver) in application.receiver.iter().enumerate() {
+            self.component(
+                &path(parent, "receiver", index, count),
+                &receiver.enabled,
+                &receiver.exported,
+                &receiver.permission,
+                &receiver.intent_filter,
+            );
+        }
+    }
+
+    /// Components with intent filters are exported by default.
+    fn component(
+        &mut self,
+        path: &str,
+        enabled: &Option<VarOrBool>,
+        exported: &Option<VarOrBool>,
+        permission: &Option<String>,
+        intent_filters: &[IntentFilter],
+    ) {
+        let exported = is_true(exported) || (exported.is_none() && !intent_filters.is_empty());
+        let permission = permission
+            .as_ref()
+            .or(self.manifest.application.permission.as_ref());
+        if exported && !is_false(enabled) && permission.is_none() {
+            let message = "component is exported without android:permission".to_string();
+            self.report(
+                SecurityRule::ExportedWithoutPermission,
+                path.to_string(),
+                message,
+            );
+        }
+    }
+
+    /// Activities with a `singleTask` or `singleInstance` launch mode start in the task
+    /// of their affinity, the package name by default, which an app declaring the same
+    /// affinity can take over. An empty affinity leaves them out of any task.
+    fn task_affinity(&mut self, path: &str, activity: &Activity) {
+        let application = &self.manifest.application;
+        let launch_mode = activity.launch_mode.as_ref().and_then(VarOr::value);
+        let single = matches!(
+            launch_mode,
+            Some(LaunchMode::SingleTask | LaunchMode::SingleInstance)
+        );
+        let affinity = activity
+            .task_affinity
+            .as_ref()
+            .or(application.task_affinity.as_ref());
+        if single && affinity.is_none_or(|affinity| !affinity.is_empty()) {
+            let message = "activity with a singleTask or singleInstance launch mode doesn't \
+                set an empty android:taskAffinity"
+                .to_string();
+            self.report(SecurityRule::TaskHijacking, path.to_string(), message);
+        }
+        let reparenting = match activity.allow_task_reparenting {
+            Some(_) => &activity.allow_task_reparenting,
+            None => &application.allow_task_reparenting,
+        };
+        if is_true(reparenting) {
+            let message = "android:allowTaskReparenting is true".to_string();
+            self.report(SecurityRule::TaskHijacking, path.to_string(), message);
+        }
+    }
+
+    /// Providers are only exported by default when targeting API level 16 or lower. An
+    /// unknown target SDK version is taken as a recent one, like the build would set.
+    fn providers(&mut self) {
+        let application = &self.manifest.application;
+        let providers = &application.provider;
+        for (index, provider) in providers.iter().enumerate() {
+            let path = path("manifest/application", "provider", index, providers.len());
+            let exported = is_true(&provider.exported)
+                || (provider.exported.is_none()
+                    && declared_target_sdk_version(self.manifest)
+                        .is_some_and(|version| version <= 16));
+            if !exported || is_false(&provider.enabled) {
+                continue;
+            }
+            let permission = provider
+                .permission
+                .as_ref()
+                .or(application.permission.as_ref());
+            let unprotected = match (&provider.read_permission, &provider.write_permission) {
+                _ if permission.is_some() => None,
+                (None, None) => Some("reads and writes"),
+                (None, Some(_)) => Some("reads"),
+                (Some(_), None) => Some("writes"),
+                (Some(_), Some(_)) => None,
+            };
+            if let Some(access) = unprotected {
+                let message = format!("provider is exported without a permission for {}", access);
+                self.report(
+                    SecurityRule::ExportedWithoutPermission,
+                    path.clone(),
+                    message,
+                );
+            }
+            if is_true(&provider.grant_uri_permissions) {
+                let message = "exported provider sets android:grantUriPermissions".to_string();
+                self.report(
+                    SecurityRule::ExportedProviderGrantsUriPermissions,
+                    path,
+                    message,
+                );
+            }
+        }
+    }
+
+    fn permissions(&mut self) {
+        let permissions = &self.manifest.permission;
+        for (index, permission) in permissions.iter().enumerate() {
+            // A placeholder could be any protection level.
+            let base = match &permission.protection_level {
+                Some(VarOr::Value(protection_level)) => protection_level.base,
+                Some(VarOr::Var(_)) => continue,
+                None => BaseProtectionLevel::default(),
+            };
+            let message = match base {
+                BaseProtectionLevel::Normal => "any app can request",
+                BaseProtectionLevel::Dangerous => "any app the user allows can request",
+                _ => continue,
+            };
+            let name = permission.name.as_deref().unwrap_or_default();
+            let message = format!(
+                "permission `{}` has a {} protection level, that {}",
+                name,
+                serde_plain::to_string(&base).unwrap_or_default(),
+                message
+            );
+            let path = path("manifest", "permission", index, permissions.len());
+            self.report(SecurityRule::WeakPermissionProtection, path, message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit() {
+        let document = ManifestDocument::parse(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-sdk android:minSdkVersion="21" android:targetSdkVersion="26" />
+    <permission android:name="com.example.permission.SYNC" />
+    <permission android:name="com.example.permission.ADMIN" android:protectionLevel="signature" />
+    <application android:testOnly="true">
+        <activity android:name=".MainActivity" android:exported="true" android:launchMode="singleTask">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+                <category android:name="android.intent.category.LAUNCHER" />
+            </intent-filter>
+        </activity>
+        <activity android:name=".ShareActivity" android:taskAffinity="" android:launchMode="singleTask">
+            <intent-filter>
+                <action android:name="android.intent.action.SEND" />
+            </intent-filter>
+        </activity>
+        <receiver android:name=".SyncReceiver" android:exported="true" android:permission="com.example.permission.SYNC" />
+        <provider android:name=".FilesProvider" android:authorities="com.example.files" android:exported="true" android:readPermission="com.example.permission.ADMIN" android:grantUriPermissions="true" />
+    </application>
+</manifest>"#)
+        .unwrap();
+        let manifest = document.manifest().unwrap();
+        let report = audit(&manifest);
+        let findings: Vec<(SecurityRule, &str)> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.rule, finding.path.as_str()))
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                (SecurityRule::TestOnly, "manifest/application"),
+                (SecurityRule::BackupWithoutRules, "manifest/application"),
+                (SecurityRule::CleartextTraffic, "manifest/application"),
+                (
+                    SecurityRule::TaskHijacking,
+                    "manifest/application/activity[1]"
+                ),
+                (
+                    SecurityRule::ExportedWithoutPermission,
+                    "manifest/application/activity[2]"
+                ),
+                (
+                    SecurityRule::ExportedWithoutPermission,
+                    "manifest/application/provider"
+                ),
+                (
+                    SecurityRule::ExportedProviderGrantsUriPermissions,
+                    "manifest/application/provider"
+                ),
+                (
+                    SecurityRule::WeakPermissionProtection,
+                    "manifest/permission[1]"
+                ),
+            ]
+        );
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["findings"][0]["rule"], "test-only");
+        let sarif: serde_json::Value =
+            serde_json::from_str(&report.to_sarif("AndroidManifest.xml", &document)).unwrap();
+        let result = &sarif["runs"][0]["results"][4];
+        assert_eq!(result["ruleId"], "exported-without-permission");
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "AndroidManifest.xml");
+        assert_eq!(location["region"]["startLine"], 12);
+        assert_eq!(location["region"]["startColumn"], 9);
+        assert_eq!(
+            result["partialFingerprints"]["manifestElement/v1"],
+            "exported-without-permission:manifest/application/activity[.ShareActivity]"
+        );
+        assert_eq!(
+            sarif["runs"][0]["results"][0]["partialFingerprints"]["manifestElement/v1"],
+            "test-only:manifest/application"
+        );
+        assert_eq!(
+            result["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "manifest/application/activity[2]"
+        );
+        assert_eq!(
+            sarif["runs"][0]["tool"]["driver"]["rules"][7]["id"],
+            "task-hijacking"
+        );
+    }
+
+    #[test]
+    fn test_audit_without_target_sdk() {
+        let xml = r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <application android:allowBackup="false">
+        <provider android:name=".FilesProvider" android:authorities="com.example.files" />
+    </application>
+</manifest>"#;
+        let manifest = crate::from_str(xml).unwrap();
+        assert_eq!(audit(&manifest), SecurityReport::default());
+        let legacy = xml.replace(
+            "<application",
+            r#"<uses-sdk android:targetSdkVersion="16" />
+    <application"#,
+        );
+        let rules: Vec<SecurityRule> = audit(&crate::from_str(&legacy).unwrap())
+            .findings
+            .into_iter()
+            .map(|finding| finding.rule)
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                SecurityRule::CleartextTraffic,
+                SecurityRule::ExportedWithoutPermission,
+            ]
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use crate::VarOrBool;
 
+use super::attribute_list::{AttributeList, VerticalBar};
+use super::extra::{ExtraAttributes, ExtraElements};
 use super::intent_filter::IntentFilter;
 use super::meta_data::MetaData;
+use super::property::Property;
 use super::resources::{
     MipmapOrDrawableResource, Resource, StringResource, StringResourceOrString,
 };
+use super::tools::ToolsAttributes;
 use serde::{Deserialize, Serialize};
 
 /// Declares a service (a [`Service`] subclass) as one of the application's components.
@@ -22,7 +26,8 @@ use serde::{Deserialize, Serialize};
 ///
 /// ## XML Syntax
 /// ```xml
-/// <service android:description="string resource"
+/// <service android:allowSharedIsolatedProcess=["true" | "false"]
+///          android:description="string resource"
 ///          android:directBootAware=["true" | "false"]
 ///          android:enabled=["true" | "false"]
 ///          android:exported=["true" | "false"]
@@ -34,7 +39,8 @@ use serde::{Deserialize, Serialize};
 ///          android:label="string resource"
 ///          android:name="string"
 ///          android:permission="string"
-///          android:process="string" >
+///          android:process="string"
+///          android:useAppZygote=["true" | "false"] >
 ///             ...
 /// </service>
 /// ```
@@ -45,6 +51,7 @@ use serde::{Deserialize, Serialize};
 /// ## Can contain
 /// * [`<intent-filter>`]
 /// * [`<meta-data>`]
+/// * [`<property>`]
 ///
 /// ## introduced in
 /// API Level 1
@@ -55,10 +62,22 @@ use serde::{Deserialize, Serialize};
 /// [`<application>`]: crate::Application
 /// [`<intent-filter>`]: crate::IntentFilter
 /// [`<meta-data>`]: crate::MetaData
+/// [`<property>`]: crate::Property
 #[derive(
     Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
 )]
 pub struct Service {
+    /// Whether the service, when [`isolatedProcess`] is "`true`", can run in an isolated
+    /// process shared with other isolated services of the app that also set it, bound
+    /// with `Context.BIND_SHARED_ISOLATED_PROCESS`.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 34.
+    ///
+    /// [`isolatedProcess`]: crate::Service#structfield.isolated_process
+    #[yaserde(attribute, prefix = "android", rename = "allowSharedIsolatedProcess")]
+    pub allow_shared_isolated_process: Option<VarOrBool>,
     /// A string that describes the service to users. The label should be set as a
     /// reference to a string resource, so that it can be localized like other strings
     /// in the user interface.
@@ -115,12 +134,20 @@ pub struct Service {
     /// that an app is getting the device's current location, usually to [`continue a
     /// user-initiated action`] related to device location.
     ///
-    /// You can assign multiple foreground service types to a particular service.
+    /// You can assign multiple foreground service types to a particular service, by
+    /// separating them with `|`. Apps targeting Android 14 (API level 34) or higher must
+    /// request the [`permission`](ForegroundServiceType::permission) of each type.
     ///
     /// [`foreground service`]: https://developer.android.com/guide/components/services
     /// [`continue a user-initiated action`]: https://developer.android.com/training/location/background#continue-user-initiated-action
-    #[yaserde(attribute, prefix = "android", rename = "foregroundServiceType")]
-    pub foreground_service_type: Option<ForegroundServiceType>,
+    #[yaserde(
+        attribute,
+        prefix = "android",
+        rename = "foregroundServiceType",
+        skip_serializing_if = "check_foreground_service_type"
+    )]
+    #[serde(default, skip_serializing_if = "AttributeList::is_empty")]
+    pub foreground_service_type: AttributeList<VerticalBar, ForegroundServiceType>,
     /// An icon representing the service. This attribute must be set as a reference to a
     /// drawable resource containing the image definition. If it is not set, the icon
     /// specified for the application as a whole is used instead (see the
@@ -217,33 +244,129 @@ pub struct Service {
     /// [`process`]: crate::Application#structfield.process
     #[yaserde(attribute, prefix = "android")]
     pub process: Option<String>,
+    /// Whether the service, when [`isolatedProcess`] is "`true`", is spawned from an app
+    /// zygote, which preloads code with the [`zygotePreloadName`] of the application
+    /// to speed up its start.
+    ///
+    /// The default value is "`false`".
+    ///
+    /// This attribute was introduced in API Level 29.
+    ///
+    /// [`isolatedProcess`]: crate::Service#structfield.isolated_process
+    /// [`zygotePreloadName`]: crate::Application#structfield.zygote_preload_name
+    #[yaserde(attribute, prefix = "android", rename = "useAppZygote")]
+    pub use_app_zygote: Option<VarOrBool>,
     #[yaserde(rename = "intent-filter")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub intent_filter: Vec<IntentFilter>,
     #[yaserde(rename = "meta-data")]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub meta_data: Vec<MetaData>,
+    /// List of `<property>` tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub property: Vec<Property>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<Service>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
+
+impl Service {
+    fn check_foreground_service_type(
+        &self,
+        value: &AttributeList<VerticalBar, ForegroundServiceType>,
+    ) -> bool {
+        value.is_empty()
+    }
 }
 
+/// Name of the `<property>` that describes the use case of a `specialUse` foreground
+/// service.
+pub const PROPERTY_SPECIAL_USE_FGS_SUBTYPE: &str = "android.app.PROPERTY_SPECIAL_USE_FGS_SUBTYPE";
+
+/// Use case of a [`foreground service`](https://developer.android.com/develop/background-work/services/fg-service-types).
 #[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
 #[derive(Default)]
 pub enum ForegroundServiceType {
+    /// Continued access to the camera, like video chat apps. Added in API level 30.
     #[yaserde(rename = "camera")]
     #[default]
     Camera,
+    /// Interactions with external devices over Bluetooth, NFC, USB or the network.
+    /// Added in API level 29.
     #[yaserde(rename = "connectedDevice")]
     ConnectedDevice,
+    /// Data transfer, like uploads, downloads, backups and file conversions. Added in
+    /// API level 29.
     #[yaserde(rename = "dataSync")]
     DataSync,
+    /// Fitness and health tracking. Added in API level 34.
+    #[yaserde(rename = "health")]
+    Health,
+    /// Continued access to the location, like navigation. Added in API level 29.
     #[yaserde(rename = "location")]
     Location,
+    /// Audio or video playback in the background. Added in API level 29.
     #[yaserde(rename = "mediaPlayback")]
     MediaPlayback,
+    /// Media transcoding and processing. Added in API level 35.
+    #[yaserde(rename = "mediaProcessing")]
+    MediaProcessing,
+    /// Content projection to a secondary or external display, like screen recording.
+    /// Added in API level 29.
     #[yaserde(rename = "mediaProjection")]
     MediaProjection,
+    /// Continued capture from the microphone, like voice recorders. Added in API level
+    /// 30.
     #[yaserde(rename = "microphone")]
     Microphone,
+    /// Ongoing calls through the `ConnectionService` API. Added in API level 29.
     #[yaserde(rename = "phoneCall")]
     PhoneCall,
+    /// Transfer of text messages between devices. Added in API level 34.
+    #[yaserde(rename = "remoteMessaging")]
+    RemoteMessaging,
+    /// Critical work that must finish within a few minutes. Added in API level 34.
+    #[yaserde(rename = "shortService")]
+    ShortService,
+    /// A use case that no other type covers, described by a
+    /// [`PROPERTY_SPECIAL_USE_FGS_SUBTYPE`] property. Added in API level 34.
+    #[yaserde(rename = "specialUse")]
+    SpecialUse,
+    /// Reserved to system apps. Added in API level 34.
+    #[yaserde(rename = "systemExempted")]
+    SystemExempted,
+}
+
+impl ForegroundServiceType {
+    /// The permission that apps targeting Android 14 (API level 34) or higher must
+    /// request to start a foreground service of this type, on top of
+    /// `android.permission.FOREGROUND_SERVICE`. `shortService` requires none.
+    pub fn permission(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::Camera => "android.permission.FOREGROUND_SERVICE_CAMERA",
+            Self::ConnectedDevice => "android.permission.FOREGROUND_SERVICE_CONNECTED_DEVICE",
+            Self::DataSync => "android.permission.FOREGROUND_SERVICE_DATA_SYNC",
+            Self::Health => "android.permission.FOREGROUND_SERVICE_HEALTH",
+            Self::Location => "android.permission.FOREGROUND_SERVICE_LOCATION",
+            Self::MediaPlayback => "android.permission.FOREGROUND_SERVICE_MEDIA_PLAYBACK",
+            Self::MediaProcessing => "android.permission.FOREGROUND_SERVICE_MEDIA_PROCESSING",
+            Self::MediaProjection => "android.permission.FOREGROUND_SERVICE_MEDIA_PROJECTION",
+            Self::Microphone => "android.permission.FOREGROUND_SERVICE_MICROPHONE",
+            Self::PhoneCall => "android.permission.FOREGROUND_SERVICE_PHONE_CALL",
+            Self::RemoteMessaging => "android.permission.FOREGROUND_SERVICE_REMOTE_MESSAGING",
+            Self::ShortService => return None,
+            Self::SpecialUse => "android.permission.FOREGROUND_SERVICE_SPECIAL_USE",
+            Self::SystemExempted => "android.permission.FOREGROUND_SERVICE_SYSTEM_EXEMPTED",
+        })
+    }
 }
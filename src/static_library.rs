@@ -0,0 +1,48 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
+use serde::{Deserialize, Serialize};
+
+/// Declares that the package is a static shared library, a library distributed as its
+/// own package, of which several versions can be installed side by side.
+///
+/// Only system and preinstalled packages can declare static shared libraries.
+///
+/// ## XML Syntax
+/// ```xml
+/// <static-library android:name="string"
+///                 android:version="integer"
+///                 android:versionMajor="integer" />
+/// ```
+///
+/// ## Contained in
+/// * [`<application>`]
+///
+/// ## Introduced in
+/// API Level 26
+///
+/// [`<application>`]: crate::Application
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct StaticLibrary {
+    /// The name of the library.
+    #[yaserde(attribute, prefix = "android")]
+    pub name: Option<String>,
+    /// The version of the library, the lower 32 bits of its long version.
+    #[yaserde(attribute, prefix = "android")]
+    pub version: Option<VarOr<u32>>,
+    /// The upper 32 bits of the long version of the library.
+    #[yaserde(attribute, prefix = "android", rename = "versionMajor")]
+    pub version_major: Option<VarOr<u32>>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<StaticLibrary>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
@@ -1,3 +1,4 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
 use serde::{Deserialize, Serialize};
 
 /// Declares a single GL texture compression format that the app supports.
@@ -85,7 +86,19 @@ pub struct SupportsGlTexture {
     /// SGX530/540 GPU, such as Motorola DROID series; Samsung Galaxy S, Nexus
     /// S, and Galaxy Tab; and others.
     #[yaserde(attribute, prefix = "android")]
-    pub name: Option<SupportsGlTextureName>,
+    pub name: Option<VarOr<SupportsGlTextureName>>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<SupportsGlTexture>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 #[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone)]
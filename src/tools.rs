@@ -0,0 +1,142 @@
+use crate::xml_tree::{XmlElement, XmlNode};
+use crate::{AttributeList, Comma};
+use serde::{Deserialize, Serialize};
+
+/// Namespace URI of the `tools:` prefix.
+pub const TOOLS_NAMESPACE: &str = "http://schemas.android.com/tools";
+
+/// Markers of the `tools:` namespace, available on every manifest element.
+///
+/// They aren't part of the compiled app, instead they tell the manifest merger how to
+/// resolve conflicts between the manifests of an app and its libraries, and tell Lint
+/// what to ignore.
+///
+/// ## XML Syntax
+/// ```xml
+/// <manifest xmlns:android="http://schemas.android.com/apk/res/android"
+///           xmlns:tools="http://schemas.android.com/tools">
+///     <uses-sdk tools:overrideLibrary="com.example.lib1, com.example.lib2" />
+///     <application tools:replace="android:label">
+///         <activity tools:node="remove" />
+///     </application>
+/// </manifest>
+/// ```
+///
+/// See [`Merge multiple manifest files`] for how the markers drive the merge.
+///
+/// [`Merge multiple manifest files`]: https://developer.android.com/build/manage-manifests#merge_rule_markers
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct ToolsAttributes {
+    /// How the element is merged with lower priority elements of the same key.
+    #[yaserde(attribute, prefix = "tools")]
+    pub node: Option<ToolsNode>,
+    /// Attributes of lower priority elements replaced by the values of this element
+    /// instead of conflicting with them, like `android:icon,android:label`.
+    #[yaserde(
+        attribute,
+        prefix = "tools",
+        skip_serializing_if = "check_attribute_list"
+    )]
+    #[serde(default, skip_serializing_if = "AttributeList::is_empty")]
+    pub replace: AttributeList<Comma, String>,
+    /// Attributes removed from the merged element.
+    #[yaserde(
+        attribute,
+        prefix = "tools",
+        skip_serializing_if = "check_attribute_list"
+    )]
+    #[serde(default, skip_serializing_if = "AttributeList::is_empty")]
+    pub remove: AttributeList<Comma, String>,
+    /// API level, as an integer or a code name, the element is used from. Only used by
+    /// Lint.
+    #[yaserde(attribute, prefix = "tools", rename = "targetApi")]
+    pub target_api: Option<String>,
+    /// Lint issue IDs ignored for the element, like `ProtectedPermissions`.
+    #[yaserde(
+        attribute,
+        prefix = "tools",
+        skip_serializing_if = "check_attribute_list"
+    )]
+    #[serde(default, skip_serializing_if = "AttributeList::is_empty")]
+    pub ignore: AttributeList<Comma, String>,
+    /// Packages of libraries allowed to have a higher `minSdkVersion` than the app. Only
+    /// meaningful on [`<uses-sdk>`].
+    ///
+    /// [`<uses-sdk>`]: crate::UsesSdk
+    #[yaserde(
+        attribute,
+        prefix = "tools",
+        rename = "overrideLibrary",
+        skip_serializing_if = "check_attribute_list"
+    )]
+    #[serde(default, skip_serializing_if = "AttributeList::is_empty")]
+    pub override_library: AttributeList<Comma, String>,
+}
+
+impl ToolsAttributes {
+    pub fn is_empty(&self) -> bool {
+        self == &ToolsAttributes::default()
+    }
+
+    fn check_attribute_list(&self, value: &AttributeList<Comma, String>) -> bool {
+        value.is_empty()
+    }
+}
+
+/// Removes the declaration of the `tools:` namespace from the serialized manifest `text`
+/// when no element has `tools:` attributes, so that manifests without markers are written
+/// the way they were before markers were modelled.
+pub(crate) fn remove_unused_tools_namespace(text: String) -> String {
+    let declaration = format!(r#" xmlns:tools="{}""#, TOOLS_NAMESPACE);
+    match XmlElement::parse(&text) {
+        Ok(root) if !uses_tools_prefix(&root) => text.replacen(&declaration, "", 1),
+        _ => text,
+    }
+}
+
+fn uses_tools_prefix(element: &XmlElement) -> bool {
+    element
+        .attributes
+        .iter()
+        .any(|attribute| attribute.prefix.as_deref() == Some("tools"))
+        || element.children.iter().any(|child| match child {
+            XmlNode::Element(child) => uses_tools_prefix(child),
+            XmlNode::Text(_) => false,
+        })
+}
+
+/// Merge rule of the `tools:node` marker.
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone, Default,
+)]
+pub enum ToolsNode {
+    /// Merge all attributes and child elements with the lower priority element, as long
+    /// as they don't conflict. This is the default.
+    #[yaserde(rename = "merge")]
+    #[serde(rename = "merge")]
+    #[default]
+    Merge,
+    /// Merge attributes only, child elements of the lower priority element are dropped.
+    #[yaserde(rename = "merge-only-attributes")]
+    #[serde(rename = "merge-only-attributes")]
+    MergeOnlyAttributes,
+    /// Remove the element from the merged manifest.
+    #[yaserde(rename = "remove")]
+    #[serde(rename = "remove")]
+    Remove,
+    /// Remove all elements of the same type from the merged manifest, regardless of
+    /// their key.
+    #[yaserde(rename = "removeAll")]
+    #[serde(rename = "removeAll")]
+    RemoveAll,
+    /// Replace the lower priority element entirely.
+    #[yaserde(rename = "replace")]
+    #[serde(rename = "replace")]
+    Replace,
+    /// Fail the merge if the lower priority element isn't exactly the same.
+    #[yaserde(rename = "strict")]
+    #[serde(rename = "strict")]
+    Strict,
+}
@@ -1,6 +1,7 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes};
 use serde::{Deserialize, Serialize};
 
-use crate::VarOrBool;
+use crate::{VarOr, VarOrBool};
 
 /// Indicates what hardware and software features the application requires.
 ///
@@ -65,7 +66,7 @@ pub struct UsesConfiguration {
     /// keyboard of a certain type is required, specify the type here and also set the
     /// reqHardKeyboard attribute to `"true"`.
     #[yaserde(attribute, prefix = "android", rename = "reqKeyboardType")]
-    pub req_keyboard_type: Option<ReqKeyboardType>,
+    pub req_keyboard_type: Option<VarOr<ReqKeyboardType>>,
     /// The navigation device required by the application, if any.
     ///
     /// If an application requires a navigational control, but the exact type of
@@ -74,10 +75,22 @@ pub struct UsesConfiguration {
     ///
     /// [`reqFiveWayNav`]: crate::UsesConfiguration#structfield.req_five_way_nav
     #[yaserde(attribute, prefix = "android", rename = "reqNavigation")]
-    pub req_navigation: Option<ReqNavigation>,
+    pub req_navigation: Option<VarOr<ReqNavigation>>,
     /// The type of touch screen the application requires, if any at all.
     #[yaserde(attribute, prefix = "android", rename = "reqTouchScreen")]
-    pub req_touch_screen: Option<ReqTouchScreen>,
+    pub req_touch_screen: Option<VarOr<ReqTouchScreen>>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<UsesConfiguration>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
 
 /// The type of keyboard the application requires, if any at all.
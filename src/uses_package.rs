@@ -0,0 +1,66 @@
+use crate::{AdditionalCertificate, ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
+use serde::{Deserialize, Serialize};
+
+/// Specifies a package that the application depends on, like a [`<static-library>`] or
+/// another package type the installer checks.
+///
+/// ## XML Syntax
+/// ```xml
+/// <uses-package android:packageType="string"
+///               android:name="string"
+///               android:version="integer"
+///               android:versionMajor="integer"
+///               android:certDigest="string" >
+///     ...
+/// </uses-package>
+/// ```
+///
+/// ## Contained in
+/// * [`<application>`]
+///
+/// ## Can contain
+/// * [`<additional-certificate>`]
+///
+/// ## Introduced in
+/// API Level 28
+///
+/// [`<static-library>`]: crate::StaticLibrary
+/// [`<application>`]: crate::Application
+/// [`<additional-certificate>`]: crate::AdditionalCertificate
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct UsesPackage {
+    /// The type of the package, like `"staticLibrary"`.
+    #[yaserde(attribute, prefix = "android", rename = "packageType")]
+    pub package_type: Option<String>,
+    /// The name of the package.
+    #[yaserde(attribute, prefix = "android")]
+    pub name: Option<String>,
+    /// The lower 32 bits of the long version of the package.
+    #[yaserde(attribute, prefix = "android")]
+    pub version: Option<VarOr<u32>>,
+    /// The upper 32 bits of the long version of the package.
+    #[yaserde(attribute, prefix = "android", rename = "versionMajor")]
+    pub version_major: Option<VarOr<u32>>,
+    /// The SHA-256 digest of the signing certificate of the package, as hexadecimal
+    /// bytes optionally separated by colons.
+    #[yaserde(attribute, prefix = "android", rename = "certDigest")]
+    pub cert_digest: Option<String>,
+    /// List of `<additional-certificate>` tags.
+    #[yaserde(rename = "additional-certificate")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_certificate: Vec<AdditionalCertificate>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<UsesPackage>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
@@ -1,3 +1,4 @@
+use crate::{AttributeList, ExtraAttributes, ExtraElements, ToolsAttributes, VarOr, VerticalBar};
 use serde::{Deserialize, Serialize};
 
 /// Specifies a system permission that the user must grant in order for the app
@@ -30,7 +31,8 @@ use serde::{Deserialize, Serialize};
 /// ## XML Syntax
 /// ```xml
 /// <uses-permission android:name="string"
-///          android:maxSdkVersion="integer" />
+///          android:maxSdkVersion="integer"
+///          android:usesPermissionFlags="neverForLocation" />
 /// ```
 ///
 /// ## Contained in
@@ -83,5 +85,51 @@ pub struct UsesPermission {
     /// [`WRITE_EXTERNAL_STORAGE`]: https://developer.android.com/reference/android/Manifest.permission#WRITE_EXTERNAL_STORAGE
     /// [`getExternalFilesDir()`]: https://developer.android.com/reference/android/content/Context#getExternalFilesDir(java.lang.String)
     #[yaserde(attribute, prefix = "android", rename = "maxSdkVersion")]
-    pub max_sdk_version: Option<u32>,
+    pub max_sdk_version: Option<VarOr<u32>>,
+    /// Flags about how the app uses the permission, separated by `|`.
+    ///
+    /// This attribute was added in API level 31.
+    #[yaserde(
+        attribute,
+        prefix = "android",
+        rename = "usesPermissionFlags",
+        skip_serializing_if = "check_uses_permission_flags"
+    )]
+    #[serde(default, skip_serializing_if = "AttributeList::is_empty")]
+    pub uses_permission_flags: AttributeList<VerticalBar, UsesPermissionFlags>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<UsesPermission>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
+
+impl UsesPermission {
+    fn check_uses_permission_flags(
+        &self,
+        value: &AttributeList<VerticalBar, UsesPermissionFlags>,
+    ) -> bool {
+        value.is_empty()
+    }
+}
+
+/// A flag about how an app uses a permission it requests.
+#[derive(Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Default)]
+pub enum UsesPermissionFlags {
+    /// The app never derives the physical location of the device from the results of
+    /// the permission, like for `BLUETOOTH_SCAN` and `NEARBY_WIFI_DEVICES`. The system
+    /// then filters out of the results the devices that could reveal the location,
+    /// like beacons.
+    #[yaserde(rename = "neverForLocation")]
+    #[default]
+    NeverForLocation,
 }
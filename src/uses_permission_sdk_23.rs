@@ -1,3 +1,4 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
 use serde::{Deserialize, Serialize};
 
 /// Specifies that an app wants a particular permission.
@@ -60,5 +61,17 @@ pub struct UsesPermissionSdk23 {
     /// app is not granted the permission and cannot use any related
     /// functionality.
     #[yaserde(attribute, prefix = "android", rename = "maxSdkVersion")]
-    pub max_sdk_version: Option<u32>,
+    pub max_sdk_version: Option<VarOr<u32>>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<UsesPermissionSdk23>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
@@ -1,3 +1,4 @@
+use crate::{ExtensionSdk, ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
 use serde::{Deserialize, Serialize};
 
 /// Lets you express an application's compatibility with one or more versions of the
@@ -20,18 +21,24 @@ use serde::{Deserialize, Serialize};
 /// ```xml
 /// <uses-sdk android:minSdkVersion="integer"
 ///           android:targetSdkVersion="integer"
-///           android:maxSdkVersion="integer" />
+///           android:maxSdkVersion="integer" >
+///     ...
+/// </uses-sdk>
 /// ```
 ///
 /// ## Contained in
 /// [`<manifest>`]
 ///
+/// ## Can contain
+/// * [`<extension-sdk>`]
+///
 /// ## introduced in
 /// API Level 1
 ///
 /// [`Versioning Your Applications.`]: https://developer.android.com/studio/publish/versioning
 /// [`Google Play filters`]: https://developer.android.com/google/play/filters
 /// [`<manifest>`]: crate::AndroidManifest
+/// [`<extension-sdk>`]: crate::ExtensionSdk
 #[derive(
     Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
 )]
@@ -51,7 +58,7 @@ pub struct UsesSdk {
     /// access the unavailable APIs. For this reason, be certain to declare the
     /// appropriate API Level in the `minSdkVersion` attribute.
     #[yaserde(attribute, prefix = "android", rename = "minSdkVersion")]
-    pub min_sdk_version: Option<u32>,
+    pub min_sdk_version: Option<VarOr<u32>>,
     /// An integer designating the API Level that the application targets. If not set, the
     /// default value equals that given to `minSdkVersion`. This attribute informs the
     /// system that you have tested against the target version and the system should
@@ -83,7 +90,7 @@ pub struct UsesSdk {
     /// [`screen compatibility mode`]: https://developer.android.com/guide/topics/manifest/supports-screens-element#compat-mode
     /// [`Build.VERSION_CODES`]: https://developer.android.com/reference/android/os/Build.VERSION_CODES
     #[yaserde(attribute, prefix = "android", rename = "targetSdkVersion")]
-    pub target_sdk_version: Option<u32>,
+    pub target_sdk_version: Option<VarOr<u32>>,
     /// An integer designating the maximum API Level on which the application is designed
     /// to run. In Android 1.5, 1.6, 2.0, and 2.0.1, the system checks the value of
     /// this attribute when installing an application and when re-validating the
@@ -130,5 +137,21 @@ pub struct UsesSdk {
     /// will continue to use the attribute as a filter, however, when presenting users
     /// with applications available for download.
     #[yaserde(attribute, prefix = "android", rename = "maxSdkVersion")]
-    pub max_sdk_version: Option<u32>,
+    pub max_sdk_version: Option<VarOr<u32>>,
+    /// List of `<extension-sdk>` tags.
+    #[yaserde(rename = "extension-sdk")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extension_sdk: Vec<ExtensionSdk>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<UsesSdk>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
 }
@@ -0,0 +1,50 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
+use serde::{Deserialize, Serialize};
+
+/// Specifies an [`<sdk-library>`] that the application must be linked against.
+///
+/// The application can't be installed unless a package declaring the library, with the
+/// same major version and signed with the certificate of `certDigest`, is installed.
+///
+/// ## XML Syntax
+/// ```xml
+/// <uses-sdk-library android:name="string"
+///                   android:versionMajor="integer"
+///                   android:certDigest="string" />
+/// ```
+///
+/// ## Contained in
+/// * [`<application>`]
+///
+/// ## Introduced in
+/// API Level 33
+///
+/// [`<sdk-library>`]: crate::SdkLibrary
+/// [`<application>`]: crate::Application
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct UsesSdkLibrary {
+    /// The name of the library.
+    #[yaserde(attribute, prefix = "android")]
+    pub name: Option<String>,
+    /// The major version of the library.
+    #[yaserde(attribute, prefix = "android", rename = "versionMajor")]
+    pub version_major: Option<VarOr<u32>>,
+    /// The SHA-256 digest of the signing certificate of the library, as hexadecimal
+    /// bytes optionally separated by colons.
+    #[yaserde(attribute, prefix = "android", rename = "certDigest")]
+    pub cert_digest: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<UsesSdkLibrary>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
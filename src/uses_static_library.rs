@@ -0,0 +1,98 @@
+use crate::{ExtraAttributes, ExtraElements, ToolsAttributes, VarOr};
+use serde::{Deserialize, Serialize};
+
+/// Specifies a [`<static-library>`] that the application must be linked against.
+///
+/// The application can't be installed unless the exact version of the library, signed
+/// with the certificate of `certDigest`, is installed.
+///
+/// ## XML Syntax
+/// ```xml
+/// <uses-static-library android:name="string"
+///                      android:version="integer"
+///                      android:certDigest="string" >
+///     ...
+/// </uses-static-library>
+/// ```
+///
+/// ## Contained in
+/// * [`<application>`]
+///
+/// ## Can contain
+/// * [`<additional-certificate>`]
+///
+/// ## Introduced in
+/// API Level 26
+///
+/// [`<static-library>`]: crate::StaticLibrary
+/// [`<application>`]: crate::Application
+/// [`<additional-certificate>`]: crate::AdditionalCertificate
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct UsesStaticLibrary {
+    /// The name of the library.
+    #[yaserde(attribute, prefix = "android")]
+    pub name: Option<String>,
+    /// The version of the library.
+    #[yaserde(attribute, prefix = "android")]
+    pub version: Option<VarOr<u32>>,
+    /// The SHA-256 digest of the signing certificate of the library, as hexadecimal
+    /// bytes optionally separated by colons.
+    #[yaserde(attribute, prefix = "android", rename = "certDigest")]
+    pub cert_digest: Option<String>,
+    /// List of `<additional-certificate>` tags.
+    #[yaserde(rename = "additional-certificate")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_certificate: Vec<AdditionalCertificate>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<UsesStaticLibrary>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
+
+/// Another signing certificate accepted for the library or package required by the
+/// parent element, for libraries signed with several certificates.
+///
+/// ## XML Syntax
+/// ```xml
+/// <additional-certificate android:certDigest="string" />
+/// ```
+///
+/// ## Contained in
+/// * [`<uses-static-library>`]
+/// * [`<uses-package>`]
+///
+/// ## Introduced in
+/// API Level 28
+///
+/// [`<uses-static-library>`]: crate::UsesStaticLibrary
+/// [`<uses-package>`]: crate::UsesPackage
+#[derive(
+    Debug, Deserialize, Serialize, YaSerialize, YaDeserialize, PartialEq, Eq, Default, Clone,
+)]
+pub struct AdditionalCertificate {
+    /// The SHA-256 digest of the certificate.
+    #[yaserde(attribute, prefix = "android", rename = "certDigest")]
+    pub cert_digest: Option<String>,
+    /// Merge rule markers of the `tools:` namespace.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ToolsAttributes::is_empty")]
+    pub tools: ToolsAttributes,
+    /// Attributes unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraAttributes::is_empty")]
+    pub extra_attributes: ExtraAttributes<AdditionalCertificate>,
+    /// Child elements unknown to this crate, written back as they were.
+    #[yaserde(flatten)]
+    #[serde(default, skip_serializing_if = "ExtraElements::is_empty")]
+    pub extra_elements: ExtraElements,
+}
@@ -0,0 +1,674 @@
+//! Semantic checks of a manifest, for mistakes that the types can't rule out but that
+//! make the build, the installation or the app itself fail.
+
+use crate::platform_permissions::misspelled_platform_permission;
+use crate::{
+    AndroidManifest, ForegroundServiceType, IntentFilter, Service, VarOr, VarOrBool,
+    PROPERTY_SPECIAL_USE_FGS_SUBTYPE,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+const FOREGROUND_SERVICE: &str = "android.permission.FOREGROUND_SERVICE";
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    /// The manifest is rejected by the build tools or the package manager.
+    Error,
+    /// The manifest is accepted but likely doesn't do what was intended.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => f.write_str("error"),
+            Self::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// A check run by [`validate`]. Each rule has a stable [`id`](Rule::id) that can be used
+/// to filter or suppress its diagnostics.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Rule {
+    /// An activity, activity alias, service or receiver with intent filters doesn't set
+    /// `android:exported`, which apps targeting Android 12 (API level 31) or higher must
+    /// do.
+    MissingExported,
+    /// `android:maxSdkVersion` of `<uses-sdk>` is lower than `android:minSdkVersion` or
+    /// `android:targetSdkVersion`.
+    InvalidSdkRange,
+    /// `android:targetSdkVersion` is lower than `android:minSdkVersion`.
+    TargetSdkBelowMinSdk,
+    /// A component or permission lacks its required `android:name`.
+    MissingName,
+    /// Two components of the same kind have the same class name.
+    DuplicateComponent,
+    /// An `<activity-alias>` points to an activity that isn't declared in the manifest.
+    MissingTargetActivity,
+    /// A `<provider>` has no `android:authorities`.
+    MissingAuthority,
+    /// Two providers declare the same authority.
+    DuplicateAuthority,
+    /// The same permission is requested more than once.
+    DuplicateUsesPermission,
+    /// The same permission is declared more than once.
+    DuplicatePermission,
+    /// An `<intent-filter>` without any `<action>`, which no intent can match.
+    IntentFilterWithoutAction,
+    /// A service with a foreground service type doesn't have the permissions required
+    /// to start it in the foreground: `android.permission.FOREGROUND_SERVICE` from
+    /// Android 9 (API level 28), and the permission of each type from Android 14 (API
+    /// level 34). Both are checked when the target SDK version isn't set, as the build
+    /// sets a recent one.
+    MissingForegroundServicePermission,
+    /// A `specialUse` foreground service lacks the
+    /// `android.app.PROPERTY_SPECIAL_USE_FGS_SUBTYPE` property describing its use case.
+    MissingSpecialUseProperty,
+    /// A requested `android.permission.` permission isn't a platform permission but is
+    /// close to one, likely a typo.
+    MisspelledPermission,
+}
+
+impl Rule {
+    /// Stable identifier of the rule, like `MissingExported`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::MissingExported => "MissingExported",
+            Self::InvalidSdkRange => "InvalidSdkRange",
+            Self::TargetSdkBelowMinSdk => "TargetSdkBelowMinSdk",
+            Self::MissingName => "MissingName",
+            Self::DuplicateComponent => "DuplicateComponent",
+            Self::MissingTargetActivity => "MissingTargetActivity",
+            Self::MissingAuthority => "MissingAuthority",
+            Self::DuplicateAuthority => "DuplicateAuthority",
+            Self::DuplicateUsesPermission => "DuplicateUsesPermission",
+            Self::DuplicatePermission => "DuplicatePermission",
+            Self::IntentFilterWithoutAction => "IntentFilterWithoutAction",
+            Self::MissingForegroundServicePermission => "MissingForegroundServicePermission",
+            Self::MissingSpecialUseProperty => "MissingSpecialUseProperty",
+            Self::MisspelledPermission => "MisspelledPermission",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::TargetSdkBelowMinSdk
+            | Self::DuplicateUsesPermission
+            | Self::DuplicatePermission
+            | Self::IntentFilterWithoutAction
+            | Self::MissingForegroundServicePermission
+            | Self::MissingSpecialUseProperty
+            | Self::MisspelledPermission => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// A problem found by [`validate`].
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub message: String,
+    /// Path of the offending element, like `manifest/application/activity[2]`. Elements
+    /// are numbered from 1 when there are several of the same kind.
+    pub path: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {} [{}]",
+            self.path, self.severity, self.message, self.rule
+        )
+    }
+}
+
+/// Checks a manifest for mistakes the Android build tools, package manager or runtime
+/// would reject or silently ignore.
+///
+/// ```
+/// # use android_manifest::*;
+/// let manifest = from_str(r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+///     <uses-sdk android:minSdkVersion="24" android:maxSdkVersion="21" />
+///     <application />
+/// </manifest>"#).unwrap();
+/// let diagnostics = validate(&manifest);
+/// assert_eq!(diagnostics[0].rule, Rule::InvalidSdkRange);
+/// ```
+pub fn validate(manifest: &AndroidManifest) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        manifest,
+        diagnostics: Vec::new(),
+    };
+    validator.uses_sdk();
+    validator.components();
+    validator.providers();
+    validator.permissions();
+    validator.diagnostics
+}
+
+struct Validator<'a> {
+    manifest: &'a AndroidManifest,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Value of an SDK version attribute, unless it's missing or a placeholder.
+fn value(version: &Option<VarOr<u32>>) -> Option<u32> {
+    version.as_ref().and_then(VarOr::value).copied()
+}
+
+/// Target SDK version of the manifest, which defaults to its minimum SDK version and
+/// then to 1.
+pub(crate) fn target_sdk_version(manifest: &AndroidManifest) -> u32 {
+    let uses_sdk = manifest.uses_sdk.as_ref();
+    uses_sdk
+        .and_then(|uses_sdk| {
+            value(&uses_sdk.target_sdk_version).or(value(&uses_sdk.min_sdk_version))
+        })
+        .unwrap_or(1)
+}
+
+/// Target SDK version set in the manifest, or `None` when it's missing or a placeholder,
+/// like in source manifests where the build injects it.
+pub(crate) fn declared_target_sdk_version(manifest: &AndroidManifest) -> Option<u32> {
+    value(&manifest.uses_sdk.as_ref()?.target_sdk_version)
+}
+
+/// Path of the `index`th of `count` elements named `name`.
+pub(crate) fn path(parent: &str, name: &str, index: usize, count: usize) -> String {
+    match count {
+        1 => format!("{}/{}", parent, name),
+        _ => format!("{}/{}[{}]", parent, name, index + 1),
+    }
+}
+
+impl Validator<'_> {
+    fn report(&mut self, rule: Rule, path: String, message: String) {
+        self.diagnostics.push(Diagnostic {
+            rule,
+            severity: rule.severity(),
+            message,
+            path,
+        });
+    }
+
+    fn target_sdk_version(&self) -> u32 {
+        target_sdk_version(self.manifest)
+    }
+
+    fn uses_sdk(&mut self) {
+        let uses_sdk = match &self.manifest.uses_sdk {
+            Some(uses_sdk) => uses_sdk,
+            None => return,
+        };
+        let path = "manifest/uses-sdk".to_string();
+        let min = value(&uses_sdk.min_sdk_version).unwrap_or(1);
+        let target = value(&uses_sdk.target_sdk_version);
+        if let Some(max) = value(&uses_sdk.max_sdk_version) {
+            if max < min {
+                let message = format!("maxSdkVersion {} is lower than minSdkVersion {}", max, min);
+                self.report(Rule::InvalidSdkRange, path.clone(), message);
+            }
+            if let Some(target) = target.filter(|target| max < *target) {
+                let message = format!(
+                    "maxSdkVersion {} is lower than targetSdkVersion {}",
+                    max, target
+                );
+                self.report(Rule::InvalidSdkRange, path.clone(), message);
+            }
+        }
+        if let Some(target) = target.filter(|target| *target < min) {
+            let message = format!(
+                "targetSdkVersion {} is lower than minSdkVersion {}",
+                target, min
+            );
+            self.report(Rule::TargetSdkBelowMinSdk, path, message);
+        }
+    }
+
+    fn components(&mut self) {
+        let application = &self.manifest.application;
+        let parent = "manifest/application";
+        let mut activities = Vec::new();
+        let count = application.activity.len();
+        for (index, activity) in application.activity.iter().enumerate() {
+            let path = path(parent, "activity", index, count);
+            let name = Some(activity.name.as_str()).filter(|name| !name.is_empty());
+            self.component(&path, name, &activity.exported, &activity.intent_filter);
+            activities.push((name, path));
+        }
+        let count = application.activity_alias.len();
+        let mut aliases = Vec::new();
+        for (index, alias) in application.activity_alias.iter().enumerate() {
+            let path = path(parent, "activity-alias", index, count);
+            let name = alias.name.as_deref().filter(|name| !name.is_empty());
+            self.component(&path, name, &alias.exported, &alias.intent_filter);
+            if let Some(target) = &alias.target_activity {
+                let target = self.manifest.qualified_class_name(target);
+                let declared = activities
+                    .iter()
+                    .filter_map(|(name, _)| *name)
+                    .any(|name| self.manifest.qualified_class_name(name) == target);
+                if !declared {
+                    let message = format!("target activity `{}` is not declared", target);
+                    self.report(Rule::MissingTargetActivity, path.clone(), message);
+                }
+            }
+            aliases.push((name, path));
+        }
+        activities.extend(aliases);
+        self.duplicate_components("activity", activities);
+
+        let mut services = Vec::new();
+        let count = application.service.len();
+        for (index, service) in application.service.iter().enumerate() {
+            let path = path(parent, "service", index, count);
+            let name = Some(service.name.as_str()).filter(|name| !name.is_empty());
+            self.component(&path, name, &service.exported, &service.intent_filter);
+            self.foreground_service(&path, service);
+            services.push((name, path));
+        }
+        self.duplicate_components("service", services);
+
+        let mut receivers = Vec::new();
+        let count = application.receiver.len();
+        for (index, receiver) in application.receiver.iter().enumerate() {
+            let path = path(parent, "receiver", index, count);
+            let name = Some(receiver.name.as_str()).filter(|name| !name.is_empty());
+            self.component(&path, name, &receiver.exported, &receiver.intent_filter);
+            receivers.push((name, path));
+        }
+        self.duplicate_components("receiver", receivers);
+    }
+
+    /// Checks what activities, activity aliases, services and receivers have in common.
+    fn component(
+        &mut self,
+        path: &str,
+        name: Option<&str>,
+        exported: &Option<VarOrBool>,
+        intent_filters: &[IntentFilter],
+    ) {
+        if name.is_none() {
+            let message = "component has no android:name".to_string();
+            self.report(Rule::MissingName, path.to_string(), message);
+        }
+        if exported.is_none() && !intent_filters.is_empty() && self.target_sdk_version() >= 31 {
+            let message = "components with intent filters must set android:exported when \
+                targeting Android 12 (API level 31) or higher"
+                .to_string();
+            self.report(Rule::MissingExported, path.to_string(), message);
+        }
+        self.intent_filters(path, intent_filters);
+    }
+
+    fn foreground_service(&mut self, path: &str, service: &Service) {
+        let types = service.foreground_service_type.vec();
+        if types.is_empty() {
+            return;
+        }
+        let target_sdk_version = declared_target_sdk_version(self.manifest);
+        let targets =
+            |api_level: u32| target_sdk_version.is_none_or(|version| version >= api_level);
+        let mut permissions = Vec::new();
+        if targets(28) {
+            permissions.push(FOREGROUND_SERVICE);
+        }
+        if targets(34) {
+            permissions.extend(types.iter().filter_map(ForegroundServiceType::permission));
+        }
+        for permission in permissions {
+            if !self.requests_permission(permission) {
+                let message = format!(
+                    "foreground service requires the `{}` permission",
+                    permission
+                );
+                self.report(
+                    Rule::MissingForegroundServicePermission,
+                    path.to_string(),
+                    message,
+                );
+            }
+        }
+        let has_subtype = service
+            .property
+            .iter()
+            .any(|property| property.name.as_deref() == Some(PROPERTY_SPECIAL_USE_FGS_SUBTYPE));
+        if types.contains(&ForegroundServiceType::SpecialUse) && !has_subtype {
+            let message = format!(
+                "specialUse foreground service has no `{}` property",
+                PROPERTY_SPECIAL_USE_FGS_SUBTYPE
+            );
+            self.report(Rule::MissingSpecialUseProperty, path.to_string(), message);
+        }
+    }
+
+    fn requests_permission(&self, permission: &str) -> bool {
+        let manifest = self.manifest;
+        manifest
+            .uses_permission
+            .iter()
+            .map(|uses_permission| &uses_permission.name)
+            .chain(
+                manifest
+                    .uses_permission_sdk_23
+                    .iter()
+                    .map(|uses_permission| &uses_permission.name),
+            )
+            .any(|name| name.as_deref() == Some(permission))
+    }
+
+    fn intent_filters(&mut self, parent: &str, intent_filters: &[IntentFilter]) {
+        let count = intent_filters.len();
+        for (index, intent_filter) in intent_filters.iter().enumerate() {
+            if intent_filter.action.is_empty() {
+                let path = path(parent, "intent-filter", index, count);
+                let message = "intent filter has no action and can't match any intent".to_string();
+                self.report(Rule::IntentFilterWithoutAction, path, message);
+            }
+        }
+    }
+
+    fn duplicate_components(&mut self, kind: &str, components: Vec<(Option<&str>, String)>) {
+        let mut seen = HashMap::new();
+        for (name, path) in components {
+            let name = match name {
+                Some(name) => self.manifest.qualified_class_name(name),
+                None => continue,
+            };
+            match seen.get(&name) {
+                Some(first) => {
+                    let message = format!("{} `{}` is already declared at `{}`", kind, name, first);
+                    self.report(Rule::DuplicateComponent, path, message);
+                }
+                None => {
+                    seen.insert(name, path);
+                }
+            }
+        }
+    }
+
+    fn providers(&mut self) {
+        let providers = &self.manifest.application.provider;
+        let mut names = Vec::new();
+        let mut authorities = HashMap::new();
+        for (index, provider) in providers.iter().enumerate() {
+            let path = path("manifest/application", "provider", index, providers.len());
+            let name = Some(provider.name.as_str()).filter(|name| !name.is_empty());
+            if name.is_none() {
+                let message = "provider has no android:name".to_string();
+                self.report(Rule::MissingName, path.clone(), message);
+            }
+            if provider.authorities.is_empty() {
+                let message = "provider has no android:authorities".to_string();
+                self.report(Rule::MissingAuthority, path.clone(), message);
+            }
+            for authority in provider.authorities.vec() {
+                match authorities.get(authority) {
+                    Some(first) => {
+                        let message = format!(
+                            "authority `{}` is already declared at `{}`",
+                            authority, first
+                        );
+                        self.report(Rule::DuplicateAuthority, path.clone(), message);
+                    }
+                    None => {
+                        authorities.insert(authority, path.clone());
+                    }
+                }
+            }
+            self.intent_filters(&path, &provider.intent_filter);
+            names.push((name, path));
+        }
+        self.duplicate_components("provider", names);
+    }
+
+    fn permissions(&mut self) {
+        let manifest = self.manifest;
+        let mut requested = HashMap::new();
+        let uses_permissions = &manifest.uses_permission;
+        for (index, uses_permission) in uses_permissions.iter().enumerate() {
+            let path = path("manifest", "uses-permission", index, uses_permissions.len());
+            match uses_permission
+                .name
+                .as_deref()
+                .filter(|name| !name.is_empty())
+            {
+                Some(name) => {
+                    if let Some(permission) = misspelled_platform_permission(name) {
+                        let message = format!(
+                            "`{}` is not a platform permission, did you mean `{}`?",
+                            name, permission.name
+                        );
+                        self.report(Rule::MisspelledPermission, path.clone(), message);
+                    }
+                    match requested.get(name) {
+                        Some(first) => {
+                            let message = format!(
+                                "permission `{}` is already requested at `{}`",
+                                name, first
+                            );
+                            self.report(Rule::DuplicateUsesPermission, path, message);
+                        }
+                        None => {
+                            requested.insert(name, path);
+                        }
+                    }
+                }
+                None => {
+                    let message = "uses-permission has no android:name".to_string();
+                    self.report(Rule::MissingName, path, message);
+                }
+            }
+        }
+        let mut declared = HashMap::new();
+        let permissions = &manifest.permission;
+        for (index, permission) in permissions.iter().enumerate() {
+            let path = path("manifest", "permission", index, permissions.len());
+            match permission.name.as_deref().filter(|name| !name.is_empty()) {
+                Some(name) => match declared.get(name) {
+                    Some(first) => {
+                        let message =
+                            format!("permission `{}` is already declared at `{}`", name, first);
+                        self.report(Rule::DuplicatePermission, path, message);
+                    }
+                    None => {
+                        declared.insert(name, path);
+                    }
+                },
+                None => {
+                    let message = "permission has no android:name".to_string();
+                    self.report(Rule::MissingName, path, message);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_manifest() {
+        let given_xml = r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-sdk android:minSdkVersion="21" android:targetSdkVersion="34" />
+    <uses-permission android:name="android.permission.INTERNET" />
+    <uses-permission android:name="android.permission.INTERNET" />
+    <uses-permission android:name="android.permission.FOREGROUND_SERVICE_CAMERA" />
+    <uses-permission android:name="android.permission.RECIEVE_BOOT_COMPLETED" />
+    <uses-permission android:name="android.permission.ACCESS_ADSERVICES_AD_ID" />
+    <uses-permission android:name="android.permission.CREDENTIAL_MANAGER_SET_ORIGIN" />
+    <uses-permission android:name="android.permission.READ_PRIVILEGED_PHONE_STATE" />
+    <application>
+        <activity android:name=".MainActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+                <category android:name="android.intent.category.LAUNCHER" />
+            </intent-filter>
+        </activity>
+        <activity android:name="com.example.MainActivity">
+            <intent-filter>
+                <category android:name="android.intent.category.DEFAULT" />
+            </intent-filter>
+        </activity>
+        <activity-alias android:name=".Alias" android:targetActivity=".Missing" android:exported="false" />
+        <service android:name=".CaptureService" android:foregroundServiceType="camera|specialUse|shortService" />
+        <provider android:name=".FirstProvider" android:authorities="com.example.files" />
+        <provider android:name=".SecondProvider" android:authorities="com.example.files" />
+    </application>
+</manifest>"#;
+        let manifest = crate::from_str(given_xml).unwrap();
+        let diagnostics: Vec<(Rule, String)> = validate(&manifest)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.path))
+            .collect();
+        let application = "manifest/application";
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    Rule::MissingExported,
+                    format!("{}/activity[2]", application)
+                ),
+                (
+                    Rule::IntentFilterWithoutAction,
+                    format!("{}/activity[2]/intent-filter", application)
+                ),
+                (
+                    Rule::MissingTargetActivity,
+                    format!("{}/activity-alias", application)
+                ),
+                (
+                    Rule::DuplicateComponent,
+                    format!("{}/activity[2]", application)
+                ),
+                (
+                    Rule::MissingForegroundServicePermission,
+                    format!("{}/service", application)
+                ),
+                (
+                    Rule::MissingForegroundServicePermission,
+                    format!("{}/service", application)
+                ),
+                (
+                    Rule::MissingSpecialUseProperty,
+                    format!("{}/service", application)
+                ),
+                (
+                    Rule::DuplicateAuthority,
+                    format!("{}/provider[2]", application)
+                ),
+                (
+                    Rule::DuplicateUsesPermission,
+                    "manifest/uses-permission[2]".to_string()
+                ),
+                (
+                    Rule::MisspelledPermission,
+                    "manifest/uses-permission[4]".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_foreground_service_rules() {
+        let rules = |target_sdk_version: u32, elements: &str| {
+            let given_xml = format!(
+                r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-sdk android:minSdkVersion="21" android:targetSdkVersion="{}" />
+    {}
+</manifest>"#,
+                target_sdk_version, elements
+            );
+            let manifest = crate::from_str(&given_xml).unwrap();
+            validate(&manifest)
+                .into_iter()
+                .map(|diagnostic| (diagnostic.rule, diagnostic.message))
+                .collect::<Vec<_>>()
+        };
+        let location_service = r#"<application>
+        <service android:name=".LocationService" android:foregroundServiceType="location" />
+    </application>"#;
+        let missing = |permission: &str| {
+            (
+                Rule::MissingForegroundServicePermission,
+                format!(
+                    "foreground service requires the `{}` permission",
+                    permission
+                ),
+            )
+        };
+        assert_eq!(
+            rules(34, location_service),
+            vec![
+                missing(FOREGROUND_SERVICE),
+                missing("android.permission.FOREGROUND_SERVICE_LOCATION"),
+            ]
+        );
+        // Per-type permissions only apply from Android 14 (API level 34).
+        assert_eq!(
+            rules(33, location_service),
+            vec![missing(FOREGROUND_SERVICE)]
+        );
+        // `FOREGROUND_SERVICE` only applies from Android 9 (API level 28).
+        assert_eq!(rules(27, location_service), vec![]);
+        // Without a target SDK version, like in source manifests, both apply.
+        let given_xml = format!(
+            r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example">
+    <uses-sdk android:minSdkVersion="21" />
+    {}
+</manifest>"#,
+            location_service
+        );
+        let diagnostics: Vec<(Rule, String)> = validate(&crate::from_str(&given_xml).unwrap())
+            .into_iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.message))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                missing(FOREGROUND_SERVICE),
+                missing("android.permission.FOREGROUND_SERVICE_LOCATION"),
+            ]
+        );
+        let granted_on_23 = r#"<uses-permission-sdk-23 android:name="android.permission.FOREGROUND_SERVICE" />
+    <uses-permission-sdk-23 android:name="android.permission.FOREGROUND_SERVICE_LOCATION" />"#;
+        assert_eq!(
+            rules(34, &format!("{}\n    {}", granted_on_23, location_service)),
+            vec![]
+        );
+        let special_use = r#"<uses-permission android:name="android.permission.FOREGROUND_SERVICE" />
+    <uses-permission android:name="android.permission.FOREGROUND_SERVICE_SPECIAL_USE" />
+    <application>
+        <service android:name=".SyncService" android:foregroundServiceType="specialUse">
+            <property android:name="android.app.PROPERTY_SPECIAL_USE_FGS_SUBTYPE" android:value="peer-to-peer sync" />
+        </service>
+    </application>"#;
+        assert_eq!(rules(34, special_use), vec![]);
+        let without_property = special_use.replace(
+            r#"<property android:name="android.app.PROPERTY_SPECIAL_USE_FGS_SUBTYPE" android:value="peer-to-peer sync" />"#,
+            "",
+        );
+        assert_eq!(
+            rules(34, &without_property)
+                .into_iter()
+                .map(|(rule, _)| rule)
+                .collect::<Vec<_>>(),
+            vec![Rule::MissingSpecialUseProperty]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Deserializer, Serialize,
+};
+use std::fmt;
+use std::io::{Read, Write};
+use yaserde::{YaDeserialize, YaSerialize};
+
+/// Value of a numeric or enum attribute that can also be a `${placeholder}`, substituted
+/// by the build, like `android:versionCode="${versionCode}"`.
+///
+/// The value is written the way its type serializes with serde, like `singleTask` for
+/// [`LaunchMode::SingleTask`](crate::LaunchMode::SingleTask).
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+#[serde(untagged)]
+pub enum VarOr<T> {
+    #[serde(deserialize_with = "deserialize_var")]
+    Var(String),
+    Value(T),
+}
+
+impl<T> From<T> for VarOr<T> {
+    fn from(value: T) -> Self {
+        Self::Value(value)
+    }
+}
+
+impl<T> VarOr<T> {
+    pub fn var(name: impl Into<String>) -> Self {
+        Self::Var(name.into())
+    }
+
+    /// Returns the value unless it's a placeholder.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Self::Value(value) => Some(value),
+            Self::Var(_) => None,
+        }
+    }
+
+    pub fn is_var(&self) -> bool {
+        matches!(self, Self::Var(_))
+    }
+}
+
+impl<T: Serialize> fmt::Display for VarOr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Var(variable) => f.write_str(variable),
+            Self::Value(value) => {
+                f.write_str(&serde_plain::to_string(value).map_err(|_| fmt::Error)?)
+            }
+        }
+    }
+}
+
+pub(crate) fn is_var(value: &str) -> bool {
+    value.starts_with("${") && value.ends_with('}')
+}
+
+fn deserialize_var<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    match is_var(&value) {
+        true => Ok(value),
+        false => Err(de::Error::custom(format!(
+            "value `{}` is not a variable in the \"${{variable}}\" format",
+            value
+        ))),
+    }
+}
+
+fn parse<T: DeserializeOwned>(value: &str) -> Result<VarOr<T>, String> {
+    if value.is_empty() {
+        return Err("value of attribute is empty".to_string());
+    }
+    if is_var(value) {
+        return Ok(VarOr::Var(value.to_string()));
+    }
+    serde_plain::from_str(value)
+        .map(VarOr::Value)
+        .map_err(|_| format!("value `{}` is not valid", value))
+}
+
+impl<T: Serialize> YaSerialize for VarOr<T> {
+    fn serialize<W: Write>(&self, writer: &mut yaserde::ser::Serializer<W>) -> Result<(), String> {
+        let _ret = writer.write(xml::writer::XmlEvent::characters(&self.to_string()));
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<xml::attribute::OwnedAttribute>,
+        namespace: xml::namespace::Namespace,
+    ) -> Result<
+        (
+            Vec<xml::attribute::OwnedAttribute>,
+            xml::namespace::Namespace,
+        ),
+        String,
+    > {
+        Ok((attributes, namespace))
+    }
+}
+
+impl<T: DeserializeOwned> YaDeserialize for VarOr<T> {
+    fn deserialize<R: Read>(reader: &mut yaserde::de::Deserializer<R>) -> Result<Self, String> {
+        loop {
+            match reader.next_event()? {
+                xml::reader::XmlEvent::StartElement { .. } => {}
+                xml::reader::XmlEvent::Characters(ref v) => return parse(v),
+                _ => break,
+            }
+        }
+        Err("Unable to parse attribute".to_string())
+    }
+}
@@ -0,0 +1,209 @@
+use xml::reader::{EventReader, XmlEvent};
+
+/// Namespace URI of the `android:` prefix.
+pub(crate) const ANDROID_NAMESPACE: &str = "http://schemas.android.com/apk/res/android";
+
+/// Owned XML element used as an intermediate representation when converting between
+/// text XML and other manifest encodings.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub(crate) struct XmlElement {
+    pub name: String,
+    /// Namespace declarations (`xmlns:prefix="uri"`) made on this element.
+    pub namespaces: Vec<(String, String)>,
+    pub attributes: Vec<XmlAttribute>,
+    pub children: Vec<XmlNode>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct XmlAttribute {
+    pub prefix: Option<String>,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum XmlNode {
+    Element(XmlElement),
+    Text(String),
+}
+
+impl XmlElement {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Parses the root element of an XML document. Comments, processing instructions and
+    /// whitespace between elements are dropped.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut stack: Vec<(XmlElement, xml::namespace::Namespace)> = Vec::new();
+        for event in EventReader::from_str(text) {
+            match event.map_err(|e| e.to_string())? {
+                XmlEvent::StartElement {
+                    name,
+                    attributes,
+                    namespace,
+                } => {
+                    let mut element = XmlElement::new(name.local_name);
+                    if let Some(prefix) = name.prefix {
+                        element.name = format!("{}:{}", prefix, element.name);
+                    }
+                    let parent_scope = stack.last().map(|(_, scope)| scope);
+                    for (prefix, uri) in &namespace {
+                        let inherited = parent_scope.and_then(|scope| scope.get(prefix));
+                        let reserved = matches!(prefix, "xml" | "xmlns") || uri.is_empty();
+                        if !reserved && inherited != Some(uri) {
+                            element
+                                .namespaces
+                                .push((prefix.to_string(), uri.to_string()));
+                        }
+                    }
+                    element.attributes = attributes
+                        .into_iter()
+                        .map(|attribute| XmlAttribute {
+                            prefix: attribute.name.prefix,
+                            name: attribute.name.local_name,
+                            value: attribute.value,
+                        })
+                        .collect();
+                    stack.push((element, namespace));
+                }
+                XmlEvent::EndElement { .. } => {
+                    let (element, _) = stack.pop().ok_or("unbalanced end of element")?;
+                    match stack.last_mut() {
+                        Some((parent, _)) => parent.children.push(XmlNode::Element(element)),
+                        None => return Ok(element),
+                    }
+                }
+                XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                    if let Some((parent, _)) = stack.last_mut() {
+                        parent.children.push(XmlNode::Text(text));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Err("XML document has no root element".to_string())
+    }
+
+    /// Returns the value of an attribute.
+    pub fn attribute(&self, prefix: Option<&str>, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.prefix.as_deref() == prefix && a.name == name)
+            .map(|a| a.value.as_str())
+    }
+
+    /// Sets the value of an attribute, adding it if it's missing.
+    pub fn set_attribute(&mut self, prefix: Option<&str>, name: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self
+            .attributes
+            .iter_mut()
+            .find(|a| a.prefix.as_deref() == prefix && a.name == name)
+        {
+            Some(attribute) => attribute.value = value,
+            None => self.attributes.push(XmlAttribute {
+                prefix: prefix.map(str::to_string),
+                name: name.to_string(),
+                value,
+            }),
+        }
+    }
+
+    /// Removes the declarations of the namespace `uri` and all attributes in it, the way
+    /// `aapt2` drops `tools:` attributes.
+    pub fn remove_namespace(&mut self, uri: &str) {
+        self.remove_namespace_prefixes(uri, &mut Vec::new());
+    }
+
+    fn remove_namespace_prefixes(&mut self, uri: &str, prefixes: &mut Vec<String>) {
+        let scope_len = prefixes.len();
+        self.namespaces.retain(|(prefix, namespace)| {
+            if namespace == uri {
+                prefixes.push(prefix.clone());
+            }
+            namespace != uri
+        });
+        self.attributes.retain(|a| {
+            !a.prefix
+                .as_ref()
+                .is_some_and(|prefix| prefixes.contains(prefix))
+        });
+        for child in &mut self.children {
+            if let XmlNode::Element(child) = child {
+                child.remove_namespace_prefixes(uri, prefixes);
+            }
+        }
+        prefixes.truncate(scope_len);
+    }
+
+    /// Returns the child elements, skipping text.
+    pub fn elements(&self) -> impl Iterator<Item = &XmlElement> {
+        self.children.iter().filter_map(|child| match child {
+            XmlNode::Element(element) => Some(element),
+            XmlNode::Text(_) => None,
+        })
+    }
+
+    /// Returns the text of the whole document with an XML declaration.
+    pub fn to_xml_string(&self) -> String {
+        let mut out = String::from(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+        self.write_to(&mut out);
+        out
+    }
+
+    fn write_to(&self, out: &mut String) {
+        out.push('<');
+        out.push_str(&self.name);
+        for (prefix, uri) in &self.namespaces {
+            match prefix.as_str() {
+                "" => out.push_str(&format!(" xmlns=\"{}\"", escape(uri, true))),
+                _ => out.push_str(&format!(" xmlns:{}=\"{}\"", prefix, escape(uri, true))),
+            }
+        }
+        for attribute in &self.attributes {
+            out.push(' ');
+            if let Some(prefix) = &attribute.prefix {
+                out.push_str(prefix);
+                out.push(':');
+            }
+            out.push_str(&attribute.name);
+            out.push_str("=\"");
+            out.push_str(&escape(&attribute.value, true));
+            out.push('"');
+        }
+        if self.children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+        out.push('>');
+        for child in &self.children {
+            match child {
+                XmlNode::Element(element) => element.write_to(out),
+                XmlNode::Text(text) => out.push_str(&escape(text, false)),
+            }
+        }
+        out.push_str("</");
+        out.push_str(&self.name);
+        out.push('>');
+    }
+}
+
+fn escape(value: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            '\n' if attribute => escaped.push_str("&#10;"),
+            '\t' if attribute => escaped.push_str("&#9;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}